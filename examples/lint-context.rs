@@ -0,0 +1,101 @@
+//! This example analyzes a context file and reports likely mistakes.
+//!
+//! Usage: `cargo run --example lint-context -- <context.jsonld>...`
+//!
+//! If the file is a document with a top-level `@context` entry,
+//! the value of this entry is analyzed.
+//! The exit code is `1` if any of the contexts cannot be processed.
+//!
+//! Remote contexts are loaded from the directory of the analyzed file,
+//! which is mounted on its `file:` URL so that relative references resolve against it.
+//! When the `reqwest-loader` feature is enabled, remote contexts are downloaded instead.
+
+use std::{
+	path::Path,
+	process::exit
+};
+use iref::IriBuf;
+use json_ld::context::lint::lint;
+
+#[cfg(not(feature = "reqwest-loader"))]
+fn loader(dir: &Path, url: &IriBuf) -> json_ld::FsLoader {
+	let mut loader = json_ld::FsLoader::new();
+	loader.mount(url.as_iri(), dir);
+	loader
+}
+
+#[cfg(feature = "reqwest-loader")]
+fn loader(_dir: &Path, _url: &IriBuf) -> json_ld::reqwest::Loader {
+	json_ld::reqwest::Loader::new()
+}
+
+/// Returns the `file:` URL of the given directory, ending with a `/`.
+fn directory_url(dir: &Path) -> Option<IriBuf> {
+	let dir = dir.to_str()?.trim_end_matches('/');
+	IriBuf::new(&format!("file://{}/", dir)).ok()
+}
+
+#[cfg_attr(feature = "reqwest-loader", tokio::main)]
+#[cfg_attr(not(feature = "reqwest-loader"), async_std::main)]
+async fn main() {
+	let mut failed = false;
+
+	for path in std::env::args().skip(1) {
+		let dir = match std::fs::canonicalize(&path) {
+			Ok(file) => file.parent().map(Path::to_path_buf).unwrap_or(file),
+			Err(e) => {
+				eprintln!("{}: {}", path, e);
+				exit(2)
+			}
+		};
+
+		let dir_url = match directory_url(&dir) {
+			Some(url) => url,
+			None => {
+				eprintln!("{}: unable to build the URL of {}", path, dir.display());
+				exit(2)
+			}
+		};
+
+		let content = match std::fs::read_to_string(&path) {
+			Ok(content) => content,
+			Err(e) => {
+				eprintln!("{}: {}", path, e);
+				exit(2)
+			}
+		};
+
+		let json = match json::parse(&content) {
+			Ok(json) => json,
+			Err(e) => {
+				eprintln!("{}: invalid JSON: {}", path, e);
+				exit(2)
+			}
+		};
+
+		let context = if json.has_key("@context") {
+			&json["@context"]
+		} else {
+			&json
+		};
+
+		let file_name = Path::new(&path).file_name().and_then(|name| name.to_str()).unwrap_or_default();
+		let base_url = IriBuf::new(&format!("{}{}", dir_url.as_str(), file_name)).ok();
+		let mut loader = loader(&dir, &dir_url);
+
+		for lint in lint::<IriBuf, _>(context, &mut loader, base_url.as_ref().map(IriBuf::as_iri), Default::default()).await {
+			let level = if lint.is_error() {
+				failed = true;
+				"error"
+			} else {
+				"warning"
+			};
+
+			println!("{}: {}: {}", path, level, lint)
+		}
+	}
+
+	if failed {
+		exit(1)
+	}
+}
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use indexmap::IndexSet;
 use futures::future::{BoxFuture, FutureExt};
 use json::JsonValue;
 use crate::{
@@ -116,7 +116,7 @@ impl<T: Sync + Send + Id, N: object::Any<T> + Sync + Send> CompactIndexed<T> for
 				// a single @id entry, set active context to previous context from active context,
 				// as the scope of a term-scoped context does not apply when processing new node objects.
 				if let Some(previous_context) = active_context.previous_context() {
-					active_context = Inversible::new(previous_context)
+					active_context = active_context.derived(previous_context)
 				}
 
 				// If the term definition for active property in active context has a local context:
@@ -127,7 +127,7 @@ impl<T: Sync + Send + Id, N: object::Any<T> + Sync + Send> CompactIndexed<T> for
 				if let Some(active_property) = active_property {
 					if let Some(active_property_definition) = type_scoped_context.get(active_property) {
 						if let Some(local_context) = &active_property_definition.context {
-							active_context = Inversible::new(local_context.process_with(*active_context.as_ref(), loader, active_property_definition.base_url(), context::ProcessingOptions::from(options).with_override()).await?.into_inner()).into_owned()
+							active_context = active_context.scoped(active_property, local_context.process_with(*active_context.as_ref(), loader, active_property_definition.base_url(), context::ProcessingOptions::from(options).with_override()).await?.into_inner()).into_owned()
 						}
 
 						list_container = active_property_definition.container.contains(ContainerType::List);
@@ -248,7 +248,7 @@ fn compact_collection_with<'a, T: 'a + Sync + Send + Id, O: 'a + Send + Iterator
 	}.boxed()
 }
 
-impl<T: Sync + Send + Id> Compact<T> for HashSet<Indexed<Object<T>>> {
+impl<T: Sync + Send + Id> Compact<T> for IndexSet<Indexed<Object<T>>> {
 	fn compact_with<'a, C: ContextMut<T>, L: Loader>(&'a self, active_context: Inversible<T, &'a C>, type_scoped_context: Inversible<T, &'a C>, active_property: Option<&'a str>, loader: &'a mut L, options: Options) -> BoxFuture<'a, Result<JsonValue, Error>> where T: 'a, C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
 		compact_collection_with(self.iter(), active_context, type_scoped_context, active_property, loader, options)
 	}
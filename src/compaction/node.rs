@@ -37,7 +37,7 @@ pub async fn compact_indexed_node_with<T: Sync + Send + Id, C: ContextMut<T>, L:
 	// as the scope of a term-scoped context does not apply when processing new node objects.
 	if !(node.is_empty() && node.id().is_some()) { // does not consist of a single @id entry
 		if let Some(previous_context) = active_context.previous_context() {
-			active_context = Inversible::new(previous_context)
+			active_context = active_context.derived(previous_context)
 		}
 	}
 
@@ -48,7 +48,7 @@ pub async fn compact_indexed_node_with<T: Sync + Send + Id, C: ContextMut<T>, L:
 	if let Some(active_property) = active_property {
 		if let Some(active_property_definition) = type_scoped_context.get(active_property) {
 			if let Some(local_context) = &active_property_definition.context {
-				active_context = Inversible::new(local_context.process_with(*active_context.as_ref(), loader, active_property_definition.base_url(), context::ProcessingOptions::from(options).with_override()).await?.into_inner()).into_owned()
+				active_context = active_context.scoped(active_property, local_context.process_with(*active_context.as_ref(), loader, active_property_definition.base_url(), context::ProcessingOptions::from(options).with_override()).await?.into_inner()).into_owned()
 			}
 		}
 	}
@@ -75,7 +75,7 @@ pub async fn compact_indexed_node_with<T: Sync + Send + Id, C: ContextMut<T>, L:
 			if let Some(term_definition) = type_scoped_context.get(term.as_str().unwrap()) {
 				if let Some(local_context) = &term_definition.context {
 					let processing_options = context::ProcessingOptions::from(options).without_propagation();
-					active_context = Inversible::new(local_context.process_with(*active_context.as_ref(), loader, term_definition.base_url(), processing_options).await?.into_inner()).into_owned()
+					active_context = active_context.scoped(term.as_str().unwrap(), local_context.process_with(*active_context.as_ref(), loader, term_definition.base_url(), processing_options).await?.into_inner()).into_owned()
 				}
 			}
 		}
@@ -150,7 +150,7 @@ pub async fn compact_indexed_node_with<T: Sync + Send + Id, C: ContextMut<T>, L:
 		let active_property = "@reverse";
 		if let Some(active_property_definition) = active_context.get(active_property) {
 			if let Some(local_context) = &active_property_definition.context {
-				active_context = Inversible::new(local_context.process_with(*active_context.as_ref(), loader, active_property_definition.base_url(), context::ProcessingOptions::from(options).with_override()).await?.into_inner()).into_owned()
+				active_context = active_context.scoped(active_property, local_context.process_with(*active_context.as_ref(), loader, active_property_definition.base_url(), context::ProcessingOptions::from(options).with_override()).await?.into_inner()).into_owned()
 			}
 		}
 
@@ -30,7 +30,7 @@ pub async fn compact_indexed_value_with<T: Sync + Send + Id, C: ContextMut<T>, L
 	if let Some(active_property) = active_property {
 		if let Some(active_property_definition) = active_context.get(active_property) {
 			if let Some(local_context) = &active_property_definition.context {
-				active_context = Inversible::new(local_context.process_with(*active_context.as_ref(), loader, active_property_definition.base_url(), context::ProcessingOptions::from(options).with_override()).await?.into_inner()).into_owned()
+				active_context = active_context.scoped(active_property, local_context.process_with(*active_context.as_ref(), loader, active_property_definition.base_url(), context::ProcessingOptions::from(options).with_override()).await?.into_inner()).into_owned()
 			}
 		}
 	}
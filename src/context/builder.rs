@@ -0,0 +1,223 @@
+use langtag::LanguageTagBuf;
+use json::{
+	JsonValue,
+	object::Object as JsonObject
+};
+use crate::{
+	Id,
+	Error,
+	Direction,
+	NoLoader,
+	syntax::{
+		Keyword,
+		ContainerType
+	},
+	util::AsJson
+};
+use super::{
+	Local,
+	JsonContext,
+	Processed
+};
+
+/// Context builder.
+///
+/// Builds a local context programmatically, term by term.
+/// The built context is processed with the
+/// [context processing algorithm](https://www.w3.org/TR/json-ld11-api/#context-processing-algorithm),
+/// just like any JSON context, and can be emitted as a JSON `@context` value using
+/// [`AsJson`].
+///
+/// Definitions of the same term are merged: calling [`term`](ContextBuilder::term)
+/// then [`container`](ContextBuilder::container) on the same term produces a single
+/// expanded term definition.
+///
+/// # Example
+/// ```
+/// use json_ld::{JsonContext, Context, context::ContextBuilder, syntax::ContainerType, xsd::Datatype, util::AsJson};
+/// # use iref::IriBuf;
+/// let builder = ContextBuilder::new()
+///     .vocab("http://schema.org/")
+///     .prefix("schema", "http://schema.org/")
+///     .term("name", "http://schema.org/name")
+///     .typed("born", Datatype::Date)
+///     .container("tags", ContainerType::Set)
+///     .protected();
+///
+/// let context: JsonContext<IriBuf> = builder.build().unwrap();
+/// assert!(context.get("born").unwrap().typ.is_some());
+/// assert!(context.get("tags").unwrap().protected);
+///
+/// assert_eq!(builder.as_json()["tags"]["@container"], "@set");
+/// ```
+#[derive(Clone)]
+pub struct ContextBuilder {
+	context: JsonObject
+}
+
+impl ContextBuilder {
+	/// Creates a new empty context builder.
+	pub fn new() -> ContextBuilder {
+		ContextBuilder {
+			context: JsonObject::new()
+		}
+	}
+
+	fn set(mut self, key: &str, value: JsonValue) -> ContextBuilder {
+		self.context.insert(key, value);
+		self
+	}
+
+	/// Sets an entry of the expanded definition of the given term.
+	fn define(mut self, term: &str, key: Keyword, value: JsonValue) -> ContextBuilder {
+		let mut definition = match self.context.remove(term) {
+			Some(JsonValue::Object(definition)) => definition,
+			Some(JsonValue::String(id)) => {
+				let mut definition = JsonObject::new();
+				definition.insert(Keyword::Id.into_str(), id.into());
+				definition
+			},
+			Some(JsonValue::Short(id)) => {
+				let mut definition = JsonObject::new();
+				definition.insert(Keyword::Id.into_str(), id.as_str().into());
+				definition
+			},
+			_ => JsonObject::new()
+		};
+
+		definition.insert(key.into_str(), value);
+		self.context.insert(term, JsonValue::Object(definition));
+		self
+	}
+
+	/// Sets the base IRI (`@base`).
+	pub fn base(self, iri: impl AsRef<str>) -> ContextBuilder {
+		self.set(Keyword::Base.into_str(), iri.as_ref().into())
+	}
+
+	/// Sets the vocabulary mapping (`@vocab`).
+	pub fn vocab(self, iri: impl AsRef<str>) -> ContextBuilder {
+		self.set(Keyword::Vocab.into_str(), iri.as_ref().into())
+	}
+
+	/// Sets the default language (`@language`).
+	pub fn language(self, lang: LanguageTagBuf) -> ContextBuilder {
+		self.set(Keyword::Language.into_str(), lang.as_str().into())
+	}
+
+	/// Sets the default base direction (`@direction`).
+	pub fn direction(self, dir: Direction) -> ContextBuilder {
+		self.set(Keyword::Direction.into_str(), dir.as_json())
+	}
+
+	/// Sets the propagation flag (`@propagate`).
+	pub fn propagate(self, propagate: bool) -> ContextBuilder {
+		self.set(Keyword::Propagate.into_str(), propagate.into())
+	}
+
+	/// Marks every term definition of the context as protected (`@protected`).
+	pub fn protected(self) -> ContextBuilder {
+		self.set(Keyword::Protected.into_str(), true.into())
+	}
+
+	/// Defines a term mapped to the given IRI, compact IRI or keyword.
+	pub fn term(self, term: &str, iri: impl AsRef<str>) -> ContextBuilder {
+		if self.context.get(term).map(JsonValue::is_object).unwrap_or(false) {
+			self.define(term, Keyword::Id, iri.as_ref().into())
+		} else {
+			self.set(term, iri.as_ref().into())
+		}
+	}
+
+	/// Defines a term usable as a prefix in compact IRIs.
+	pub fn prefix(self, term: &str, iri: impl AsRef<str>) -> ContextBuilder {
+		self.define(term, Keyword::Id, iri.as_ref().into())
+			.define(term, Keyword::Prefix, true.into())
+	}
+
+	/// Defines a reverse property term.
+	pub fn reverse(self, term: &str, iri: impl AsRef<str>) -> ContextBuilder {
+		self.define(term, Keyword::Reverse, iri.as_ref().into())
+	}
+
+	/// Sets the type mapping of a term.
+	///
+	/// The type can be an IRI, such as a [`Datatype`](crate::xsd::Datatype),
+	/// or one of the `@id`, `@vocab`, `@json` and `@none` keywords.
+	pub fn typed(self, term: &str, ty: impl AsRef<str>) -> ContextBuilder {
+		self.define(term, Keyword::Type, ty.as_ref().into())
+	}
+
+	/// Adds a container type to the container mapping of a term.
+	pub fn container(self, term: &str, container: ContainerType) -> ContextBuilder {
+		let container: JsonValue = Keyword::from(container).into_str().into();
+		let current = match self.context.get(term) {
+			Some(JsonValue::Object(definition)) => definition.get(Keyword::Container.into_str()),
+			_ => None
+		};
+
+		let value = match current {
+			Some(JsonValue::Array(current)) => {
+				let mut current = current.clone();
+				current.push(container);
+				JsonValue::Array(current)
+			},
+			Some(current) => JsonValue::Array(vec![current.clone(), container]),
+			None => container
+		};
+
+		self.define(term, Keyword::Container, value)
+	}
+
+	/// Sets the language mapping of a term.
+	///
+	/// `None` means that values of the term have no language.
+	pub fn term_language(self, term: &str, lang: Option<LanguageTagBuf>) -> ContextBuilder {
+		let value = match lang {
+			Some(lang) => lang.as_str().into(),
+			None => JsonValue::Null
+		};
+
+		self.define(term, Keyword::Language, value)
+	}
+
+	/// Sets the scoped context of a term.
+	pub fn scoped(self, term: &str, context: ContextBuilder) -> ContextBuilder {
+		self.define(term, Keyword::Context, context.as_json())
+	}
+
+	/// Marks a single term definition as protected.
+	pub fn protect(self, term: &str) -> ContextBuilder {
+		self.define(term, Keyword::Protected, true.into())
+	}
+
+	/// Processes the built context on top of a new empty active context.
+	///
+	/// The context is validated through the context processing algorithm,
+	/// with the same errors as a JSON context.
+	pub fn build<T: Send + Sync + Id>(&self) -> Result<JsonContext<T>, Error> {
+		Ok(self.build_processed()?.into_inner())
+	}
+
+	/// Processes the built context, attaching it to its JSON representation.
+	///
+	/// The result can be used as a compaction context.
+	pub fn build_processed<T: Send + Sync + Id>(&self) -> Result<Processed<JsonValue, JsonContext<T>>, Error> {
+		let local = self.as_json();
+		let processed = futures::executor::block_on(Local::<T>::process::<JsonContext<T>, _>(&local, &mut NoLoader, None))?.into_inner();
+		Ok(Processed::new(local, processed))
+	}
+}
+
+impl Default for ContextBuilder {
+	fn default() -> ContextBuilder {
+		ContextBuilder::new()
+	}
+}
+
+/// Produces the JSON context object, to be used as the value of an `@context` entry.
+impl AsJson for ContextBuilder {
+	fn as_json(&self) -> JsonValue {
+		JsonValue::Object(self.context.clone())
+	}
+}
@@ -0,0 +1,544 @@
+//! Precompiled context bundles.
+//!
+//! A [`Bundle`] holds a processed context, its original JSON representation and its
+//! inverse context, in a binary form that can be stored and loaded back without running
+//! the context processing algorithm again.
+//!
+//! Each bundle starts with a header holding the bundle format version ([`VERSION`]).
+//! Bundles written with another version of the format are rejected with
+//! [`Error::Version`] and must be built again from the JSON context.
+//!
+//! This module is only available with the `bundle` feature.
+
+use std::convert::TryFrom;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::io::{Read, Write};
+use serde::{Serialize, Deserialize};
+use iref::{Iri, IriBuf};
+use langtag::LanguageTagBuf;
+use json::JsonValue;
+use crate::{
+	Id,
+	Nullable,
+	Direction,
+	BlankId,
+	Reference,
+	syntax::{
+		Keyword,
+		Term,
+		Type,
+		Container,
+		ContainerType
+	},
+	context::inverse::LangDir
+};
+use super::{
+	JsonContext,
+	TermDefinition,
+	Provenance,
+	InverseContext,
+	CompactionContext,
+	ContextCache
+};
+
+/// Bundle format magic number.
+const MAGIC: &[u8; 8] = b"JSONLDCB";
+
+/// Current version of the bundle format.
+///
+/// It is increased each time the binary representation of contexts changes.
+pub const VERSION: u32 = 1;
+
+/// Bundle error.
+#[derive(Debug)]
+pub enum Error {
+	/// The data is not a context bundle.
+	NotABundle,
+
+	/// The bundle has been written with another version of the format.
+	Version(u32),
+
+	/// The bundle content cannot be decoded.
+	Decode(bincode::Error),
+
+	/// The bundle content is decoded, but is not a valid context.
+	Invalid(String)
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Error::NotABundle => write!(f, "not a context bundle"),
+			Error::Version(v) => write!(f, "unsupported bundle version {} (expected {})", v, VERSION),
+			Error::Decode(e) => write!(f, "invalid bundle: {}", e),
+			Error::Invalid(value) => write!(f, "invalid bundle: invalid value `{}`", value)
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+impl From<bincode::Error> for Error {
+	fn from(e: bincode::Error) -> Error {
+		Error::Decode(e)
+	}
+}
+
+impl From<std::io::Error> for Error {
+	fn from(e: std::io::Error) -> Error {
+		Error::Decode(e.into())
+	}
+}
+
+/// Precompiled context.
+///
+/// # Example
+/// ```
+/// use json_ld::{JsonContext, Document, NoLoader, context::{Local, bundle::Bundle}};
+/// # use iref::IriBuf;
+/// # use futures::executor::block_on;
+/// let local = json::parse(r#"{ "name": "http://schema.org/name" }"#).unwrap();
+/// let context: JsonContext<IriBuf> = block_on(Local::<IriBuf>::process::<JsonContext<IriBuf>, _>(&local, &mut NoLoader, None)).unwrap().into_inner();
+///
+/// let bytes = Bundle::new(local, context.clone()).to_bytes().unwrap();
+///
+/// // Later, at startup.
+/// let bundle: Bundle<IriBuf> = Bundle::from_bytes(&bytes).unwrap();
+/// assert!(*bundle.context() == context);
+///
+/// let compaction_context = bundle.into_compaction_context();
+/// let doc = json::parse(r#"{ "http://schema.org/name": "Jane Doe" }"#).unwrap();
+/// let compacted = block_on(doc.compact(&compaction_context, &mut NoLoader)).unwrap();
+/// assert_eq!(compacted["name"], "Jane Doe");
+/// ```
+pub struct Bundle<T: Id> {
+	/// Original JSON context.
+	local: JsonValue,
+
+	/// Processed context.
+	context: JsonContext<T>,
+
+	/// Inverse context.
+	inverse: InverseContext<T>
+}
+
+impl<T: Id> Bundle<T> {
+	/// Creates a new bundle from a processed context and its original JSON representation,
+	/// computing its inverse context.
+	pub fn new(local: JsonValue, context: JsonContext<T>) -> Bundle<T> {
+		let inverse = InverseContext::from(&context);
+		Bundle {
+			local,
+			context,
+			inverse
+		}
+	}
+
+	/// Original JSON context.
+	pub fn local(&self) -> &JsonValue {
+		&self.local
+	}
+
+	/// Processed context.
+	pub fn context(&self) -> &JsonContext<T> {
+		&self.context
+	}
+
+	/// Inverse context.
+	pub fn inverse(&self) -> &InverseContext<T> {
+		&self.inverse
+	}
+
+	/// Consumes the bundle and returns the original JSON context and the processed context.
+	pub fn into_parts(self) -> (JsonValue, JsonContext<T>) {
+		(self.local, self.context)
+	}
+
+	/// Consumes the bundle and returns a compaction context reusing the bundled inverse context.
+	pub fn into_compaction_context(self) -> CompactionContext<T, JsonValue, JsonContext<T>> {
+		CompactionContext::with_inverse(self.local, self.context, self.inverse)
+	}
+
+	/// Writes the bundle.
+	pub fn write<W: Write>(&self, mut output: W) -> Result<(), Error> {
+		output.write_all(MAGIC)?;
+		output.write_all(&VERSION.to_le_bytes())?;
+		let raw = RawBundle {
+			local: self.local.dump(),
+			context: RawContext::from(&self.context),
+			inverse: self.inverse.to_raw()
+		};
+
+		bincode::serialize_into(output, &raw)?;
+		Ok(())
+	}
+
+	/// Reads a bundle.
+	///
+	/// Fails with [`Error::Version`] if the bundle has been written with another version of the format.
+	/// The bundled context is not attached to any cache (see [`read_with_cache`](Bundle::read_with_cache)).
+	pub fn read<R: Read>(input: R) -> Result<Bundle<T>, Error> {
+		Self::read_with(input, None)
+	}
+
+	/// Reads a bundle, attaching the bundled context to the given cache.
+	///
+	/// The contexts processed on top of the bundled context are then cached.
+	pub fn read_with_cache<R: Read>(input: R, cache: ContextCache<JsonContext<T>>) -> Result<Bundle<T>, Error> {
+		Self::read_with(input, Some(&cache))
+	}
+
+	fn read_with<R: Read>(mut input: R, cache: Option<&ContextCache<JsonContext<T>>>) -> Result<Bundle<T>, Error> {
+		let mut magic = [0u8; 8];
+		if input.read_exact(&mut magic).is_err() || &magic != MAGIC {
+			return Err(Error::NotABundle)
+		}
+
+		let mut version = [0u8; 4];
+		input.read_exact(&mut version)?;
+		let version = u32::from_le_bytes(version);
+		if version != VERSION {
+			return Err(Error::Version(version))
+		}
+
+		let raw: RawBundle = bincode::deserialize_from(input)?;
+		Ok(Bundle {
+			local: json::parse(&raw.local).map_err(|_| Error::Invalid(raw.local.clone()))?,
+			context: raw.context.into_context(cache)?,
+			inverse: InverseContext::from_raw(raw.inverse)?
+		})
+	}
+
+	/// Encodes the bundle into bytes.
+	pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+		let mut bytes = Vec::new();
+		self.write(&mut bytes)?;
+		Ok(bytes)
+	}
+
+	/// Decodes a bundle from bytes.
+	pub fn from_bytes(bytes: &[u8]) -> Result<Bundle<T>, Error> {
+		Self::read(bytes)
+	}
+
+	/// Decodes a bundle from bytes, attaching the bundled context to the given cache.
+	///
+	/// # Example
+	/// ```
+	/// use json_ld::{JsonContext, Document, NoLoader, context::{Local, ContextCache, bundle::{self, Bundle}}};
+	/// # use iref::IriBuf;
+	/// # use futures::executor::block_on;
+	/// let local = json::parse(r#"{ "name": "http://schema.org/name" }"#).unwrap();
+	/// let context: JsonContext<IriBuf> = block_on(Local::<IriBuf>::process::<JsonContext<IriBuf>, _>(&local, &mut NoLoader, None)).unwrap().into_inner();
+	/// let mut bytes = Bundle::new(local, context).to_bytes().unwrap();
+	///
+	/// let cache = ContextCache::new();
+	/// let bundle: Bundle<IriBuf> = Bundle::from_bytes_with_cache(&bytes, cache.clone()).unwrap();
+	/// let doc = json::parse(r#"{ "@context": { "knows": "http://xmlns.com/foaf/0.1/knows" }, "name": "Jane Doe" }"#).unwrap();
+	/// for _ in 0..2 {
+	///     block_on(doc.expand_with(None, bundle.context(), &mut NoLoader, Default::default())).unwrap();
+	/// }
+	/// assert_eq!(cache.len(), 1);
+	///
+	/// // Bundles written with another version of the format are rejected.
+	/// bytes[8..12].copy_from_slice(&(bundle::VERSION + 1).to_le_bytes());
+	/// assert!(matches!(Bundle::<IriBuf>::from_bytes_with_cache(&bytes, cache), Err(bundle::Error::Version(v)) if v == bundle::VERSION + 1));
+	/// ```
+	pub fn from_bytes_with_cache(bytes: &[u8], cache: ContextCache<JsonContext<T>>) -> Result<Bundle<T>, Error> {
+		Self::read_with_cache(bytes, cache)
+	}
+}
+
+impl<T: Id> From<Bundle<T>> for CompactionContext<T, JsonValue, JsonContext<T>> {
+	fn from(bundle: Bundle<T>) -> CompactionContext<T, JsonValue, JsonContext<T>> {
+		bundle.into_compaction_context()
+	}
+}
+
+/// Bundle content.
+#[derive(Serialize, Deserialize)]
+struct RawBundle {
+	local: String,
+	context: RawContext,
+	inverse: RawInverseContext
+}
+
+/// Binary representation of a term.
+#[derive(Serialize, Deserialize)]
+pub(crate) enum RawTerm {
+	Null,
+	Iri(String),
+	Blank(String),
+	Keyword(String)
+}
+
+impl<'a, T: Id> From<&'a Term<T>> for RawTerm {
+	fn from(term: &'a Term<T>) -> RawTerm {
+		match term {
+			Term::Null => RawTerm::Null,
+			Term::Ref(Reference::Id(id)) => RawTerm::Iri(id.as_iri().as_str().to_string()),
+			Term::Ref(Reference::Blank(id)) => RawTerm::Blank(id.as_str().to_string()),
+			Term::Keyword(k) => RawTerm::Keyword(k.into_str().to_string())
+		}
+	}
+}
+
+impl RawTerm {
+	pub(crate) fn into_term<T: Id>(self) -> Result<Term<T>, Error> {
+		match self {
+			RawTerm::Null => Ok(Term::Null),
+			RawTerm::Iri(iri) => Ok(Term::Ref(Reference::Id(id(&iri)?))),
+			RawTerm::Blank(blank) => match BlankId::try_from(blank.as_str()) {
+				Ok(id) => Ok(Term::Ref(Reference::Blank(id))),
+				Err(()) => Err(Error::Invalid(blank))
+			},
+			RawTerm::Keyword(k) => match Keyword::try_from(k.as_str()) {
+				Ok(k) => Ok(Term::Keyword(k)),
+				Err(_) => Err(Error::Invalid(k))
+			}
+		}
+	}
+}
+
+/// Binary representation of a language and direction pair.
+pub(crate) type RawLangDir = Option<(Option<String>, Option<String>)>;
+
+/// Binary representation of an inverse context.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct RawInverseContext {
+	pub definitions: Vec<(RawTerm, RawInverseDefinition)>
+}
+
+/// Binary representation of an inverse context definition.
+pub(crate) type RawInverseDefinition = Vec<(Vec<String>, RawInverseContainer)>;
+
+/// Binary representation of an inverse context container entry.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct RawInverseContainer {
+	pub language_any: Option<String>,
+	pub language: Vec<(RawLangDir, String)>,
+	pub type_reverse: Option<String>,
+	pub type_any: Option<String>,
+	pub types: Vec<(String, String)>,
+	pub none: String
+}
+
+/// Binary representation of a processed context.
+#[derive(Serialize, Deserialize)]
+struct RawContext {
+	original_base_url: Option<String>,
+	base_iri: Option<String>,
+	vocabulary: Option<RawTerm>,
+	default_language: Option<String>,
+	default_base_direction: Option<String>,
+	previous_context: Option<Box<RawContext>>,
+	definitions: Vec<(String, RawDefinition)>
+}
+
+impl<'a, T: Id> From<&'a JsonContext<T>> for RawContext {
+	fn from(context: &'a JsonContext<T>) -> RawContext {
+		let mut definitions: Vec<_> = context.definitions.iter().map(|(term, definition)| {
+			(term.clone(), RawDefinition::from(definition))
+		}).collect();
+		definitions.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+		RawContext {
+			original_base_url: context.original_base_url.as_ref().map(|iri| iri.as_str().to_string()),
+			base_iri: context.base_iri.as_ref().map(|iri| iri.as_str().to_string()),
+			vocabulary: context.vocabulary.as_ref().map(RawTerm::from),
+			default_language: context.default_language.as_ref().map(|lang| lang.as_str().to_string()),
+			default_base_direction: context.default_base_direction.map(|dir| dir.to_string()),
+			previous_context: context.previous_context.as_ref().map(|previous| Box::new(RawContext::from(previous.as_ref()))),
+			definitions
+		}
+	}
+}
+
+impl RawContext {
+	/// Decodes the context, attaching it to the given cache with a new identifier.
+	fn into_context<T: Id>(self, cache: Option<&ContextCache<JsonContext<T>>>) -> Result<JsonContext<T>, Error> {
+		let mut definitions = HashMap::new();
+		for (term, definition) in self.definitions {
+			definitions.insert(term, definition.into_definition()?);
+		}
+
+		Ok(JsonContext {
+			original_base_url: self.original_base_url.map(iri).transpose()?,
+			base_iri: self.base_iri.map(iri).transpose()?,
+			vocabulary: self.vocabulary.map(RawTerm::into_term).transpose()?,
+			default_language: self.default_language.map(language).transpose()?,
+			default_base_direction: self.default_base_direction.map(direction).transpose()?,
+			previous_context: match self.previous_context {
+				Some(previous) => Some(Box::new(previous.into_context(cache)?)),
+				None => None
+			},
+			definitions,
+			cache: cache.cloned(),
+			cache_id: cache.map(ContextCache::fresh_id)
+		})
+	}
+}
+
+/// Binary representation of a term definition.
+#[derive(Serialize, Deserialize)]
+struct RawDefinition {
+	value: Option<RawTerm>,
+	prefix: bool,
+	protected: bool,
+	reverse_property: bool,
+	base_url: Option<String>,
+	context: Option<String>,
+	container: Vec<String>,
+	direction: Option<Option<String>>,
+	index: Option<String>,
+	language: Option<Option<String>>,
+	nest: Option<String>,
+	typ: Option<String>,
+	provenance: Option<RawProvenance>
+}
+
+impl<'a, T: Id> From<&'a TermDefinition<T, JsonContext<T>>> for RawDefinition {
+	fn from(definition: &'a TermDefinition<T, JsonContext<T>>) -> RawDefinition {
+		RawDefinition {
+			value: definition.value.as_ref().map(RawTerm::from),
+			prefix: definition.prefix,
+			protected: definition.protected,
+			reverse_property: definition.reverse_property,
+			base_url: definition.base_url.as_ref().map(|iri| iri.as_str().to_string()),
+			context: definition.context.as_ref().map(JsonValue::dump),
+			container: raw_container(&definition.container),
+			direction: definition.direction.map(|dir| dir.option().map(|dir| dir.to_string())),
+			index: definition.index.clone(),
+			language: definition.language.as_ref().map(|lang| lang.as_ref().option().map(|lang| lang.as_str().to_string())),
+			nest: definition.nest.clone(),
+			typ: definition.typ.as_ref().map(raw_type),
+			provenance: definition.provenance.as_ref().map(|provenance| RawProvenance {
+				url: provenance.url.as_ref().map(|iri| iri.as_str().to_string()),
+				import: provenance.import.as_ref().map(|iri| iri.as_str().to_string()),
+				override_protected: provenance.override_protected,
+				propagate: provenance.propagate,
+				previous: provenance.previous.as_ref().map(|previous| Box::new(RawDefinition::from(previous.as_ref())))
+			})
+		}
+	}
+}
+
+impl RawDefinition {
+	fn into_definition<T: Id>(self) -> Result<TermDefinition<T, JsonContext<T>>, Error> {
+		Ok(TermDefinition {
+			value: self.value.map(RawTerm::into_term).transpose()?,
+			prefix: self.prefix,
+			protected: self.protected,
+			reverse_property: self.reverse_property,
+			base_url: self.base_url.map(iri).transpose()?,
+			context: match self.context {
+				Some(context) => Some(json::parse(&context).map_err(|_| Error::Invalid(context))?),
+				None => None
+			},
+			container: container(self.container)?,
+			direction: match self.direction {
+				Some(Some(dir)) => Some(Nullable::Some(direction(dir)?)),
+				Some(None) => Some(Nullable::Null),
+				None => None
+			},
+			index: self.index,
+			language: match self.language {
+				Some(Some(lang)) => Some(Nullable::Some(language(lang)?)),
+				Some(None) => Some(Nullable::Null),
+				None => None
+			},
+			nest: self.nest,
+			typ: self.typ.map(typ).transpose()?,
+			provenance: match self.provenance {
+				Some(provenance) => Some(Provenance {
+					url: provenance.url.map(iri).transpose()?,
+					import: provenance.import.map(iri).transpose()?,
+					override_protected: provenance.override_protected,
+					propagate: provenance.propagate,
+					previous: match provenance.previous {
+						Some(previous) => Some(Arc::new(previous.into_definition()?)),
+						None => None
+					}
+				}),
+				None => None
+			}
+		})
+	}
+}
+
+/// Binary representation of a term definition provenance.
+#[derive(Serialize, Deserialize)]
+struct RawProvenance {
+	url: Option<String>,
+	import: Option<String>,
+	override_protected: bool,
+	propagate: bool,
+	previous: Option<Box<RawDefinition>>
+}
+
+fn iri(iri: String) -> Result<IriBuf, Error> {
+	IriBuf::new(&iri).map_err(|_| Error::Invalid(iri))
+}
+
+fn id<T: Id>(value: &str) -> Result<T, Error> {
+	match Iri::new(value) {
+		Ok(iri) => Ok(T::from_iri(iri)),
+		Err(_) => Err(Error::Invalid(value.to_string()))
+	}
+}
+
+fn language(lang: String) -> Result<LanguageTagBuf, Error> {
+	LanguageTagBuf::parse_copy(lang.as_str()).map_err(|_| Error::Invalid(lang))
+}
+
+fn direction(dir: String) -> Result<Direction, Error> {
+	match Direction::try_from(dir.as_str()) {
+		Ok(dir) => Ok(dir),
+		Err(_) => Err(Error::Invalid(dir))
+	}
+}
+
+pub(crate) fn raw_container(container: &Container) -> Vec<String> {
+	container.iter().map(|c| Keyword::from(*c).into_str().to_string()).collect()
+}
+
+pub(crate) fn container(raw: Vec<String>) -> Result<Container, Error> {
+	let mut types = Vec::new();
+	for c in raw {
+		match ContainerType::try_from(c.as_str()) {
+			Ok(c) => types.push(c),
+			Err(_) => return Err(Error::Invalid(c))
+		}
+	}
+
+	Container::from(&types).map_err(|c| Error::Invalid(Keyword::from(c).into_str().to_string()))
+}
+
+pub(crate) fn raw_type<T: Id>(ty: &Type<T>) -> String {
+	ty.as_str().to_string()
+}
+
+pub(crate) fn typ<T: Id>(raw: String) -> Result<Type<T>, Error> {
+	match raw.as_str() {
+		"@id" => Ok(Type::Id),
+		"@json" => Ok(Type::Json),
+		"@none" => Ok(Type::None),
+		"@vocab" => Ok(Type::Vocab),
+		_ => Ok(Type::Ref(id(&raw)?))
+	}
+}
+
+pub(crate) fn raw_lang_dir(lang_dir: &LangDir) -> RawLangDir {
+	lang_dir.as_ref().option().map(|(lang, dir)| {
+		(lang.as_ref().map(|lang| lang.as_str().to_string()), dir.map(|dir| dir.to_string()))
+	})
+}
+
+pub(crate) fn lang_dir(raw: RawLangDir) -> Result<LangDir, Error> {
+	match raw {
+		Some((lang, dir)) => Ok(Nullable::Some((lang.map(language).transpose()?, dir.map(direction).transpose()?))),
+		None => Ok(Nullable::Null)
+	}
+}
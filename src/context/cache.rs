@@ -0,0 +1,289 @@
+use std::any::{Any, TypeId};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use once_cell::sync::Lazy;
+use iref::{Iri, IriBuf};
+use crate::{
+	Id,
+	LoaderId
+};
+use super::{
+	Context,
+	ContextMut,
+	ProcessingOptions,
+	ProcessingStack
+};
+
+/// Default number of processed contexts kept by a cache.
+pub const DEFAULT_CAPACITY: usize = 1024;
+
+/// Shared, thread-safe cache of processed contexts.
+///
+/// A cache is attached to an initial active context with
+/// [`JsonContext::with_cache`](super::JsonContext::with_cache), either a cache of its own or
+/// the process-wide cache returned by [`ContextCache::global`].
+/// Caching can be disabled with [`ContextMut::set_cache`].
+///
+/// Every context derived from it through the context processing algorithm shares the same
+/// cache, so that processing the same local context, or loading the same remote context,
+/// on top of the same active context is only done once, during expansion as well as
+/// compaction.
+///
+/// Processed contexts depend on the loader used to load remote contexts:
+/// the cache is only used with loaders providing a [`LoaderId`],
+/// and contexts processed with one loader are never reused with another.
+///
+/// Each cached context is identified by an identifier that is cleared as soon as the context
+/// is modified outside of the processing algorithm: a modified context does not use the cache.
+/// Remote contexts are assumed to never change for a given loader: the cache can be emptied with
+/// [`clear`](ContextCache::clear).
+///
+/// The cache keeps at most [`capacity`](ContextCache::capacity) contexts,
+/// evicting the least recently used ones first.
+pub struct ContextCache<C> {
+	inner: Arc<Mutex<Inner<C>>>
+}
+
+struct Inner<C> {
+	/// Number of identifiers given.
+	count: u64,
+
+	/// Maximum number of entries.
+	capacity: usize,
+
+	/// Last access time.
+	tick: u64,
+
+	/// Processed contexts, with their last access time.
+	entries: HashMap<Key, (C, u64)>,
+
+	/// Entries by last access time.
+	usage: BTreeMap<u64, Key>
+}
+
+/// Identifier of a context in a cache.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum CacheId {
+	/// Newly-initialized active context with the given base IRI.
+	Initial(Option<IriBuf>),
+
+	/// Context obtained through the context processing algorithm.
+	Processed(u64)
+}
+
+/// Cache key.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Key {
+	/// Identifier of the active context.
+	active_context: CacheId,
+
+	/// Loader used to load remote contexts.
+	loader: LoaderId,
+
+	/// Processed local context.
+	source: Source,
+
+	/// Base URL.
+	base_url: Option<IriBuf>,
+
+	/// Processing options.
+	options: ProcessingOptions,
+
+	/// Remote contexts being loaded, used to detect recursive inclusions.
+	stack: Vec<IriBuf>
+}
+
+/// Local context source.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub(crate) enum Source {
+	/// Serialized local context.
+	Local(String),
+
+	/// Remote context URL.
+	Remote(IriBuf)
+}
+
+/// Process-wide caches, by context type.
+static GLOBAL: Lazy<Mutex<HashMap<TypeId, Box<dyn Any + Send>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+impl<C> Clone for ContextCache<C> {
+	fn clone(&self) -> ContextCache<C> {
+		ContextCache {
+			inner: self.inner.clone()
+		}
+	}
+}
+
+impl<C> ContextCache<C> {
+	/// Creates a new empty cache with the [default capacity](DEFAULT_CAPACITY).
+	pub fn new() -> ContextCache<C> {
+		Self::with_capacity(DEFAULT_CAPACITY)
+	}
+
+	/// Creates a new empty cache keeping at most `capacity` contexts.
+	///
+	/// # Example
+	/// ```
+	/// use json_ld::{JsonContext, Document, NoLoader, context::ContextCache};
+	/// # use iref::IriBuf;
+	/// # use futures::executor::block_on;
+	/// let cache = ContextCache::with_capacity(1);
+	/// let context: JsonContext<IriBuf> = JsonContext::with_cache(None, cache.clone());
+	///
+	/// for term in &["a", "b", "c"] {
+	///     let doc = json::parse(&format!(r#"{{ "@context": {{ "{0}": "http://example.org/{0}" }} }}"#, term)).unwrap();
+	///     block_on(doc.expand_with(None, &context, &mut NoLoader, Default::default())).unwrap();
+	/// }
+	///
+	/// // Only the most recently used context is kept.
+	/// assert_eq!(cache.len(), 1);
+	/// ```
+	pub fn with_capacity(capacity: usize) -> ContextCache<C> {
+		ContextCache {
+			inner: Arc::new(Mutex::new(Inner {
+				count: 0,
+				capacity,
+				tick: 0,
+				entries: HashMap::new(),
+				usage: BTreeMap::new()
+			}))
+		}
+	}
+
+	/// Returns the maximum number of cached contexts.
+	pub fn capacity(&self) -> usize {
+		self.inner.lock().unwrap().capacity
+	}
+
+	/// Returns the number of cached contexts.
+	pub fn len(&self) -> usize {
+		self.inner.lock().unwrap().entries.len()
+	}
+
+	/// Checks if the cache is empty.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Removes every cached context.
+	pub fn clear(&self) {
+		let mut inner = self.inner.lock().unwrap();
+		inner.entries.clear();
+		inner.usage.clear()
+	}
+
+	/// Returns a new context identifier.
+	pub(crate) fn fresh_id(&self) -> CacheId {
+		let mut inner = self.inner.lock().unwrap();
+		let id = inner.count;
+		inner.count += 1;
+		CacheId::Processed(id)
+	}
+
+	/// Checks if the given cache is this cache.
+	pub(crate) fn ptr_eq(&self, other: &ContextCache<C>) -> bool {
+		Arc::ptr_eq(&self.inner, &other.inner)
+	}
+}
+
+impl<C: Send + 'static> ContextCache<C> {
+	/// Returns the process-wide cache of contexts of type `C`.
+	///
+	/// # Example
+	/// ```
+	/// use json_ld::{JsonContext, context::ContextCache};
+	/// # use iref::IriBuf;
+	/// let context: JsonContext<IriBuf> = JsonContext::with_cache(None, ContextCache::global());
+	/// ```
+	pub fn global() -> ContextCache<C> {
+		GLOBAL.lock().unwrap()
+			.entry(TypeId::of::<C>())
+			.or_insert_with(|| Box::new(ContextCache::<C>::new()))
+			.downcast_ref::<ContextCache<C>>()
+			.unwrap()
+			.clone()
+	}
+}
+
+impl<C: Clone> ContextCache<C> {
+	/// Returns the cached context for the given key.
+	pub(crate) fn get(&self, key: &Key) -> Option<C> {
+		let mut guard = self.inner.lock().unwrap();
+		let inner = &mut *guard;
+		inner.tick += 1;
+		let tick = inner.tick;
+		let (context, last_access) = inner.entries.get_mut(key)?;
+		let context = context.clone();
+		let last_access = std::mem::replace(last_access, tick);
+		inner.usage.remove(&last_access);
+		inner.usage.insert(tick, key.clone());
+		Some(context)
+	}
+}
+
+impl<C> ContextCache<C> {
+	/// Returns the identifier of the cached context for the given key, if any.
+	///
+	/// Unlike [`get`](ContextCache::get), the context is not cloned and
+	/// this does not count as a use of the context.
+	pub(crate) fn cached_id<T: Id>(&self, key: &Key) -> Option<CacheId> where C: Context<T> {
+		let inner = self.inner.lock().unwrap();
+		inner.entries.get(key).and_then(|(context, _)| context.cache_id().cloned())
+	}
+}
+
+impl<C> Inner<C> {
+	/// Stores the given context, evicting the least recently used ones if needed.
+	fn insert(&mut self, key: Key, context: C) {
+		self.tick += 1;
+		if let Some((_, last_access)) = self.entries.insert(key.clone(), (context, self.tick)) {
+			self.usage.remove(&last_access);
+		}
+		self.usage.insert(self.tick, key);
+
+		while self.entries.len() > self.capacity {
+			let oldest = *self.usage.keys().next().unwrap();
+			let key = self.usage.remove(&oldest).unwrap();
+			self.entries.remove(&key);
+		}
+	}
+}
+
+impl<C> Default for ContextCache<C> {
+	fn default() -> ContextCache<C> {
+		ContextCache::new()
+	}
+}
+
+/// Returns the cache of the given active context along with the key of the processing
+/// of the given source on top of it, if the active context is cached and the loader identified.
+pub(crate) fn key<T: Id, C: Context<T>, S: FnOnce() -> Source>(active_context: &C, loader: Option<LoaderId>, source: S, base_url: Option<Iri>, options: ProcessingOptions, stack: &ProcessingStack) -> Option<(ContextCache<C>, Key)> {
+	let cache = active_context.cache()?;
+	let id = active_context.cache_id()?;
+	Some((cache.clone(), key_of(id, loader?, source(), base_url, options, stack)))
+}
+
+/// Returns the key of the processing of the given source on top of the active context
+/// with the given identifier.
+pub(crate) fn key_of(active_context: &CacheId, loader: LoaderId, source: Source, base_url: Option<Iri>, options: ProcessingOptions, stack: &ProcessingStack) -> Key {
+	Key {
+		active_context: active_context.clone(),
+		loader,
+		source,
+		base_url: base_url.map(IriBuf::from),
+		options,
+		stack: stack.urls()
+	}
+}
+
+/// Stores the given processed context into the cache,
+/// giving it a new identifier if it has been modified.
+pub(crate) fn insert<T: Id, C: ContextMut<T>>(cache: &ContextCache<C>, key: Key, context: &mut C) {
+	if context.cache().map(|c| c.ptr_eq(cache)).unwrap_or(false) {
+		if context.cache_id().is_none() {
+			context.set_cache_id(Some(cache.fresh_id()))
+		}
+
+		cache.inner.lock().unwrap().insert(key, context.clone());
+	}
+}
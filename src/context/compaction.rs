@@ -0,0 +1,178 @@
+use std::sync::Arc;
+use once_cell::sync::OnceCell;
+use json::JsonValue;
+use crate::{
+	Id,
+	util::AsJson
+};
+use super::{
+	ContextMut,
+	ContextMutProxy,
+	Inversible,
+	InverseContext,
+	Processed,
+	DEFAULT_CAPACITY,
+	inverse::ScopedInverses
+};
+
+/// Compaction context.
+///
+/// Processed context attached to its original local representation,
+/// along with its precomputed inverse context.
+/// The inverse context is computed once, when the compaction context is built,
+/// and shared by every compaction using this context, including clones.
+///
+/// The inverse contexts of the type-scoped and property-scoped contexts encountered during
+/// compaction are also kept, by term and processed scoped context, and shared the same way.
+/// At most [`DEFAULT_CAPACITY`](super::DEFAULT_CAPACITY) of them are kept,
+/// the least recently used being dropped first.
+/// Scoped contexts are only identified when they come from a [`ContextCache`](super::ContextCache):
+/// the processed context must be attached to a cache (see [`JsonContext::with_cache`](super::JsonContext::with_cache)).
+///
+/// This avoids rebuilding the inverse contexts on each call to
+/// [`Document::compact_with`](crate::Document::compact_with),
+/// which is otherwise done for every compacted document.
+///
+/// # Example
+/// ```
+/// use json_ld::{JsonContext, Document, NoLoader, context::{Local, CompactionContext}};
+/// # use iref::IriBuf;
+/// # use futures::executor::block_on;
+/// let local = json::parse(r#"{ "name": "http://schema.org/name" }"#).unwrap();
+/// let processed = block_on(Local::<IriBuf>::process::<JsonContext<IriBuf>, _>(&local, &mut NoLoader, None)).unwrap().owned();
+/// let context = CompactionContext::from(processed);
+///
+/// for name in &["Jane Doe", "John Smith"] {
+///     let mut doc = json::object::Object::new();
+///     doc.insert("http://schema.org/name", (*name).into());
+///     let compacted = block_on(json::JsonValue::Object(doc).compact(&context, &mut NoLoader)).unwrap();
+///     assert_eq!(compacted["name"], *name);
+/// }
+/// ```
+pub struct CompactionContext<T: Id, L, C> {
+	/// Original unprocessed context.
+	local: L,
+
+	/// Processed context.
+	context: C,
+
+	/// Inverse of the processed context.
+	inverse: Arc<OnceCell<InverseContext<T>>>,
+
+	/// Inverses of the scoped contexts derived from the processed context.
+	scoped: Arc<ScopedInverses<T>>
+}
+
+impl<T: Id, L, C: ContextMut<T>> CompactionContext<T, L, C> {
+	/// Creates a new compaction context from a processed context and its original
+	/// local representation, computing its inverse context.
+	pub fn new(local: L, context: C) -> CompactionContext<T, L, C> {
+		let inverse = OnceCell::new();
+		inverse.get_or_init(|| InverseContext::from(&context));
+
+		CompactionContext {
+			local,
+			context,
+			inverse: Arc::new(inverse),
+			scoped: Arc::new(ScopedInverses::new(DEFAULT_CAPACITY))
+		}
+	}
+
+	/// Creates a new compaction context from a processed context, its original local
+	/// representation and its already computed inverse context.
+	pub fn with_inverse(local: L, context: C, inverse: InverseContext<T>) -> CompactionContext<T, L, C> {
+		CompactionContext {
+			local,
+			context,
+			inverse: Arc::new(OnceCell::from(inverse)),
+			scoped: Arc::new(ScopedInverses::new(DEFAULT_CAPACITY))
+		}
+	}
+
+	/// Returns the precomputed inverse context.
+	pub fn inverse(&self) -> &InverseContext<T> {
+		self.inverse.get_or_init(|| InverseContext::from(&self.context))
+	}
+
+	/// Returns the number of inverse contexts of scoped contexts kept by this compaction context.
+	///
+	/// # Example
+	/// ```
+	/// use json_ld::{JsonContext, Document, NoLoader, context::{Local, CompactionContext, ContextCache}};
+	/// # use iref::IriBuf;
+	/// # use futures::executor::block_on;
+	/// let active_context: JsonContext<IriBuf> = JsonContext::with_cache(None, ContextCache::new());
+	/// let local = json::parse(r#"{
+	///     "knows": {
+	///         "@id": "http://xmlns.com/foaf/0.1/knows",
+	///         "@context": { "name": "http://xmlns.com/foaf/0.1/name" }
+	///     }
+	/// }"#).unwrap();
+	/// let processed = block_on(local.process_with(&active_context, &mut NoLoader, None, Default::default())).unwrap().owned();
+	/// let context = CompactionContext::from(processed);
+	///
+	/// let doc = json::parse(r#"{
+	///     "http://xmlns.com/foaf/0.1/knows": { "http://xmlns.com/foaf/0.1/name": "Jane Doe" }
+	/// }"#).unwrap();
+	///
+	/// for _ in 0..2 {
+	///     let compacted = block_on(doc.compact(&context, &mut NoLoader)).unwrap();
+	///     assert_eq!(compacted["knows"]["name"], "Jane Doe");
+	/// }
+	///
+	/// // The inverse of the scoped context of `knows` is computed once.
+	/// assert_eq!(context.scoped_inverse_count(), 1);
+	/// ```
+	pub fn scoped_inverse_count(&self) -> usize {
+		self.scoped.len()
+	}
+
+	/// Consumes the compaction context and returns the processed context.
+	pub fn into_inner(self) -> C {
+		self.context
+	}
+}
+
+impl<T: Id, L, C: ContextMut<T>> From<Processed<L, C>> for CompactionContext<T, L, C> {
+	fn from(processed: Processed<L, C>) -> CompactionContext<T, L, C> {
+		let (local, context) = processed.into_parts();
+		CompactionContext::new(local, context)
+	}
+}
+
+impl<T: Id, L: Clone, C: Clone> Clone for CompactionContext<T, L, C> {
+	fn clone(&self) -> CompactionContext<T, L, C> {
+		CompactionContext {
+			local: self.local.clone(),
+			context: self.context.clone(),
+			inverse: self.inverse.clone(),
+			scoped: self.scoped.clone()
+		}
+	}
+}
+
+impl<T: Id, L, C: ContextMut<T>> ContextMutProxy<T> for CompactionContext<T, L, C> {
+	type Target = C;
+
+	fn deref(&self) -> &C {
+		&self.context
+	}
+
+	fn inversible(&self) -> Inversible<T, &C> {
+		Inversible::with_inverse(&self.context, self.inverse.clone(), self.scoped.clone())
+	}
+}
+
+impl<T: Id, L: AsJson, C> AsJson for CompactionContext<T, L, C> {
+	fn as_json(&self) -> JsonValue {
+		self.local.as_json()
+	}
+}
+
+impl<T: Id, L, C> std::ops::Deref for CompactionContext<T, L, C> {
+	type Target = C;
+
+	fn deref(&self) -> &C {
+		&self.context
+	}
+}
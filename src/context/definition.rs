@@ -1,16 +1,23 @@
+use std::sync::Arc;
 use iref::{Iri, IriBuf};
 use langtag::LanguageTagBuf;
+use json::JsonValue;
 use crate::{
 	Nullable,
 	Id,
 	Direction,
 	syntax::{
+		Keyword,
 		Term,
 		Type,
 		Container
-	}
+	},
+	util::AsJson
+};
+use super::{
+	Context,
+	is_gen_delim_or_blank
 };
-use super::Context;
 
 // A term definition.
 #[derive(Clone)]
@@ -49,13 +56,189 @@ pub struct TermDefinition<T: Id, C: Context<T>> {
 	pub nest: Option<String>,
 
 	// Optional type mapping.
-	pub typ: Option<Type<T>>
+	pub typ: Option<Type<T>>,
+
+	// Origin of the definition.
+	pub provenance: Option<Provenance<T, C>>
+}
+
+/// Origin of a term definition.
+pub struct Provenance<T: Id, C: Context<T>> {
+	/// URL of the document holding the context that defines the term, if known.
+	///
+	/// This is the URL of the remote context, or the base URL of the document
+	/// in which the context is embedded.
+	pub url: Option<IriBuf>,
+
+	/// URL of the context imported with `@import` the definition comes from, if any.
+	pub import: Option<IriBuf>,
+
+	/// Whether the context has been processed with `override_protected`,
+	/// as property-scoped contexts are.
+	pub override_protected: bool,
+
+	/// Whether the context is propagated.
+	///
+	/// This is `false` for type-scoped contexts and contexts with `@propagate` set to `false`.
+	pub propagate: bool,
+
+	/// Previous definition of the term, overridden by this definition.
+	///
+	/// It is shared, so that copying an active context does not copy the history of its terms.
+	pub previous: Option<Arc<TermDefinition<T, C>>>
+}
+
+impl<T: Id, C: Context<T>> Clone for Provenance<T, C> where C::LocalContext: Clone {
+	fn clone(&self) -> Provenance<T, C> {
+		Provenance {
+			url: self.url.clone(),
+			import: self.import.clone(),
+			override_protected: self.override_protected,
+			propagate: self.propagate,
+			previous: self.previous.clone()
+		}
+	}
+}
+
+/// Local context being processed, giving the [`Provenance`] of the terms it defines.
+#[derive(Clone)]
+pub struct Origin {
+	/// URL of the document holding the context, if known.
+	pub url: Option<IriBuf>,
+
+	/// URL of the context imported with `@import`, if any.
+	pub import: Option<IriBuf>,
+
+	/// Terms coming from the imported context.
+	pub imported: Vec<String>,
+
+	/// Whether the context is processed with `override_protected`.
+	pub override_protected: bool,
+
+	/// Whether the context is propagated.
+	pub propagate: bool
+}
+
+impl Origin {
+	/// Returns the provenance of a definition of `term` by this context,
+	/// overriding the given previous definition.
+	pub fn provenance<T: Id, C: Context<T>>(&self, term: &str, previous: Option<TermDefinition<T, C>>) -> Provenance<T, C> {
+		Provenance {
+			url: self.url.clone(),
+			import: if self.imported.iter().any(|key| key == term) { self.import.clone() } else { None },
+			override_protected: self.override_protected,
+			propagate: self.propagate,
+			previous: previous.map(Arc::new)
+		}
+	}
 }
 
 impl<T: Id, C: Context<T>> TermDefinition<T, C> {
 	pub fn base_url(&self) -> Option<Iri> {
 		self.base_url.as_ref().map(|iri| iri.as_iri())
 	}
+
+	/// Returns the JSON form of the IRI mapping of this definition if it can be written as a
+	/// simple string (or `null`) term definition for the given term.
+	fn simple_value(&self, term: &str, protected: bool) -> Option<JsonValue> {
+		let implied_prefix = match &self.value {
+			Some(Term::Null) | None => false,
+			Some(value) => !term.contains(':') && !term.contains('/') && is_gen_delim_or_blank(value)
+		};
+
+		if !term.starts_with('@')
+		&& self.protected == protected
+		&& !self.reverse_property
+		&& self.prefix == implied_prefix
+		&& self.context.is_none()
+		&& self.container.is_empty()
+		&& self.direction.is_none()
+		&& self.index.is_none()
+		&& self.language.is_none()
+		&& self.nest.is_none()
+		&& self.typ.is_none() {
+			match &self.value {
+				Some(value) => Some(value.as_json()),
+				None => Some(JsonValue::Null)
+			}
+		} else {
+			None
+		}
+	}
+
+	/// Produces the minimal JSON form of this definition of the given term.
+	///
+	/// The definition is written as a simple string when possible.
+	/// The protected flag is written only if it differs from `protected`,
+	/// the value of the `@protected` entry of the enclosing context.
+	pub fn as_json_for(&self, term: &str, protected: bool) -> JsonValue where C::LocalContext: AsJson {
+		match self.simple_value(term, protected) {
+			Some(value) => value,
+			None => self.expanded_json(term == Keyword::Type.into_str(), protected)
+		}
+	}
+
+	/// Produces the expanded JSON form of this definition.
+	///
+	/// The IRI mapping is omitted if `omit_id` is true,
+	/// as required for the definition of the `@type` keyword.
+	fn expanded_json(&self, omit_id: bool, protected: bool) -> JsonValue where C::LocalContext: AsJson {
+		let mut obj = json::object::Object::new();
+
+		match &self.value {
+			Some(value) if self.reverse_property => obj.insert(Keyword::Reverse.into_str(), value.as_json()),
+			Some(value) if !omit_id => obj.insert(Keyword::Id.into_str(), value.as_json()),
+			None if !omit_id => obj.insert(Keyword::Id.into_str(), JsonValue::Null),
+			_ => ()
+		}
+
+		if let Some(typ) = &self.typ {
+			obj.insert(Keyword::Type.into_str(), typ.as_json())
+		}
+
+		if !self.container.is_empty() {
+			obj.insert(Keyword::Container.into_str(), self.container.as_json())
+		}
+
+		if let Some(context) = &self.context {
+			obj.insert(Keyword::Context.into_str(), context.as_json())
+		}
+
+		if let Some(language) = &self.language {
+			obj.insert(Keyword::Language.into_str(), language.as_json())
+		}
+
+		if let Some(direction) = &self.direction {
+			obj.insert(Keyword::Direction.into_str(), direction.as_json())
+		}
+
+		if let Some(index) = &self.index {
+			obj.insert(Keyword::Index.into_str(), index.as_json())
+		}
+
+		if let Some(nest) = &self.nest {
+			obj.insert(Keyword::Nest.into_str(), nest.as_json())
+		}
+
+		if self.prefix {
+			obj.insert(Keyword::Prefix.into_str(), true.into())
+		}
+
+		if self.protected != protected {
+			obj.insert(Keyword::Protected.into_str(), self.protected.into())
+		}
+
+		JsonValue::Object(obj)
+	}
+}
+
+/// Produces the expanded JSON form of the definition.
+///
+/// Use [`TermDefinition::as_json_for`] to get the minimal form of the definition of a given term.
+impl<T: Id, C: Context<T>> AsJson for TermDefinition<T, C> where C::LocalContext: AsJson {
+	fn as_json(&self) -> JsonValue {
+		self.expanded_json(false, false)
+	}
 }
 
 impl<T: Id, C: Context<T>> Default for TermDefinition<T, C> {
@@ -72,14 +255,15 @@ impl<T: Id, C: Context<T>> Default for TermDefinition<T, C> {
 			context: None,
 			nest: None,
 			index: None,
-			container: Container::new()
+			container: Container::new(),
+			provenance: None
 		}
 	}
 }
 
 impl<T: Id, C: Context<T>> PartialEq for TermDefinition<T, C> {
 	fn eq(&self, other: &TermDefinition<T, C>) -> bool {
-		// NOTE we ignore the `protected` flag.
+		// NOTE we ignore the `protected` flag and provenance.
 		self.prefix == other.prefix &&
 		self.reverse_property == other.reverse_property &&
 		self.language == other.language &&
@@ -0,0 +1,161 @@
+use std::fmt;
+use crate::{
+	Id,
+	syntax::Term
+};
+use super::{
+	Context,
+	TermDefinition
+};
+
+/// Explanation of the definition of a term in an active context.
+///
+/// Built using [`Context::explain`].
+///
+/// # Example
+/// ```
+/// use json_ld::{JsonContext, Context, NoLoader, context::{Local, ProcessingOptions}};
+/// # use iref::{Iri, IriBuf};
+/// # use futures::executor::block_on;
+/// let base_url = Iri::new("http://example.org/doc.jsonld").unwrap();
+/// let context = json::parse(r#"{
+///     "name": "http://xmlns.com/foaf/0.1/name",
+///     "Person": {
+///         "@id": "http://schema.org/Person",
+///         "@context": { "name": "http://schema.org/name" }
+///     }
+/// }"#).unwrap();
+/// let active_context: JsonContext<IriBuf> = block_on(Local::<IriBuf>::process_with(&context, &JsonContext::new(Some(base_url)), &mut NoLoader, Some(base_url), ProcessingOptions::default())).unwrap().into_inner();
+///
+/// // Apply the type-scoped context of `Person`.
+/// let scoped = active_context.get("Person").unwrap().context.as_ref().unwrap();
+/// let options = ProcessingOptions::default().without_propagation();
+/// let scoped_context: JsonContext<IriBuf> = block_on(Local::<IriBuf>::process_with(scoped, &active_context, &mut NoLoader, Some(base_url), options)).unwrap().into_inner();
+///
+/// let explanation = scoped_context.explain("name");
+/// assert_eq!(explanation.definitions().len(), 2);
+/// let provenance = explanation.definition().unwrap().provenance.as_ref().unwrap();
+/// assert!(!provenance.propagate);
+/// assert_eq!(provenance.url.as_ref().map(|url| url.as_str()), Some("http://example.org/doc.jsonld"));
+/// assert!(!explanation.is_propagated());
+/// println!("{}", explanation);
+/// ```
+pub struct Explanation<'a, T: Id, C: Context<T>> {
+	/// Explained term.
+	term: &'a str,
+
+	/// Current definition of the term, followed by the definitions it overrides.
+	definitions: Vec<&'a TermDefinition<T, C>>,
+
+	/// Definition of the term in the previous context, if the active context is not propagated.
+	previous_context: Option<Option<&'a TermDefinition<T, C>>>
+}
+
+impl<'a, T: Id, C: Context<T>> Explanation<'a, T, C> {
+	/// Explains the definition of the given term in the given active context.
+	pub fn new(context: &'a C, term: &'a str) -> Explanation<'a, T, C> {
+		let mut definitions = Vec::new();
+		let mut current = context.get(term);
+		while let Some(definition) = current {
+			definitions.push(definition);
+			current = definition.provenance.as_ref().and_then(|p| p.previous.as_deref())
+		}
+
+		Explanation {
+			term,
+			definitions,
+			previous_context: context.previous_context().map(|previous| previous.get(term))
+		}
+	}
+
+	/// Explained term.
+	pub fn term(&self) -> &str {
+		self.term
+	}
+
+	/// Current definition of the term, if any.
+	pub fn definition(&self) -> Option<&'a TermDefinition<T, C>> {
+		self.definitions.first().copied()
+	}
+
+	/// Current definition of the term, followed by the successive definitions it overrides.
+	pub fn definitions(&self) -> &[&'a TermDefinition<T, C>] {
+		&self.definitions
+	}
+
+	/// Checks if the active context is propagated to node objects descendants.
+	///
+	/// If not, the active context reverts to its previous context when entering a new node object.
+	pub fn is_propagated(&self) -> bool {
+		self.previous_context.is_none()
+	}
+
+	/// Definition of the term once the active context is reverted to its previous context,
+	/// if it is not propagated.
+	pub fn reverted(&self) -> Option<&'a TermDefinition<T, C>> {
+		self.previous_context.flatten()
+	}
+}
+
+fn fmt_definition<T: Id, C: Context<T>>(definition: &TermDefinition<T, C>, f: &mut fmt::Formatter) -> fmt::Result {
+	match &definition.value {
+		Some(Term::Null) | None => write!(f, "null")?,
+		Some(value) if definition.reverse_property => write!(f, "reverse of <{}>", value.as_str())?,
+		Some(Term::Keyword(k)) => write!(f, "{}", k.into_str())?,
+		Some(value) => write!(f, "<{}>", value.as_str())?
+	}
+
+	if let Some(provenance) = &definition.provenance {
+		match &provenance.url {
+			Some(url) => write!(f, ", defined in <{}>", url)?,
+			None => write!(f, ", defined in a context without URL")?
+		}
+
+		if let Some(import) = &provenance.import {
+			write!(f, ", imported from <{}>", import)?
+		}
+
+		if provenance.override_protected {
+			write!(f, " (property-scoped)")?
+		}
+
+		if !provenance.propagate {
+			write!(f, " (not propagated)")?
+		}
+	}
+
+	if definition.protected {
+		write!(f, " (protected)")?
+	}
+
+	Ok(())
+}
+
+impl<'a, T: Id, C: Context<T>> fmt::Display for Explanation<'a, T, C> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.definitions.split_first() {
+			Some((definition, overridden)) => {
+				write!(f, "`{}` is mapped to ", self.term)?;
+				fmt_definition(definition, f)?;
+				for definition in overridden {
+					write!(f, "\n  overriding ")?;
+					fmt_definition(definition, f)?;
+				}
+			},
+			None => write!(f, "`{}` is not defined", self.term)?
+		}
+
+		if let Some(reverted) = self.previous_context {
+			write!(f, "\n  the context is not propagated, ")?;
+			match reverted {
+				Some(definition) => {
+					write!(f, "reverting to ")?;
+					fmt_definition(definition, f)?
+				},
+				None => write!(f, "reverting to no definition")?
+			}
+		}
+
+		Ok(())
+	}
+}
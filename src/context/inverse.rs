@@ -1,6 +1,6 @@
 use std::{
 	cmp::Ordering,
-	collections::HashMap,
+	collections::{BTreeMap, HashMap, HashSet},
 	fmt
 };
 use once_cell::sync::OnceCell;
@@ -9,7 +9,7 @@ use langtag::{
 	LanguageTag
 };
 use mown::Mown;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use crate::{
 	Id,
 	Nullable,
@@ -21,8 +21,11 @@ use crate::{
 	}
 };
 use super::{
-	Context
+	Context,
+	CacheId
 };
+#[cfg(feature = "bundle")]
+use super::bundle;
 
 /// Context that can be inverted.
 /// 
@@ -33,7 +36,83 @@ pub struct Inversible<T: Id, C> {
 	context: C,
 
 	/// Inverse context.
-	inverse: Arc<OnceCell<InverseContext<T>>>
+	inverse: Arc<OnceCell<InverseContext<T>>>,
+
+	/// Inverse contexts of the scoped contexts derived from this context, if shared.
+	scoped: Option<Arc<ScopedInverses<T>>>
+}
+
+/// Inverse contexts of the type-scoped and property-scoped contexts derived from a
+/// [`CompactionContext`](super::CompactionContext), by term and processed context identifier.
+///
+/// Processed contexts evicted from their cache are given a new identifier when processed again,
+/// so the number of kept inverse contexts is bounded:
+/// the least recently used ones are dropped first.
+pub(crate) struct ScopedInverses<T: Id>(Mutex<ScopedInversesInner<T>>);
+
+/// Scoped context, identified by its term and processed context identifier.
+type ScopedKey = (String, CacheId);
+
+/// Inverse context computed once and shared.
+type SharedInverse<T> = Arc<OnceCell<InverseContext<T>>>;
+
+struct ScopedInversesInner<T: Id> {
+	capacity: usize,
+	tick: u64,
+
+	/// Inverse contexts with their last access tick.
+	entries: HashMap<ScopedKey, (SharedInverse<T>, u64)>,
+
+	/// Keys by last access tick.
+	usage: BTreeMap<u64, ScopedKey>
+}
+
+impl<T: Id> ScopedInverses<T> {
+	/// Creates a new set of inverse contexts keeping at most `capacity` of them.
+	pub fn new(capacity: usize) -> ScopedInverses<T> {
+		ScopedInverses(Mutex::new(ScopedInversesInner {
+			capacity,
+			tick: 0,
+			entries: HashMap::new(),
+			usage: BTreeMap::new()
+		}))
+	}
+
+	/// Returns the number of kept inverse contexts.
+	pub fn len(&self) -> usize {
+		self.0.lock().unwrap().entries.len()
+	}
+
+	/// Returns the (possibly not yet computed) inverse of the scoped context of `term`
+	/// with the given identifier.
+	fn get(&self, term: &str, id: &CacheId) -> SharedInverse<T> {
+		let mut guard = self.0.lock().unwrap();
+		let inner = &mut *guard;
+		inner.tick += 1;
+		let tick = inner.tick;
+		let key = (term.to_string(), id.clone());
+		let inverse = match inner.entries.get_mut(&key) {
+			Some((inverse, last_access)) => {
+				let last_access = std::mem::replace(last_access, tick);
+				inner.usage.remove(&last_access);
+				inverse.clone()
+			},
+			None => {
+				let inverse = Arc::new(OnceCell::new());
+				inner.entries.insert(key.clone(), (inverse.clone(), tick));
+				inverse
+			}
+		};
+		inner.usage.insert(tick, key);
+
+		while inner.entries.len() > inner.capacity {
+			let oldest = *inner.usage.keys().next().unwrap();
+			let key = inner.usage.remove(&oldest).unwrap();
+			inner.entries.remove(&key);
+		}
+
+		inverse
+	}
 }
 
 impl<T: Id, C: Clone> Clone for Inversible<T, C> {
@@ -41,7 +120,8 @@ impl<T: Id, C: Clone> Clone for Inversible<T, C> {
 	fn clone(&self) -> Self {
 		Inversible {
 			context: self.context.clone(),
-			inverse: self.inverse.clone()
+			inverse: self.inverse.clone(),
+			scoped: self.scoped.clone()
 		}
 	}
 }
@@ -67,7 +147,8 @@ impl<T: Id, C> Inversible<T, C> {
 	pub fn new(context: C) -> Inversible<T, C> {
 		Inversible {
 			context,
-			inverse: Arc::new(OnceCell::new())
+			inverse: Arc::new(OnceCell::new()),
+			scoped: None
 		}
 	}
 
@@ -77,10 +158,48 @@ impl<T: Id, C> Inversible<T, C> {
 		})
 	}
 
+	/// Wraps the given context along with its shared inverse context,
+	/// and the shared inverse contexts of the scoped contexts derived from it.
+	pub(crate) fn with_inverse(context: C, inverse: Arc<OnceCell<InverseContext<T>>>, scoped: Arc<ScopedInverses<T>>) -> Inversible<T, C> {
+		Inversible {
+			context,
+			inverse,
+			scoped: Some(scoped)
+		}
+	}
+
+	/// Wraps the given context, derived from this one, sharing the scoped inverse contexts of this context.
+	pub(crate) fn derived<D>(&self, context: D) -> Inversible<T, D> {
+		Inversible {
+			context,
+			inverse: Arc::new(OnceCell::new()),
+			scoped: self.scoped.clone()
+		}
+	}
+
+	/// Wraps the given context, obtained by processing the scoped context of `term`
+	/// on top of this one.
+	///
+	/// The inverse of the scoped context is reused if it has already been computed
+	/// for the same term and processed context.
+	pub(crate) fn scoped<D: Context<T>>(&self, term: &str, context: D) -> Inversible<T, D> {
+		let inverse = match (&self.scoped, context.cache_id()) {
+			(Some(scoped), Some(id)) => scoped.get(term, id),
+			_ => Arc::new(OnceCell::new())
+		};
+
+		Inversible {
+			context,
+			inverse,
+			scoped: self.scoped.clone()
+		}
+	}
+
 	pub fn into_owned<'a>(self) -> Inversible<T, Mown<'a, C>> {
 		Inversible {
 			context: Mown::Owned(self.context),
-			inverse: self.inverse
+			inverse: self.inverse,
+			scoped: self.scoped
 		}
 	}
 }
@@ -89,7 +208,8 @@ impl<'a, T: Id, C> Inversible<T, &'a C> {
 	pub fn into_borrowed(self) -> Inversible<T, Mown<'a, C>> {
 		Inversible {
 			context: Mown::Borrowed(self.context),
-			inverse: self.inverse
+			inverse: self.inverse,
+			scoped: self.scoped
 		}
 	}
 }
@@ -98,7 +218,8 @@ impl<'a, T: Id, C> Inversible<T, Mown<'a, C>> {
 	pub fn as_ref(&self) -> Inversible<T, &C> {
 		Inversible {
 			context: self.context.as_ref(),
-			inverse: self.inverse.clone()
+			inverse: self.inverse.clone(),
+			scoped: self.scoped.clone()
 		}
 	}
 }
@@ -154,7 +275,7 @@ impl<T: Id> InverseType<T> {
 	}
 }
 
-type LangDir = Nullable<(Option<LanguageTagBuf>, Option<Direction>)>;
+pub(crate) type LangDir = Nullable<(Option<LanguageTagBuf>, Option<Direction>)>;
 
 struct InverseLang {
 	any: Option<String>,
@@ -336,6 +457,89 @@ impl<T: Id> InverseContext<T> {
 			None => None
 		}
 	}
+
+	/// Returns the set of terms that can be selected by the term selection algorithm.
+	///
+	/// A term definition is not selectable if another term, shorter or lexicographically
+	/// smaller, is always selected in its place.
+	pub fn selectable_terms(&self) -> HashSet<&str> {
+		let mut terms = HashSet::new();
+		for definition in self.map.values() {
+			for container in definition.map.values() {
+				terms.insert(container.any.none.as_str());
+				terms.extend(container.typ.reverse.as_deref());
+				terms.extend(container.typ.any.as_deref());
+				terms.extend(container.typ.map.values().map(String::as_str));
+				terms.extend(container.language.any.as_deref());
+				terms.extend(container.language.map.values().map(String::as_str));
+			}
+		}
+
+		terms
+	}
+
+	/// Returns the binary representation of this inverse context.
+	#[cfg(feature = "bundle")]
+	pub(crate) fn to_raw(&self) -> bundle::RawInverseContext {
+		let mut definitions: Vec<_> = self.map.iter().map(|(term, definition)| {
+			let containers = definition.map.iter().map(|(container, c)| {
+				(bundle::raw_container(container), bundle::RawInverseContainer {
+					language_any: c.language.any.clone(),
+					language: c.language.map.iter().map(|(lang_dir, term)| (bundle::raw_lang_dir(lang_dir), term.clone())).collect(),
+					type_reverse: c.typ.reverse.clone(),
+					type_any: c.typ.any.clone(),
+					types: c.typ.map.iter().map(|(ty, term)| (bundle::raw_type(ty), term.clone())).collect(),
+					none: c.any.none.clone()
+				})
+			}).collect();
+
+			(term.as_str().to_string(), (bundle::RawTerm::from(term), containers))
+		}).collect();
+		definitions.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+		bundle::RawInverseContext {
+			definitions: definitions.into_iter().map(|(_, entry)| entry).collect()
+		}
+	}
+
+	/// Builds an inverse context from its binary representation.
+	#[cfg(feature = "bundle")]
+	pub(crate) fn from_raw(raw: bundle::RawInverseContext) -> Result<InverseContext<T>, bundle::Error> {
+		let mut result = InverseContext::new();
+		for (term, containers) in raw.definitions {
+			let mut definition = InverseDefinition::new();
+			for (container, c) in containers {
+				let mut language = HashMap::new();
+				for (lang_dir, term) in c.language {
+					language.insert(bundle::lang_dir(lang_dir)?, term);
+				}
+
+				let mut types = HashMap::new();
+				for (ty, term) in c.types {
+					types.insert(bundle::typ(ty)?, term);
+				}
+
+				definition.map.insert(bundle::container(container)?, InverseContainer {
+					language: InverseLang {
+						any: c.language_any,
+						map: language
+					},
+					typ: InverseType {
+						reverse: c.type_reverse,
+						any: c.type_any,
+						map: types
+					},
+					any: Any {
+						none: c.none
+					}
+				});
+			}
+
+			result.insert(term.into_term()?, definition);
+		}
+
+		Ok(result)
+	}
 }
 
 impl<'a, T: Id, C: Context<T>> From<&'a C> for InverseContext<T> {
@@ -0,0 +1,251 @@
+//! Context static analysis.
+//!
+//! The [`lint`] function processes a local context on its own, without any document,
+//! and reports definitions that are likely to be mistakes.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use iref::Iri;
+use json::{
+	JsonValue,
+	object::Object as JsonObject
+};
+use crate::{
+	Id,
+	ErrorCode,
+	syntax::{
+		Term,
+		is_keyword,
+		is_keyword_like
+	}
+};
+use super::{
+	Context,
+	Local,
+	Loader,
+	JsonContext,
+	ProcessingOptions,
+	InverseContext
+};
+
+/// Problem found in a context.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Lint {
+	/// The context processing algorithm failed.
+	///
+	/// This includes cyclic IRI mappings, recursive context inclusions and
+	/// protected term redefinitions through `@import`.
+	Processing(ErrorCode),
+
+	/// The term has the form of a keyword, but is not a keyword.
+	///
+	/// Such terms are ignored by the context processing algorithm.
+	KeywordLike(String),
+
+	/// The term is explicitly declared as a prefix with `@prefix`,
+	/// but is never used as such in the context.
+	UnusedPrefix(String),
+
+	/// Multiple terms share the same IRI mapping.
+	DuplicateMapping {
+		/// Shared IRI mapping.
+		iri: String,
+
+		/// Terms with this IRI mapping.
+		terms: Vec<String>
+	},
+
+	/// The scoped context of a term redefines protected terms.
+	///
+	/// This is allowed for property-scoped contexts, but fails with a
+	/// `protected term redefinition` error if the term is used as a type.
+	ProtectedRedefinition {
+		/// Term defining the scoped context.
+		scope: String,
+
+		/// Redefined protected terms.
+		terms: Vec<String>
+	},
+
+	/// The scoped context of a term cannot be processed.
+	ScopedContext {
+		/// Term defining the scoped context.
+		term: String,
+
+		/// Processing error.
+		error: ErrorCode
+	},
+
+	/// The term can never be selected during compaction,
+	/// another term always being selected in its place.
+	Unselectable(String)
+}
+
+impl Lint {
+	/// Checks if this lint denotes a context that cannot be used.
+	pub fn is_error(&self) -> bool {
+		matches!(self, Lint::Processing(_) | Lint::ScopedContext { .. })
+	}
+}
+
+impl fmt::Display for Lint {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Lint::Processing(code) => write!(f, "context processing failed: {}", code.as_str()),
+			Lint::KeywordLike(term) => write!(f, "term `{}` has the form of a keyword and is ignored", term),
+			Lint::UnusedPrefix(term) => write!(f, "term `{}` is declared as a prefix but never used as such", term),
+			Lint::DuplicateMapping { iri, terms } => write!(f, "terms `{}` are all mapped to <{}>", terms.join("`, `"), iri),
+			Lint::ProtectedRedefinition { scope, terms } => write!(f, "scoped context of `{}` redefines protected terms `{}`", scope, terms.join("`, `")),
+			Lint::ScopedContext { term, error } => write!(f, "scoped context of `{}` is invalid: {}", term, error.as_str()),
+			Lint::Unselectable(term) => write!(f, "term `{}` can never be selected during compaction", term)
+		}
+	}
+}
+
+/// Analyzes the given local context.
+///
+/// The context is processed on top of an empty active context, using the given loader
+/// to load remote contexts. If processing fails, the processing error is the only reported lint.
+///
+/// # Example
+/// ```
+/// use json_ld::{NoLoader, context::lint::{lint, Lint}};
+/// # use iref::IriBuf;
+/// # use futures::executor::block_on;
+/// let context = json::parse(r#"{
+///     "@foo": "http://example.org/foo",
+///     "name": "http://schema.org/name",
+///     "fullName": "http://schema.org/name"
+/// }"#).unwrap();
+///
+/// let lints = block_on(lint::<IriBuf, _>(&context, &mut NoLoader, None, Default::default()));
+/// assert!(lints.contains(&Lint::KeywordLike("@foo".to_string())));
+/// assert!(lints.contains(&Lint::Unselectable("fullName".to_string())));
+/// ```
+pub async fn lint<T: Send + Sync + Id, L: Send + Sync + Loader>(local: &JsonValue, loader: &mut L, base_url: Option<Iri<'_>>, options: ProcessingOptions) -> Vec<Lint> where JsonValue: From<L::Output> {
+	let mut lints = Vec::new();
+
+	let active_context = JsonContext::<T>::new(base_url);
+	let context = match Local::<T>::process_with(local, &active_context, loader, base_url, options).await {
+		Ok(processed) => processed.into_inner(),
+		Err(e) => {
+			lints.push(Lint::Processing(e.code()));
+			return lints
+		}
+	};
+
+	let objects = context_objects(local);
+
+	// Keyword-like terms.
+	for object in &objects {
+		for (key, _) in object.iter() {
+			if is_keyword_like(key) && !is_keyword(key) {
+				lints.push(Lint::KeywordLike(key.to_string()))
+			}
+		}
+	}
+
+	// Explicit prefixes never used.
+	let mut strings = HashSet::new();
+	collect_strings(local, &mut strings);
+	for object in &objects {
+		for (key, value) in object.iter() {
+			if let JsonValue::Object(definition) = value {
+				if definition.get("@prefix").and_then(JsonValue::as_bool).unwrap_or(false) {
+					let prefix = format!("{}:", key);
+					if !strings.iter().any(|s| s.starts_with(&prefix)) {
+						lints.push(Lint::UnusedPrefix(key.to_string()))
+					}
+				}
+			}
+		}
+	}
+
+	let mut definitions: Vec<_> = context.definitions().collect();
+	definitions.sort_by_key(|(term, _)| *term);
+
+	// Duplicate IRI mappings.
+	let mut mappings: HashMap<(&str, bool), Vec<String>> = HashMap::new();
+	for (term, definition) in &definitions {
+		if let Some(Term::Ref(r)) = &definition.value {
+			if !definition.prefix {
+				mappings.entry((r.as_str(), definition.reverse_property)).or_default().push(term.to_string())
+			}
+		}
+	}
+
+	let mut duplicates: Vec<_> = mappings.into_iter().filter(|(_, terms)| terms.len() > 1).collect();
+	duplicates.sort();
+	for ((iri, _), terms) in duplicates {
+		lints.push(Lint::DuplicateMapping {
+			iri: iri.to_string(),
+			terms
+		})
+	}
+
+	// Scoped contexts.
+	for (term, definition) in &definitions {
+		if let Some(scoped) = &definition.context {
+			let base_url = definition.base_url();
+			match Local::<T>::process_with(scoped, &context, loader, base_url, options.with_override()).await {
+				Ok(processed) => {
+					let redefined: Vec<String> = definitions.iter().filter(|(t, d)| {
+						d.protected && processed.get(t) != Some(d)
+					}).map(|(t, _)| t.to_string()).collect();
+
+					if !redefined.is_empty() {
+						lints.push(Lint::ProtectedRedefinition {
+							scope: term.to_string(),
+							terms: redefined
+						})
+					}
+				},
+				Err(e) => lints.push(Lint::ScopedContext {
+					term: term.to_string(),
+					error: e.code()
+				})
+			}
+		}
+	}
+
+	// Terms never selected by the inverse context.
+	let inverse = InverseContext::from(&context);
+	let selectable = inverse.selectable_terms();
+	for (term, definition) in &definitions {
+		if definition.value.is_some() && !selectable.contains(term.as_str()) {
+			lints.push(Lint::Unselectable(term.to_string()))
+		}
+	}
+
+	lints
+}
+
+/// Returns the context definitions objects of a local context.
+fn context_objects(local: &JsonValue) -> Vec<&JsonObject> {
+	match local {
+		JsonValue::Object(object) => vec![object],
+		JsonValue::Array(items) => items.iter().flat_map(context_objects).collect(),
+		_ => Vec::new()
+	}
+}
+
+/// Collects every string appearing in the given JSON value, including object keys.
+fn collect_strings<'a>(value: &'a JsonValue, strings: &mut HashSet<&'a str>) {
+	match value {
+		JsonValue::Short(_) | JsonValue::String(_) => {
+			strings.insert(value.as_str().unwrap());
+		},
+		JsonValue::Array(items) => {
+			for item in items {
+				collect_strings(item, strings)
+			}
+		},
+		JsonValue::Object(object) => {
+			for (key, value) in object.iter() {
+				strings.insert(key);
+				collect_strings(value, strings)
+			}
+		},
+		_ => ()
+	}
+}
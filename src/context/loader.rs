@@ -1,6 +1,9 @@
-use futures::future::BoxFuture;
+use futures::future::{BoxFuture, FutureExt};
 use iref::{Iri, IriBuf};
-use crate::Error;
+use crate::{
+	Error,
+	LoaderId
+};
 
 pub struct RemoteContext<C> {
 	url: IriBuf,
@@ -46,4 +49,84 @@ pub trait Loader {
 	type Output;
 
 	fn load_context<'a>(&'a mut self, url: Iri) -> BoxFuture<'a, Result<RemoteContext<Self::Output>, Error>>;
+
+	/// Prefetches the given contexts, that are about to be loaded in order with
+	/// [`load_context`](Loader::load_context).
+	///
+	/// This is called by the context processing algorithm with the remote contexts referenced
+	/// by a local context array, including `@import` entries, so that they can be loaded concurrently.
+	/// Remote contexts whose processing is found in the [`ContextCache`](super::ContextCache)
+	/// are not prefetched.
+	/// Errors must be ignored: they are reported when the context is actually loaded.
+	///
+	/// Prefetching is a capability of the loader: the default implementation does nothing,
+	/// and contexts are then loaded one after the other.
+	/// Remote contexts are only looked for when [`supports_prefetch`](Loader::supports_prefetch) returns `true`.
+	///
+	/// # Example
+	/// ```
+	/// use json_ld::{JsonContext, Loader, LoaderId, RemoteDocument, Error, context::{Local, ContextCache}};
+	/// use futures::future::{BoxFuture, FutureExt};
+	/// use iref::{Iri, IriBuf};
+	/// # use futures::executor::block_on;
+	///
+	/// struct Prefetching {
+	///     id: LoaderId,
+	///     prefetched: Vec<IriBuf>
+	/// }
+	///
+	/// impl Loader for Prefetching {
+	///     type Document = json::JsonValue;
+	///
+	///     fn load<'a>(&'a mut self, url: Iri<'_>) -> BoxFuture<'a, Result<RemoteDocument, Error>> {
+	///         let doc = json::object! { "@context" => json::object! { "@vocab" => url.as_str() } };
+	///         let doc = RemoteDocument::new(doc, url);
+	///         async move { Ok(doc) }.boxed()
+	///     }
+	///
+	///     fn prefetch<'a>(&'a mut self, urls: Vec<IriBuf>) -> BoxFuture<'a, ()> {
+	///         self.prefetched.extend(urls);
+	///         async {}.boxed()
+	///     }
+	///
+	///     fn supports_prefetch(&self) -> bool {
+	///         true
+	///     }
+	///
+	///     fn loader_id(&self) -> Option<LoaderId> {
+	///         Some(self.id)
+	///     }
+	/// }
+	///
+	/// let active_context: JsonContext<IriBuf> = JsonContext::with_cache(None, ContextCache::new());
+	/// let mut loader = Prefetching { id: LoaderId::fresh(), prefetched: Vec::new() };
+	/// let local = json::parse(r#"[ "http://example.org/a", "http://example.org/b" ]"#).unwrap();
+	/// block_on(local.process_with(&active_context, &mut loader, None, Default::default())).unwrap();
+	/// assert_eq!(loader.prefetched.len(), 2);
+	///
+	/// // Both remote contexts have already been processed: nothing is prefetched.
+	/// loader.prefetched.clear();
+	/// let local = json::parse(r#"[ "http://example.org/a", "http://example.org/b", { "c": "http://example.org/c" } ]"#).unwrap();
+	/// block_on(local.process_with(&active_context, &mut loader, None, Default::default())).unwrap();
+	/// assert!(loader.prefetched.is_empty());
+	/// ```
+	fn prefetch_contexts<'a>(&'a mut self, _urls: Vec<IriBuf>) -> BoxFuture<'a, ()> {
+		async {}.boxed()
+	}
+
+	/// Checks if the loader prefetches contexts.
+	///
+	/// The default implementation returns `false`.
+	fn supports_prefetch(&self) -> bool {
+		false
+	}
+
+	/// Identifier of the loader, if any.
+	///
+	/// Processed contexts are only cached for loaders having an identifier,
+	/// and never shared between loaders with different identifiers.
+	/// The default implementation returns `None`.
+	fn loader_id(&self) -> Option<LoaderId> {
+		None
+	}
 }
@@ -3,7 +3,14 @@
 mod definition;
 mod loader;
 mod processing;
+mod builder;
+mod explain;
+mod cache;
+mod compaction;
 pub mod inverse;
+pub mod lint;
+#[cfg(feature = "bundle")]
+pub mod bundle;
 
 use std::collections::HashMap;
 use futures::{
@@ -21,20 +28,32 @@ use crate::{
 	Error,
 	Direction,
 	Id,
-	syntax::Term,
+	Limits,
+	syntax::{
+		Keyword,
+		Term
+	},
 	util
 };
 
 pub use definition::*;
 pub use loader::*;
 pub use processing::*;
+pub use builder::*;
+pub use explain::*;
+pub use cache::{
+	ContextCache,
+	CacheId,
+	DEFAULT_CAPACITY
+};
+pub use compaction::*;
 pub use inverse::{
 	InverseContext,
 	Inversible
 };
 
 /// Options of the Context Processing Algorithm.
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ProcessingOptions {
 	/// The processing mode
 	pub processing_mode: ProcessingMode,
@@ -43,7 +62,10 @@ pub struct ProcessingOptions {
 	pub override_protected: bool,
 
 	/// Propagate the processed context.
-	pub propagate: bool
+	pub propagate: bool,
+
+	/// Resource limits.
+	pub limits: Limits
 }
 
 impl ProcessingOptions {
@@ -74,7 +96,8 @@ impl Default for ProcessingOptions {
 		ProcessingOptions {
 			processing_mode: ProcessingMode::default(),
 			override_protected: false,
-			propagate: true
+			propagate: true,
+			limits: Limits::default()
 		}
 	}
 }
@@ -127,6 +150,25 @@ pub trait Context<T: Id = IriBuf> : Clone {
 	fn previous_context(&self) -> Option<&Self>;
 
 	fn definitions<'a>(&'a self) -> Box<dyn 'a + Iterator<Item = (&'a String, &'a TermDefinition<T, Self>)>>;
+
+	/// Explains the definition of the given term: where it has been defined,
+	/// which definitions it overrides, and whether the context is propagated.
+	fn explain<'a>(&'a self, term: &'a str) -> Explanation<'a, T, Self> {
+		Explanation::new(self, term)
+	}
+
+	/// Cache of processed contexts shared by this context, if any.
+	fn cache(&self) -> Option<&ContextCache<Self>> {
+		None
+	}
+
+	/// Identifier of this context in its cache.
+	///
+	/// Returns `None` if the context is not cached, in which case
+	/// the cache is not consulted when processing a local context on top of it.
+	fn cache_id(&self) -> Option<&CacheId> {
+		None
+	}
 }
 
 /// Mutable JSON-LD context.
@@ -148,6 +190,16 @@ pub trait ContextMut<T: Id = IriBuf>: Context<T> {
 
 	/// Sets the previous context.
 	fn set_previous_context(&mut self, previous: Self);
+
+	/// Sets the cache of processed contexts shared by this context.
+	///
+	/// Contexts that do not support caching ignore it.
+	fn set_cache(&mut self, _cache: Option<ContextCache<Self>>) {}
+
+	/// Sets the identifier of this context in its cache.
+	///
+	/// Contexts that do not support caching ignore it.
+	fn set_cache_id(&mut self, _id: Option<CacheId>) {}
 }
 
 /// Trait for types that are or wrap a mutable context.
@@ -159,6 +211,13 @@ pub trait ContextMutProxy<T: Id = IriBuf> {
 
 	/// Returns a reference to the mutable context.
 	fn deref(&self) -> &Self::Target;
+
+	/// Returns the context along with its inverse context, used by the compaction algorithm.
+	///
+	/// By default the inverse context is computed on demand, each time this function is called.
+	fn inversible(&self) -> Inversible<T, &Self::Target> {
+		Inversible::new(self.deref())
+	}
 }
 
 /// Local context used for context expansion.
@@ -210,6 +269,11 @@ impl<L, C> Processed<L, C> {
 	pub fn into_inner(self) -> C {
 		self.processed
 	}
+
+	/// Consumes the wrapper and returns the original local context and the processed context.
+	pub fn into_parts(self) -> (L, C) {
+		(self.local, self.processed)
+	}
 }
 
 impl<T: Id, L, C: ContextMut<T>> ContextMutProxy<T> for Processed<L, C> {
@@ -256,7 +320,7 @@ impl<L, C> std::convert::AsRef<C> for Processed<L, C> {
 	}
 }
 
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone)]
 pub struct JsonContext<T: Id = IriBuf> {
 	original_base_url: Option<IriBuf>,
 	base_iri: Option<IriBuf>,
@@ -264,10 +328,16 @@ pub struct JsonContext<T: Id = IriBuf> {
 	default_language: Option<LanguageTagBuf>,
 	default_base_direction: Option<Direction>,
 	previous_context: Option<Box<Self>>,
-	definitions: HashMap<String, TermDefinition<T, Self>>
+	definitions: HashMap<String, TermDefinition<T, Self>>,
+	cache: Option<ContextCache<Self>>,
+	cache_id: Option<CacheId>
 }
 
 impl<T: Id> JsonContext<T> {
+	/// Create a newly-initialized active context with the given *base IRI*.
+	///
+	/// The context does not use any cache of processed contexts
+	/// (see [`with_cache`](JsonContext::with_cache)).
 	pub fn new(base_iri: Option<Iri>) -> JsonContext<T> {
 		JsonContext {
 			original_base_url: base_iri.map(|iri| iri.into()),
@@ -276,9 +346,121 @@ impl<T: Id> JsonContext<T> {
 			default_language: None,
 			default_base_direction: None,
 			previous_context: None,
-			definitions: HashMap::new()
+			definitions: HashMap::new(),
+			cache: None,
+			cache_id: Some(CacheId::Initial(base_iri.map(|iri| iri.into())))
 		}
 	}
+
+	/// Create a newly-initialized active context with the given *base IRI*,
+	/// sharing the given cache of processed contexts.
+	///
+	/// Every context obtained by processing a local context on top of this one shares the
+	/// same cache. Use it as the initial context of [`Document::expand_with`](crate::Document::expand_with)
+	/// or as the compaction context so that the same local and remote contexts are only processed once.
+	///
+	/// # Example
+	/// ```
+	/// use json_ld::{JsonContext, Document, NoLoader, context::ContextCache};
+	/// # use iref::IriBuf;
+	/// # use futures::executor::block_on;
+	/// let cache = ContextCache::new();
+	/// let context: JsonContext<IriBuf> = JsonContext::with_cache(None, cache.clone());
+	///
+	/// let doc = json::parse(r#"{
+	///     "@context": { "name": "http://schema.org/name" },
+	///     "name": "Jane Doe"
+	/// }"#).unwrap();
+	///
+	/// block_on(doc.expand_with(None, &context, &mut NoLoader, Default::default())).unwrap();
+	/// assert_eq!(cache.len(), 1);
+	///
+	/// // The embedded context is not processed again.
+	/// block_on(doc.expand_with(None, &context, &mut NoLoader, Default::default())).unwrap();
+	/// assert_eq!(cache.len(), 1);
+	/// ```
+	pub fn with_cache(base_iri: Option<Iri>, cache: ContextCache<Self>) -> JsonContext<T> {
+		let mut context = Self::new(base_iri);
+		context.cache = Some(cache);
+		context
+	}
+}
+
+/// Compares the contexts content, regardless of their cache.
+impl<T: Id> PartialEq for JsonContext<T> {
+	fn eq(&self, other: &Self) -> bool {
+		self.original_base_url == other.original_base_url
+			&& self.base_iri == other.base_iri
+			&& self.vocabulary == other.vocabulary
+			&& self.default_language == other.default_language
+			&& self.default_base_direction == other.default_base_direction
+			&& self.previous_context == other.previous_context
+			&& self.definitions == other.definitions
+	}
+}
+
+impl<T: Id> Eq for JsonContext<T> {}
+
+/// Produces an equivalent JSON context.
+///
+/// Processing the produced context on top of an empty active context gives back the same
+/// term definitions, vocabulary mapping, default language and base direction.
+/// The previous context, used when the context is not propagated, is not part of the output.
+/// Terms are sorted, and each term definition is written in its minimal form.
+///
+/// # Example
+/// ```
+/// use json_ld::{JsonContext, context::{Local, ContextBuilder}, syntax::ContainerType, util::AsJson, NoLoader};
+/// # use iref::IriBuf;
+/// # use futures::executor::block_on;
+/// let context: JsonContext<IriBuf> = ContextBuilder::new()
+///     .vocab("http://schema.org/")
+///     .prefix("foaf", "http://xmlns.com/foaf/0.1/")
+///     .reverse("children", "http://schema.org/parent")
+///     .container("tags", ContainerType::Set)
+///     .protected()
+///     .build().unwrap();
+///
+/// let json = context.as_json();
+/// assert_eq!(json["@protected"], true);
+/// assert_eq!(json["foaf"], "http://xmlns.com/foaf/0.1/");
+///
+/// let processed = block_on(Local::<IriBuf>::process::<JsonContext<IriBuf>, _>(&json, &mut NoLoader, None)).unwrap().into_inner();
+/// assert!(processed == context);
+/// ```
+impl<T: Id> util::AsJson for JsonContext<T> {
+	fn as_json(&self) -> JsonValue {
+		let mut obj = json::object::Object::new();
+
+		if let Some(base_iri) = &self.base_iri {
+			obj.insert(Keyword::Base.into_str(), base_iri.as_str().into())
+		}
+
+		if let Some(vocab) = &self.vocabulary {
+			obj.insert(Keyword::Vocab.into_str(), vocab.as_json())
+		}
+
+		if let Some(lang) = &self.default_language {
+			obj.insert(Keyword::Language.into_str(), lang.as_json())
+		}
+
+		if let Some(dir) = self.default_base_direction {
+			obj.insert(Keyword::Direction.into_str(), dir.as_json())
+		}
+
+		let protected = !self.definitions.is_empty() && self.definitions.values().all(|def| def.protected);
+		if protected {
+			obj.insert(Keyword::Protected.into_str(), true.into())
+		}
+
+		let mut terms: Vec<_> = self.definitions.iter().collect();
+		terms.sort_by_key(|(term, _)| *term);
+		for (term, definition) in terms {
+			obj.insert(term, definition.as_json_for(term, protected))
+		}
+
+		JsonValue::Object(obj)
+	}
 }
 
 impl<T: Id> ContextMutProxy<T> for JsonContext<T> {
@@ -291,15 +473,7 @@ impl<T: Id> ContextMutProxy<T> for JsonContext<T> {
 
 impl<T: Id> Default for JsonContext<T> {
 	fn default() -> JsonContext<T> {
-		JsonContext {
-			original_base_url: None,
-			base_iri: None,
-			vocabulary: None,
-			default_language: None,
-			default_base_direction: None,
-			previous_context: None,
-			definitions: HashMap::new()
-		}
+		JsonContext::new(None)
 	}
 }
 
@@ -356,10 +530,19 @@ impl<T: Id> Context<T> for JsonContext<T> {
 	fn definitions<'a>(&'a self) -> Box<dyn 'a + Iterator<Item = (&'a String, &'a TermDefinition<T, Self>)>> {
 		Box::new(self.definitions.iter())
 	}
+
+	fn cache(&self) -> Option<&ContextCache<Self>> {
+		self.cache.as_ref()
+	}
+
+	fn cache_id(&self) -> Option<&CacheId> {
+		self.cache_id.as_ref()
+	}
 }
 
 impl<T: Id> ContextMut<T> for JsonContext<T> {
 	fn set(&mut self, term: &str, definition: Option<TermDefinition<T, Self>>) -> Option<TermDefinition<T, Self>> {
+		self.cache_id = None;
 		match definition {
 			Some(def) => {
 				self.definitions.insert(term.to_string(), def)
@@ -371,6 +554,7 @@ impl<T: Id> ContextMut<T> for JsonContext<T> {
 	}
 
 	fn set_base_iri(&mut self, iri: Option<Iri>) {
+		self.cache_id = None;
 		self.base_iri = match iri {
 			Some(iri) => {
 				let mut iri_buf: IriBuf = iri.into();
@@ -382,18 +566,35 @@ impl<T: Id> ContextMut<T> for JsonContext<T> {
 	}
 
 	fn set_vocabulary(&mut self, vocab: Option<Term<T>>) {
+		self.cache_id = None;
 		self.vocabulary = vocab;
 	}
 
 	fn set_default_language(&mut self, lang: Option<LanguageTagBuf>) {
+		self.cache_id = None;
 		self.default_language = lang;
 	}
 
 	fn set_default_base_direction(&mut self, dir: Option<Direction>) {
+		self.cache_id = None;
 		self.default_base_direction = dir;
 	}
 
 	fn set_previous_context(&mut self, previous: Self) {
+		self.cache_id = None;
 		self.previous_context = Some(Box::new(previous))
 	}
+
+	fn set_cache(&mut self, cache: Option<ContextCache<Self>>) {
+		// Only newly-initialized contexts are identified the same way in every cache.
+		if !matches!(self.cache_id, Some(CacheId::Initial(_))) {
+			self.cache_id = None;
+		}
+
+		self.cache = cache
+	}
+
+	fn set_cache_id(&mut self, id: Option<CacheId>) {
+		self.cache_id = id
+	}
 }
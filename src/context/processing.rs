@@ -35,14 +35,31 @@ use super::{
 	ContextMut,
 	Processed,
 	Loader,
-	TermDefinition
+	TermDefinition,
+	Origin,
+	cache::{
+		self,
+		Source
+	}
 };
 
 impl<T: Id> Local<T> for JsonValue {
 	/// Load a local context.
 	fn process_full<'a, 's: 'a, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(&'s self, active_context: &'a C, stack: ProcessingStack, loader: &'a mut L, base_url: Option<Iri<'a>>, options: ProcessingOptions) -> BoxFuture<'a, Result<Processed<&'s Self, C>, Error>> where C::LocalContext: Send + Sync + From<L::Output> + From<Self>, L::Output: Into<Self>, T: Send + Sync {
 		async move {
-			Ok(Processed::new(self, process_context(active_context, self, stack, loader, base_url, options).await?))
+			let cached = cache::key(active_context, loader.loader_id(), || Source::Local(self.dump()), base_url, options, &stack);
+			if let Some((cache, key)) = &cached {
+				if let Some(result) = cache.get(key) {
+					return Ok(Processed::new(self, result))
+				}
+			}
+
+			let mut result = process_context(active_context, self, stack, loader, base_url, options).await?;
+			if let Some((cache, key)) = cached {
+				cache::insert(&cache, key, &mut result)
+			}
+
+			Ok(Processed::new(self, result))
 		}.boxed()
 	}
 }
@@ -145,6 +162,81 @@ impl ProcessingStack {
 			true
 		}
 	}
+
+	/// Returns the URLs of the stack, from the most recently loaded context.
+	pub(crate) fn urls(&self) -> Vec<IriBuf> {
+		let mut urls = Vec::new();
+		let mut current = self.head.as_ref();
+		while let Some(node) = current {
+			urls.push(node.url.clone());
+			current = node.previous.as_ref();
+		}
+
+		urls
+	}
+}
+
+/// Returns the remote contexts referenced by the given local context items,
+/// either directly or through an `@import` entry, that would be loaded by the
+/// context processing algorithm.
+fn remote_context_urls(local_context: &[JsonValue], base_url: Option<Iri>, remote_contexts: &ProcessingStack) -> Vec<IriBuf> {
+	let mut urls: Vec<IriBuf> = Vec::new();
+	for context in local_context {
+		let (value, is_import) = match context {
+			JsonValue::String(_) | JsonValue::Short(_) => (context.as_str(), false),
+			JsonValue::Object(context) => (context.get(Keyword::Import.into()).and_then(JsonValue::as_str), true),
+			_ => (None, false)
+		};
+
+		if let Some(url) = value.and_then(|value| IriRef::new(value).ok()).and_then(|iri_ref| resolve_iri(iri_ref, base_url)) {
+			if (is_import || !remote_contexts.cycle(url.as_iri())) && !urls.contains(&url) {
+				urls.push(url)
+			}
+		}
+	}
+
+	urls
+}
+
+/// Returns the remote contexts referenced by the given local context items that should be
+/// prefetched before processing them on top of the given active context.
+///
+/// Leading remote contexts already processed on top of the current result are found in the
+/// cache of processed contexts and skipped.
+/// The result of processing a context that is not cached is unknown, so the remote contexts
+/// referenced after it are always included.
+fn uncached_remote_context_urls<T: Id, C: Context<T>, L: Loader>(active_context: &C, local_context: &[JsonValue], loader: &L, base_url: Option<Iri>, remote_contexts: &ProcessingStack, options: ProcessingOptions) -> Vec<IriBuf> {
+	let options = ProcessingOptions {
+		processing_mode: options.processing_mode,
+		override_protected: false,
+		propagate: true,
+		limits: options.limits
+	};
+
+	let (cache, mut id, loader_id) = match (active_context.cache(), active_context.cache_id(), loader.loader_id()) {
+		(Some(cache), Some(id), Some(loader_id)) => (cache, id.clone(), loader_id),
+		_ => return remote_context_urls(local_context, base_url, remote_contexts)
+	};
+
+	let mut stack = remote_contexts.clone();
+	let mut cached = 0;
+	for context in local_context {
+		let url = match context.as_str().and_then(|value| IriRef::new(value).ok()).and_then(|iri_ref| resolve_iri(iri_ref, base_url)) {
+			Some(url) => url,
+			None => break
+		};
+
+		if stack.push(url.as_iri()) {
+			match cache.cached_id(&cache::key_of(&id, loader_id, Source::Remote(url), None, options, &stack)) {
+				Some(next_id) => id = next_id,
+				None => break
+			}
+		}
+
+		cached += 1
+	}
+
+	remote_context_urls(&local_context[cached..], base_url, remote_contexts)
 }
 
 // This function tries to follow the recommended context proessing algorithm.
@@ -192,6 +284,15 @@ fn process_context<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: S
 		// 4) If local context is not an array, set it to an array containing only local context.
 		let local_context = as_array(local_context);
 
+		// Load the remote contexts referenced by the array concurrently, if supported by the loader,
+		// before processing them in order.
+		if local_context.len() > 1 && loader.supports_prefetch() {
+			let urls = uncached_remote_context_urls(active_context, local_context, loader, base_url, &remote_contexts, options);
+			if urls.len() > 1 {
+				loader.prefetch_contexts(urls).await
+			}
+		}
+
 		// 5) For each item context in local context:
 		for context in local_context {
 			match context {
@@ -212,6 +313,7 @@ fn process_context<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: S
 						// `base_iri` and `original_base_url` to the value of `original_base_url` in
 						// active context, ...
 						result = C::new(active_context.original_base_url());
+						result.set_cache(previous_result.cache().cloned());
 
 						// ... and, if `propagate` is `false`, `previous_context` in `result` to the
 						// previous value of `result`.
@@ -253,6 +355,20 @@ fn process_context<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: S
 					// context has been detected and processing is aborted.
 					// Set loaded context to the value of that entry.
 					if remote_contexts.push(context.as_iri()) {
+						let new_options = ProcessingOptions {
+							processing_mode: options.processing_mode,
+							override_protected: false,
+							propagate: true,
+							limits: options.limits
+						};
+
+						// The context may have already been loaded and processed on top of `result`.
+						let cached = cache::key(&result, loader.loader_id(), || Source::Remote(context.clone()), None, new_options, &remote_contexts);
+						if let Some(cached_result) = cached.as_ref().and_then(|(cache, key)| cache.get(key)) {
+							result = cached_result;
+							continue
+						}
+
 						let context_document = loader.load_context(context.as_iri()).await?.cast::<JsonValue>();
 						let loaded_context = context_document.context();
 
@@ -260,13 +376,10 @@ fn process_context<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: S
 						// Set result to the result of recursively calling this algorithm, passing result
 						// for active context, loaded context for local context, the documentUrl of context
 						// document for base URL, and a copy of remote contexts.
-						let new_options = ProcessingOptions {
-							processing_mode: options.processing_mode,
-							override_protected: false,
-							propagate: true
-						};
-
 						result = loaded_context.process_full(&result, remote_contexts.clone(), loader, Some(context_document.url()), new_options).await?.into_inner();
+						if let Some((cache, key)) = cached {
+							cache::insert(&cache, key, &mut result)
+						}
 						// result = process_context(&result, loaded_context, remote_contexts, loader, Some(context_document.url()), new_options).await?
 					}
 				},
@@ -288,6 +401,10 @@ fn process_context<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: S
 						}
 					}
 
+					// URL of the imported context, and keys coming from it.
+					let mut import_url = None;
+					let mut imported_keys = Vec::new();
+
 					// 5.6) If context has an @import entry:
 					let context = if let Some(import_value) = context.get(Keyword::Import.into()) {
 						// 5.6.1) If processing mode is json-ld-1.0, an invalid context entry error
@@ -328,9 +445,12 @@ fn process_context<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: S
 								for (key, value) in import_context.iter() {
 									if context.get(key).is_none() {
 										context.insert(key, value.clone());
+										imported_keys.push(key.to_string());
 									}
 								}
 
+								import_url = Some(import);
+
 								JsonObjectRef::Owned(context)
 							} else {
 								return Err(ErrorCode::InvalidRemoteContext.into())
@@ -444,6 +564,15 @@ fn process_context<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: S
 						}
 					}
 
+					// Origin of the new definitions.
+					let origin = Origin {
+						url: base_url.map(IriBuf::from),
+						import: import_url,
+						imported: imported_keys,
+						override_protected: options.override_protected,
+						propagate: options.propagate
+					};
+
 					// 5.12) Create a map `defined` to keep track of whether or not a term
 					// has already been defined or is currently being defined during recursion.
 					let mut defined = HashMap::new();
@@ -465,7 +594,7 @@ fn process_context<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: S
 						match key {
 							"@base" | "@direction" | "@import" | "@language" | "@propagate" | "@protected" | "@version" | "@vocab" => (),
 							_ => {
-								define(&mut result, context.as_ref(), key, &mut defined, remote_contexts.clone(), loader, base_url, protected, options).await?
+								define(&mut result, context.as_ref(), &origin, key, &mut defined, remote_contexts.clone(), loader, base_url, protected, options).await?
 							}
 						}
 					}
@@ -508,7 +637,7 @@ fn is_gen_delim(c: char) -> bool {
 	}
 }
 
-fn is_gen_delim_or_blank<T: Id>(t: &Term<T>) -> bool {
+pub(crate) fn is_gen_delim_or_blank<T: Id>(t: &Term<T>) -> bool {
 	match t {
 		Term::Keyword(_) => false,
 		Term::Ref(Reference::Blank(_)) => true,
@@ -546,7 +675,7 @@ fn contains_between_boundaries(id: &str, c: char) -> bool {
 
 /// Follows the `https://www.w3.org/TR/json-ld11-api/#create-term-definition` algorithm.
 /// Default value for `base_url` is `None`. Default values for `protected` and `override_protected` are `false`.
-pub fn define<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(active_context: &'a mut C, local_context: &'a JsonObject, term: &'a str, defined: &'a mut HashMap<String, bool>, remote_contexts: ProcessingStack, loader: &'a mut L, base_url: Option<Iri<'a>>, protected: bool, options: ProcessingOptions) -> BoxFuture<'a, Result<(), Error>> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
+pub fn define<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(active_context: &'a mut C, local_context: &'a JsonObject, origin: &'a Origin, term: &'a str, defined: &'a mut HashMap<String, bool>, remote_contexts: ProcessingStack, loader: &'a mut L, base_url: Option<Iri<'a>>, protected: bool, options: ProcessingOptions) -> BoxFuture<'a, Result<(), Error>> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
 	// let term = term.to_string();
 	// let base_url = if let Some(base_url) = base_url {
 	// 	Some(IriBuf::from(base_url))
@@ -680,7 +809,7 @@ pub fn define<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send +
 						if let Some(typ) = type_value.as_str() {
 							// Set `typ` to the result of IRI expanding type, using local context,
 							// and defined.
-							match expand_iri(active_context, typ, false, true, local_context, defined, remote_contexts.clone(), loader, options).await? {
+							match expand_iri(active_context, typ, false, true, local_context, origin, defined, remote_contexts.clone(), loader, options).await? {
 								Lenient::Ok(typ) => {
 									// If the expanded type is @json or @none, and processing mode is
 									// json-ld-1.0, an invalid type mapping error has been detected and
@@ -727,7 +856,7 @@ pub fn define<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send +
 							// If the result does not have the form of an IRI or a blank node
 							// identifier, an invalid IRI mapping error has been detected and
 							// processing is aborted.
-							match expand_iri(active_context, reverse_value, false, true, local_context, defined, remote_contexts, loader, options).await? {
+							match expand_iri(active_context, reverse_value, false, true, local_context, origin, defined, remote_contexts, loader, options).await? {
 								Lenient::Ok(Term::Ref(mapping)) => {
 									definition.value = Some(Term::Ref(mapping))
 								},
@@ -766,6 +895,7 @@ pub fn define<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send +
 							// Set the term definition of `term` in `active_context` to
 							// `definition` and the value associated with `defined`'s entry `term`
 							// to `true` and return.
+							definition.provenance = Some(origin.provenance(term, previous_definition));
 							active_context.set(term, Some(definition.into()));
 							defined.insert(term.to_string(), true);
 							return Ok(())
@@ -798,7 +928,7 @@ pub fn define<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send +
 								// Otherwise, set the IRI mapping of `definition` to the result
 								// of IRI expanding the value associated with the `@id` entry,
 								// using `local_context`, and `defined`.
-								definition.value = match expand_iri(active_context, id_value, false, true, local_context, defined, remote_contexts.clone(), loader, options).await? {
+								definition.value = match expand_iri(active_context, id_value, false, true, local_context, origin, defined, remote_contexts.clone(), loader, options).await? {
 									Lenient::Ok(value) => {
 										// if it equals `@context`, an invalid keyword alias error has
 										// been detected and processing is aborted.
@@ -829,7 +959,7 @@ pub fn define<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send +
 									// `local_context`, and `defined`, is not the same as the
 									// IRI mapping of definition, an invalid IRI mapping error
 									// has been detected and processing is aborted.
-									if let Lenient::Ok(expanded_term) = expand_iri(active_context, term, false, true, local_context, defined, remote_contexts.clone(), loader, options).await? {
+									if let Lenient::Ok(expanded_term) = expand_iri(active_context, term, false, true, local_context, origin, defined, remote_contexts.clone(), loader, options).await? {
 										// if !iri_eq_opt(&Some(expanded_term), &definition.value) {
 										// 	return Err(ErrorCode::InvalidIriMapping.into())
 										// }
@@ -868,7 +998,7 @@ pub fn define<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send +
 						// context a dependency has been found.
 						// Use this algorithm recursively passing `active_context`,
 						// `local_context`, the prefix as term, and `defined`.
-						define(active_context, local_context, prefix, defined, remote_contexts.clone(), loader, None, false, options.with_no_override()).await?;
+						define(active_context, local_context, origin, prefix, defined, remote_contexts.clone(), loader, None, false, options.with_no_override()).await?;
 
 						// If `term`'s prefix has a term definition in `active_context`, set the
 						// IRI mapping of `definition` to the result of concatenating the value
@@ -1042,7 +1172,14 @@ pub fn define<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send +
 						// Invoke the Context Processing algorithm using the `active_context`,
 						// `context` as local context, `base_url`, and `true` for override
 						// protected.
-						if let Err(_) = process_context(active_context, context, remote_contexts.clone(), loader, base_url, options.with_override()).await {
+						let mut scoped_options = options.with_override();
+						scoped_options.limits = options.limits.scoped()?;
+						if let Err(e) = process_context(active_context, context, remote_contexts.clone(), loader, base_url, scoped_options).await {
+							// Exceeded resource limits are reported as is.
+							if e.code().is_limit() {
+								return Err(e)
+							}
+
 							// If any error is detected, an invalid scoped context error has been
 							// detected and processing is aborted.
 							return Err(ErrorCode::InvalidScopedContext.into())
@@ -1164,12 +1301,12 @@ pub fn define<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send +
 
 					// If override protected is false and previous_definition exists and is protected;
 					if !options.override_protected {
-						if let Some(previous_definition) = previous_definition {
+						if let Some(previous_definition) = &previous_definition {
 							if previous_definition.protected {
 								// If `definition` is not the same as `previous_definition`
 								// (other than the value of protected), a protected term
 								// redefinition error has been detected, and processing is aborted.
-								if definition != previous_definition {
+								if definition != *previous_definition {
 									return Err(ErrorCode::ProtectedTermRedefinition.into())
 								}
 
@@ -1182,6 +1319,7 @@ pub fn define<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send +
 
 					// Set the term definition of `term` in `active_context` to `definition` and
 					// set the value associated with `defined`'s entry term to true.
+					definition.provenance = Some(origin.provenance(term, previous_definition));
 					active_context.set(term, Some(definition.into()));
 					defined.insert(term.to_string(), true);
 				}
@@ -1194,7 +1332,7 @@ pub fn define<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send +
 }
 
 /// Default values for `document_relative` and `vocab` should be `false` and `true`.
-pub fn expand_iri<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(active_context: &'a mut C, value: &str, document_relative: bool, vocab: bool, local_context: &'a JsonObject, defined: &'a mut HashMap<String, bool>, remote_contexts: ProcessingStack, loader: &'a mut L, options: ProcessingOptions) -> impl 'a + Future<Output = Result<Lenient<Term<T>>, Error>> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
+pub fn expand_iri<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(active_context: &'a mut C, value: &str, document_relative: bool, vocab: bool, local_context: &'a JsonObject, origin: &'a Origin, defined: &'a mut HashMap<String, bool>, remote_contexts: ProcessingStack, loader: &'a mut L, options: ProcessingOptions) -> impl 'a + Future<Output = Result<Lenient<Term<T>>, Error>> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
 	let value = value.to_string();
 	async move {
 		if let Ok(keyword) = Keyword::try_from(value.as_ref()) {
@@ -1212,7 +1350,7 @@ pub fn expand_iri<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Se
 			// algorithm, passing active context, local context, value as term, and defined. This will
 			// ensure that a term definition is created for value in active context during Context
 			// Processing.
-			define(active_context, local_context, value.as_ref(), defined, remote_contexts.clone(), loader, None, false, options.with_no_override()).await?;
+			define(active_context, local_context, origin, value.as_ref(), defined, remote_contexts.clone(), loader, None, false, options.with_no_override()).await?;
 
 			if let Some(term_definition) = active_context.get(value.as_ref()) {
 				// If active context has a term definition for value, and the associated IRI mapping
@@ -1261,7 +1399,7 @@ pub fn expand_iri<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Se
 					// algorithm, passing active context, local context, prefix as term, and defined.
 					// This will ensure that a term definition is created for prefix in active context
 					// during Context Processing.
-					define(active_context, local_context, prefix, defined, remote_contexts, loader, None, false, options.with_no_override()).await?;
+					define(active_context, local_context, origin, prefix, defined, remote_contexts, loader, None, false, options.with_no_override()).await?;
 
 					// If active context contains a term definition for prefix having a non-null IRI
 					// mapping and the prefix flag of the term definition is true, return the result
@@ -0,0 +1,225 @@
+//! Semantic diff between documents.
+//!
+//! Documents are compared at the graph level: both documents are converted into RDF
+//! datasets, whose blank nodes are relabelled using a [canonical labelling](crate::rdf::canonical_labelling).
+//! Since the label of a blank node only depends on its connected component,
+//! blank nodes whose component is unchanged are matched together.
+//! The blank nodes of a changed component are reported as removed and added again,
+//! along with their triples.
+
+use std::collections::HashMap;
+use json::JsonValue;
+use crate::{
+	Id,
+	Reference,
+	ExpandedDocument,
+	syntax::Keyword,
+	util::AsJson,
+	rdf::{
+		self,
+		Quad,
+		Term,
+		Literal,
+		Dataset
+	},
+	xsd
+};
+
+/// Differences between two datasets.
+pub struct Diff<T: Id> {
+	/// Quads present in the new dataset only.
+	pub added: Dataset<T>,
+
+	/// Quads present in the old dataset only.
+	pub removed: Dataset<T>
+}
+
+/// Property values added to and removed from a node.
+pub struct NodeDiff<'a, T: Id> {
+	/// Added values, by property.
+	pub added: HashMap<&'a T, Vec<&'a Term<T>>>,
+
+	/// Removed values, by property.
+	pub removed: HashMap<&'a T, Vec<&'a Term<T>>>
+}
+
+impl<'a, T: Id> NodeDiff<'a, T> {
+	fn new() -> NodeDiff<'a, T> {
+		NodeDiff {
+			added: HashMap::new(),
+			removed: HashMap::new()
+		}
+	}
+}
+
+/// Node identifier in a given graph.
+pub type NodeKey<'a, T> = (Option<&'a Reference<T>>, &'a Reference<T>);
+
+impl<T: Id> Diff<T> {
+	/// Computes the differences between the given datasets.
+	pub fn new(old: &Dataset<T>, new: &Dataset<T>) -> Diff<T> {
+		let old = rdf::canonicalize(old);
+		let new = rdf::canonicalize(new);
+
+		Diff {
+			added: new.difference(&old).cloned().collect(),
+			removed: old.difference(&new).cloned().collect()
+		}
+	}
+
+	/// Checks if there are no differences.
+	pub fn is_empty(&self) -> bool {
+		self.added.is_empty() && self.removed.is_empty()
+	}
+
+	/// Groups the differences by node, identified by their graph and subject.
+	pub fn by_node(&self) -> HashMap<NodeKey<'_, T>, NodeDiff<'_, T>> {
+		let mut nodes: HashMap<NodeKey<T>, NodeDiff<T>> = HashMap::new();
+
+		for quad in &self.added {
+			let node = nodes.entry((quad.graph.as_ref(), &quad.subject)).or_insert_with(NodeDiff::new);
+			node.added.entry(&quad.predicate).or_default().push(&quad.object)
+		}
+
+		for quad in &self.removed {
+			let node = nodes.entry((quad.graph.as_ref(), &quad.subject)).or_insert_with(NodeDiff::new);
+			node.removed.entry(&quad.predicate).or_default().push(&quad.object)
+		}
+
+		nodes
+	}
+}
+
+/// Computes the differences between two expanded documents.
+///
+/// # Example
+/// ```
+/// use json_ld::{Reference, BlankId, Node, Object, Value, ExpandedDocument, object::Literal, diff, util::AsJson};
+/// # use iref::IriBuf;
+/// let name = Reference::Id(IriBuf::new("http://schema.org/name").unwrap());
+/// let doc = |label: &str, value: &str| -> ExpandedDocument<IriBuf> {
+///     let mut node = Node::with_id(Reference::Blank(BlankId::new(label)).into());
+///     node.insert(name.clone(), Object::Value(Value::Literal(Literal::String(value.to_string()), None)).into());
+///     vec![Object::Node(node).into()].into_iter().collect()
+/// };
+///
+/// assert!(diff::diff(&doc("a", "Alice"), &doc("b", "Alice")).is_empty());
+///
+/// let d = diff::diff(&doc("a", "Alice"), &doc("a", "Bob"));
+/// assert_eq!(d.added.len(), 1);
+/// assert_eq!(d.removed.len(), 1);
+///
+/// // The patch is sorted, so that it can be compared with other patches.
+/// let new: ExpandedDocument<IriBuf> = doc("x", "Bob").into_iter().chain(doc("y", "Eve")).chain(doc("z", "Alice")).collect();
+/// assert_eq!(diff::diff(&doc("a", "Alice"), &new).as_json().dump(), r#"{"added":[{"@id":"_:c14n5d43fd6a59c81174","http://schema.org/name":[{"@value":"Bob"}]},{"@id":"_:c14n6e6a296fb7849a59","http://schema.org/name":[{"@value":"Eve"}]}],"removed":[]}"#);
+///
+/// // Adding an unrelated blank node does not rename the others.
+/// let old: ExpandedDocument<IriBuf> = doc("a", "A").into_iter().chain(doc("b", "B")).collect();
+/// let new: ExpandedDocument<IriBuf> = doc("a", "A").into_iter().chain(doc("b", "B")).chain(doc("z", "Z")).collect();
+/// let d = diff::diff(&old, &new);
+/// assert_eq!(d.added.len(), 1);
+/// assert!(d.removed.is_empty());
+/// ```
+pub fn diff<T: Id>(old: &ExpandedDocument<T>, new: &ExpandedDocument<T>) -> Diff<T> {
+	Diff::new(&rdf::to_rdf(old), &rdf::to_rdf(new))
+}
+
+/// Produces a patch-like JSON-LD document of the form
+/// `{ "added": [ ... ], "removed": [ ... ] }`, where each entry is a
+/// flattened expanded JSON-LD document.
+impl<T: Id> AsJson for Diff<T> {
+	fn as_json(&self) -> JsonValue {
+		let mut result = json::object::Object::new();
+		result.insert("added", dataset_to_json(&self.added));
+		result.insert("removed", dataset_to_json(&self.removed));
+		JsonValue::Object(result)
+	}
+}
+
+fn term_to_json<T: Id>(term: &Term<T>) -> JsonValue {
+	let mut obj = json::object::Object::new();
+	match term {
+		Term::Ref(r) => {
+			obj.insert(Keyword::Id.into_str(), r.as_str().into());
+		},
+		Term::Literal(Literal::Typed(value, ty)) => {
+			obj.insert(Keyword::Value.into_str(), value.as_str().into());
+			if ty.as_iri().as_str() != xsd::Datatype::String.as_str() {
+				obj.insert(Keyword::Type.into_str(), ty.as_json());
+			}
+		},
+		Term::Literal(Literal::LangString(value, lang)) => {
+			obj.insert(Keyword::Value.into_str(), value.as_str().into());
+			obj.insert(Keyword::Language.into_str(), lang.as_str().into());
+		}
+	}
+
+	JsonValue::Object(obj)
+}
+
+/// Quads grouped by graph and subject.
+type Graphs<'a, T> = Vec<(Option<&'a Reference<T>>, Vec<(&'a Reference<T>, Vec<&'a Quad<T>>)>)>;
+
+/// Converts a set of quads into a flattened expanded document.
+///
+/// Graphs, nodes, properties and values are sorted by their N-Quads form,
+/// so that equal datasets give the same document.
+fn dataset_to_json<T: Id>(dataset: &Dataset<T>) -> JsonValue {
+	let mut quads: Vec<_> = dataset.iter().map(|quad| (quad.graph.as_ref().map(Reference::as_str), quad.to_string(), quad)).collect();
+	quads.sort_unstable_by(|a, b| (a.0, &a.1).cmp(&(b.0, &b.1)));
+
+	let mut graphs: Graphs<T> = Vec::new();
+	for (_, _, quad) in quads {
+		let graph = quad.graph.as_ref();
+		if graphs.last().map(|(g, _)| *g != graph).unwrap_or(true) {
+			graphs.push((graph, Vec::new()))
+		}
+
+		let nodes = &mut graphs.last_mut().unwrap().1;
+		if nodes.last().map(|(subject, _)| **subject != quad.subject).unwrap_or(true) {
+			nodes.push((&quad.subject, Vec::new()))
+		}
+
+		nodes.last_mut().unwrap().1.push(quad)
+	}
+
+	let mut default_graph = Vec::new();
+	let mut named_graphs = Vec::new();
+	for (graph, nodes) in graphs {
+		let nodes: Vec<JsonValue> = nodes.into_iter().map(|(subject, quads)| {
+			let mut node = json::object::Object::new();
+			node.insert(Keyword::Id.into_str(), subject.as_str().into());
+			let mut types = Vec::new();
+			for quad in quads {
+				match &quad.object {
+					Term::Ref(Reference::Id(ty)) if quad.predicate.as_iri().as_str() == rdf::RDF_TYPE => types.push(ty.as_json()),
+					object => {
+						let key = quad.predicate.as_iri().as_str().to_string();
+						let mut values = node.remove(&key).unwrap_or_else(JsonValue::new_array);
+						values.push(term_to_json(object)).unwrap();
+						node.insert(&key, values);
+					}
+				}
+			}
+
+			if !types.is_empty() {
+				node.insert(Keyword::Type.into_str(), JsonValue::Array(types));
+			}
+
+			JsonValue::Object(node)
+		}).collect();
+
+		match graph {
+			Some(graph) => {
+				let mut obj = json::object::Object::new();
+				obj.insert(Keyword::Id.into_str(), graph.as_str().into());
+				obj.insert(Keyword::Graph.into_str(), JsonValue::Array(nodes));
+				named_graphs.push(JsonValue::Object(obj))
+			},
+			None => default_graph.extend(nodes)
+		}
+	}
+
+	default_graph.extend(named_graphs);
+	JsonValue::Array(default_graph)
+}
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use indexmap::IndexSet;
 use std::ops::{
 	Deref,
 	DerefMut
@@ -28,7 +28,31 @@ use crate::{
 /// Result of the document expansion algorithm.
 ///
 /// It is just an alias for a set of (indexed) objects.
-pub type ExpandedDocument<T> = HashSet<Indexed<Object<T>>>;
+/// Objects are kept in the order of the input document,
+/// and so are the properties of nodes, so that the expansion output is deterministic.
+/// Equality does not depend on this order.
+///
+/// # Example
+/// ```
+/// use json_ld::{Document, JsonContext, NoLoader, util::AsJson};
+/// # use iref::IriBuf;
+/// let doc = json::parse(r#"{
+///     "@context": { "@vocab": "http://schema.org/" },
+///     "@graph": [
+///         { "@id": "http://example.org/jane", "name": "Jane Doe", "knows": { "@id": "http://example.org/john" }, "age": 42 },
+///         { "@id": "http://example.org/john", "name": "John Doe", "email": "john@example.org", "age": 41 }
+///     ]
+/// }"#).unwrap();
+///
+/// let expanded = futures::executor::block_on(doc.expand::<JsonContext<IriBuf>, _>(&mut NoLoader)).unwrap();
+/// assert_eq!(expanded.as_json().dump(), concat!(
+///     r#"[{"@id":"http://example.org/jane","http://schema.org/name":[{"@value":"Jane Doe"}],"#,
+///     r#""http://schema.org/knows":[{"@id":"http://example.org/john"}],"http://schema.org/age":[{"@value":42}]},"#,
+///     r#"{"@id":"http://example.org/john","http://schema.org/name":[{"@value":"John Doe"}],"#,
+///     r#""http://schema.org/email":[{"@value":"john@example.org"}],"http://schema.org/age":[{"@value":41}]}]"#
+/// ));
+/// ```
+pub type ExpandedDocument<T> = IndexSet<Indexed<Object<T>>>;
 
 /// JSON-LD document.
 ///
@@ -111,7 +135,7 @@ pub trait Document<T: Id> {
 		use compaction::Compact;
 		async move {
 			let json_context = context.as_json();
-			let context = context::Inversible::new(context.deref());
+			let context = context.inversible();
 			let expanded = self.expand_with(base_url, &C::Target::new(base_url), loader, options.into()).await?;
 
 			let compacted = if expanded.len() == 1 && options.compact_arrays {
@@ -158,6 +182,7 @@ pub trait Document<T: Id> {
 	{
 		self.compact_with(self.base_url(), context, loader, compaction::Options::default())
 	}
+
 }
 
 /// Default JSON document implementation.
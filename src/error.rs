@@ -70,6 +70,11 @@ pub enum ErrorCode {
 	/// A cycle in IRI mappings has been detected.
 	CyclicIriMapping,
 
+	/// The maximum nesting depth of the expanded document has been exceeded.
+	///
+	/// Note: this error is not defined in the JSON-LD API specification.
+	DepthLimitExceeded,
+
 	/// An `@id` entry was encountered whose value was not a string.
 	InvalidIdValue,
 
@@ -142,6 +147,11 @@ pub enum ErrorCode {
 	/// A number, true, or false with an associated language tag was detected.
 	InvalidLanguageTaggedValue,
 
+	/// A typed value with an invalid lexical form for its datatype was detected,
+	/// while literal validation is enabled.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	InvalidLexicalForm,
+
 	/// An invalid local context was detected.
 	InvalidLocalContext,
 
@@ -214,15 +224,44 @@ pub enum ErrorCode {
 	/// relation have been detected.
 	MultipleContextLinkHeaders,
 
+	/// The document cannot be expanded in streaming mode.
+	/// Its top-level value must either be an array, or an object with only `@context` and `@graph`
+	/// entries, `@context` coming first.
+	///
+	/// Note: this error is not defined in the JSON-LD API specification.
+	NotStreamable,
+
+	/// The maximum number of objects produced by the expansion of a document has been exceeded.
+	///
+	/// Note: this error is not defined in the JSON-LD API specification.
+	ObjectLimitExceeded,
+
 	/// An attempt was made to change the processing mode which is incompatible with the previous
 	/// specified version.
 	ProcessingModeConflict,
 
 	/// An attempt was made to redefine a protected term.
-	ProtectedTermRedefinition
+	ProtectedTermRedefinition,
+
+	/// The maximum number of remote contexts loaded during the expansion of a document has been exceeded.
+	///
+	/// Note: this error is not defined in the JSON-LD API specification.
+	RemoteContextLimitExceeded,
+
+	/// The maximum nesting depth of scoped contexts has been exceeded.
+	///
+	/// Note: this error is not defined in the JSON-LD API specification.
+	ScopedContextDepthLimitExceeded
 }
 
 impl ErrorCode {
+	/// Checks if this error code reports an exceeded resource limit (see [`Limits`](crate::Limits)).
+	pub fn is_limit(&self) -> bool {
+		use ErrorCode::*;
+
+		matches!(self, DepthLimitExceeded | ObjectLimitExceeded | RemoteContextLimitExceeded | ScopedContextDepthLimitExceeded)
+	}
+
 	/// Get the error message corresponding to the error code.
 	pub fn as_str(&self) -> &str {
 		use ErrorCode::*;
@@ -232,6 +271,7 @@ impl ErrorCode {
 			ConflictingIndexes => "conflicting indexes",
 			ContextOverflow => "context overflow",
 			CyclicIriMapping => "cyclic IRI mapping",
+			DepthLimitExceeded => "depth limit exceeded",
 			InvalidIdValue => "invalid @id value",
 			InvalidImportValue => "invalid @import value",
 			InvalidIncludedValue => "invalid @included value",
@@ -255,6 +295,7 @@ impl ErrorCode {
 			InvalidLanguageMapping => "invalid language mapping",
 			InvalidLanguageTaggedString => "invalid language-tagged string",
 			InvalidLanguageTaggedValue => "invalid language-tagged value",
+			InvalidLexicalForm => "invalid lexical form",
 			InvalidLocalContext => "invalid local context",
 			InvalidRemoteContext => "invalid remote context",
 			InvalidReverseProperty => "invalid reverse property",
@@ -276,8 +317,12 @@ impl ErrorCode {
 			LoadingDocumentFailed => "loading document failed",
 			LoadingRemoteContextFailed => "loading remote context failed",
 			MultipleContextLinkHeaders => "multiple context link headers",
+			NotStreamable => "not streamable",
+			ObjectLimitExceeded => "object limit exceeded",
 			ProcessingModeConflict => "processing mode conflict",
-			ProtectedTermRedefinition => "protected term redefinition"
+			ProtectedTermRedefinition => "protected term redefinition",
+			RemoteContextLimitExceeded => "remote context limit exceeded",
+			ScopedContextDepthLimitExceeded => "scoped context depth limit exceeded"
 		}
 	}
 }
@@ -292,6 +337,7 @@ impl<'a> TryFrom<&'a str> for ErrorCode {
 			"conflicting indexes" => Ok(ConflictingIndexes),
 			"context overflow" => Ok(ContextOverflow),
 			"cyclic IRI mapping" => Ok(CyclicIriMapping),
+			"depth limit exceeded" => Ok(DepthLimitExceeded),
 			"invalid @id value" => Ok(InvalidIdValue),
 			"invalid @import value" => Ok(InvalidImportValue),
 			"invalid @included value" => Ok(InvalidIncludedValue),
@@ -315,6 +361,7 @@ impl<'a> TryFrom<&'a str> for ErrorCode {
 			"invalid language mapping" => Ok(InvalidLanguageMapping),
 			"invalid language-tagged string" => Ok(InvalidLanguageTaggedString),
 			"invalid language-tagged value" => Ok(InvalidLanguageTaggedValue),
+			"invalid lexical form" => Ok(InvalidLexicalForm),
 			"invalid local context" => Ok(InvalidLocalContext),
 			"invalid remote context" => Ok(InvalidRemoteContext),
 			"invalid reverse property" => Ok(InvalidReverseProperty),
@@ -336,8 +383,12 @@ impl<'a> TryFrom<&'a str> for ErrorCode {
 			"loading document failed" => Ok(LoadingDocumentFailed),
 			"loading remote context failed" => Ok(LoadingRemoteContextFailed),
 			"multiple context link headers" => Ok(MultipleContextLinkHeaders),
+			"not streamable" => Ok(NotStreamable),
+			"object limit exceeded" => Ok(ObjectLimitExceeded),
 			"processing mode conflict" => Ok(ProcessingModeConflict),
 			"protected term redefinition" => Ok(ProtectedTermRedefinition),
+			"remote context limit exceeded" => Ok(RemoteContextLimitExceeded),
+			"scoped context depth limit exceeded" => Ok(ScopedContextDepthLimitExceeded),
 			_ => Err(())
 		}
 	}
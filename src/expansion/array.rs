@@ -13,11 +13,12 @@ use crate::{
 };
 use super::{
 	Options,
+	Tracker,
 	Expanded,
 	expand_element
 };
 
-pub async fn expand_array<T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(active_context: &C, active_property: Option<&str>, active_property_definition: Option<&TermDefinition<T, C>>, element: &[JsonValue], base_url: Option<Iri<'_>>, loader: &mut L, options: Options, from_map: bool) -> Result<Expanded<T>, Error> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
+pub async fn expand_array<T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(active_context: &C, active_property: Option<&str>, active_property_definition: Option<&TermDefinition<T, C>>, element: &[JsonValue], base_url: Option<Iri<'_>>, loader: &mut L, tracker: &mut Tracker, options: Options, from_map: bool) -> Result<Expanded<T>, Error> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
 	// Initialize an empty array, result.
 	let mut is_list = false;
 	let mut result = Vec::new();
@@ -34,7 +35,7 @@ pub async fn expand_array<T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L
 		// Initialize `expanded_item` to the result of using this algorithm
 		// recursively, passing `active_context`, `active_property`, `item` as element,
 		// `base_url`, the `frame_expansion`, `ordered`, and `from_map` flags.
-		result.extend(expand_element(active_context, active_property, item, base_url, loader, options, from_map).await?);
+		result.extend(expand_element(active_context, active_property, item, base_url, loader, tracker, options, from_map).await?);
 	}
 
 	if is_list {
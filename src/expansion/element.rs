@@ -1,5 +1,9 @@
 use mown::Mown;
-use futures::future::{BoxFuture, FutureExt};
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use futures::future::{BoxFuture, FutureExt, Either};
 use iref::Iri;
 use json::JsonValue;
 use crate::{
@@ -23,24 +27,81 @@ use crate::{
 use crate::util::as_array;
 use super::{
 	Expanded,
+	Tracker,
 	Entry,
 	Options,
 	expand_literal,
 	expand_array,
 	expand_value,
 	expand_node,
-	expand_iri
+	expand_iri,
+	validate_value
 };
 
+/// Future returned by [`expand_element`].
+///
+/// Null values and literals without property-scoped context are expanded when first polled,
+/// without allocating a boxed future.
+pub type ExpandElement<'a, T, C> = Either<ExpandScalar<'a, T, C>, BoxFuture<'a, Result<Expanded<T>, Error>>>;
+
+/// Expansion of a null value or a literal without property-scoped context.
+pub struct ExpandScalar<'a, T, C> {
+	active_context: &'a C,
+	active_property: Option<&'a str>,
+	element: &'a JsonValue,
+	tracker: &'a mut Tracker,
+	options: Options,
+	t: PhantomData<fn() -> T>
+}
+
+impl<'a, T: Id, C: ContextMut<T>> Future for ExpandScalar<'a, T, C> {
+	type Output = Result<Expanded<T>, Error>;
+
+	fn poll(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<Self::Output> {
+		let this = self.get_mut();
+		if this.element.is_null() {
+			Poll::Ready(Ok(Expanded::Null))
+		} else {
+			Poll::Ready(expand_scalar(this.active_context, this.active_property, this.element, this.tracker, &this.options))
+		}
+	}
+}
+
 /// https://www.w3.org/TR/json-ld11-api/#expansion-algorithm
 /// The default specified value for `ordered` and `from_map` is `false`.
-pub fn expand_element<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(active_context: &'a C, active_property: Option<&'a str>, element: &'a JsonValue, base_url: Option<Iri<'a>>, loader: &'a mut L, options: Options, from_map: bool) -> BoxFuture<'a, Result<Expanded<T>, Error>> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
-	async move {
+pub fn expand_element<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(active_context: &'a C, active_property: Option<&'a str>, element: &'a JsonValue, base_url: Option<Iri<'a>>, loader: &'a mut L, tracker: &'a mut Tracker, options: Options, from_map: bool) -> ExpandElement<'a, T, C> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
+	let scalar = match element {
+		JsonValue::Null => true,
+		JsonValue::Array(_) | JsonValue::Object(_) => false,
+		_ => !active_context.get_opt(active_property).map(|definition| definition.context.is_some()).unwrap_or(false)
+	};
+
+	if scalar {
+		return Either::Left(ExpandScalar {
+			active_context,
+			active_property,
+			element,
+			tracker,
+			options,
+			t: PhantomData
+		})
+	}
+
+	Either::Right(async move {
 		// If `element` is null, return null.
 		if element.is_null() {
 			return Ok(Expanded::Null)
 		}
 
+		// JSON value the expanded object comes from.
+		let source = element;
+
+		// Arrays and objects are one level deeper in the document.
+		let mut options = options;
+		if element.is_array() || element.is_object() {
+			options.limits = options.limits.nested()?;
+		}
+
 		let active_property_definition = active_context.get_opt(active_property);
 
 		// // If `active_property` is `@default`, initialize the `frame_expansion` flag to `false`.
@@ -64,7 +125,12 @@ pub fn expand_element<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L
 		match element {
 			JsonValue::Null => unreachable!(),
 			JsonValue::Array(element) => {
-				expand_array(active_context, active_property, active_property_definition, element, base_url, loader, options, from_map).await
+				let mut result = expand_array(active_context, active_property, active_property_definition, element, base_url, loader, tracker, options, from_map).await?;
+				if let Expanded::Object(list) = &mut result {
+					tracker.add(source, list, &options.limits)?
+				}
+
+				Ok(result)
 			},
 
 			JsonValue::Object(element) => {
@@ -249,10 +315,12 @@ pub fn expand_element<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L
 					// result is an array..
 					let mut result = Vec::new();
 					for item in as_array(list_entry) {
-						result.extend(expand_element(active_context.as_ref(), active_property, item, base_url, loader, options, false).await?)
+						result.extend(expand_element(active_context.as_ref(), active_property, item, base_url, loader, tracker, options, false).await?)
 					}
 
-					Ok(Expanded::Object(Indexed::new(Object::List(result), index)))
+					let mut list = Indexed::new(Object::List(result), index);
+					tracker.add(source, &mut list, &options.limits)?;
+					Ok(Expanded::Object(list))
 				} else if let Some(set_entry) = set_entry {
 					// Set objects.
 					// let mut index = None;
@@ -277,18 +345,25 @@ pub fn expand_element<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L
 					// set expanded value to the result of using this algorithm recursively,
 					// passing active context, active property, value for element, base URL, and
 					// the frameExpansion and ordered flags.
-					expand_element(active_context.as_ref(), active_property, set_entry, base_url, loader, options, false).await
+					expand_element(active_context.as_ref(), active_property, set_entry, base_url, loader, tracker, options, false).await
 				} else if let Some(value_entry) = value_entry {
 					// Value objects.
-					if let Some(value) = expand_value(input_type, type_scoped_context, expanded_entries, value_entry)? {
-						Ok(Expanded::Object(value.into()))
+					if let Some(mut value) = expand_value(input_type, type_scoped_context, expanded_entries, value_entry)? {
+						if let Object::Value(value) = value.as_mut() {
+							validate_value(value, &options)?
+						}
+
+						tracker.add(source, &mut value, &options.limits)?;
+						Ok(Expanded::Object(value))
 					} else {
 						Ok(Expanded::Null)
 					}
 				} else {
 					// Node objects.
-					if let Some(result) = expand_node(active_context.as_ref(), type_scoped_context, active_property, expanded_entries, base_url, loader, options).await? {
-						Ok(result.cast::<Object<T>>().into())
+					if let Some(result) = expand_node(active_context.as_ref(), type_scoped_context, active_property, expanded_entries, base_url, loader, tracker, options).await? {
+						let mut result = result.cast::<Object<T>>();
+						tracker.add(source, &mut result, &options.limits)?;
+						Ok(result.into())
 					} else {
 						Ok(Expanded::Null)
 					}
@@ -327,10 +402,27 @@ pub fn expand_element<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L
 					Mown::Borrowed(active_context)
 				};
 
-				// Return the result of the Value Expansion algorithm, passing the `active_context`,
-				// `active_property`, and `element` as value.
-				return Ok(Expanded::Object(expand_literal(active_context.as_ref(), active_property, element)?))
+				expand_scalar(active_context.as_ref(), active_property, element, tracker, &options)
 			}
 		}
-	}.boxed()
+	}.boxed())
+}
+
+/// Expands a scalar element, once the property-scoped context, if any, has been applied.
+fn expand_scalar<T: Id, C: ContextMut<T>>(active_context: &C, active_property: Option<&str>, element: &JsonValue, tracker: &mut Tracker, options: &Options) -> Result<Expanded<T>, Error> {
+	// If `active_property` is `null` or `@graph`, drop the free-floating scalar by
+	// returning null.
+	if active_property.is_none() || active_property == Some("@graph") {
+		return Ok(Expanded::Null)
+	}
+
+	// Return the result of the Value Expansion algorithm, passing the `active_context`,
+	// `active_property`, and `element` as value.
+	let mut result = expand_literal(active_context, active_property, element)?;
+	if let Object::Value(value) = result.as_mut() {
+		validate_value(value, options)?
+	}
+
+	tracker.add(element, &mut result, &options.limits)?;
+	Ok(Expanded::Object(result))
 }
@@ -7,9 +7,12 @@ mod value;
 mod node;
 mod array;
 mod element;
+mod validation;
+mod stream;
+mod tracker;
 
 use std::cmp::{Ord, Ordering};
-use std::collections::HashSet;
+use indexmap::IndexSet;
 use futures::Future;
 use iref::{Iri, IriBuf};
 use json::JsonValue;
@@ -20,6 +23,9 @@ use crate::{
 	Indexed,
 	Object,
 	ContextMut,
+	Limits,
+	CountingLoader,
+	location::SourceMap,
 	context::{
 		ProcessingOptions,
 		Loader
@@ -33,6 +39,9 @@ pub use value::*;
 pub use node::*;
 pub use array::*;
 pub use element::*;
+pub use validation::*;
+pub use stream::*;
+pub use tracker::*;
 
 #[derive(Clone, Copy, Default)]
 pub struct Options {
@@ -44,13 +53,26 @@ pub struct Options {
 
 	/// If set to true, input document entries are processed lexicographically.
 	/// If false, order is not considered in processing.
-	pub ordered: bool
+	pub ordered: bool,
+
+	/// Validation of the lexical form of values typed with a well-known XSD datatype.
+	pub literal_validation: LiteralValidation,
+
+	/// If set to true, the lexical form of values typed with a well-known XSD datatype
+	/// is replaced with its canonical form.
+	///
+	/// Values with an invalid lexical form are left untouched.
+	pub canonicalize_literals: bool,
+
+	/// Resource limits.
+	pub limits: Limits
 }
 
 impl From<Options> for ProcessingOptions {
 	fn from(options: Options) -> ProcessingOptions {
 		let mut copt = ProcessingOptions::default();
 		copt.processing_mode = options.processing_mode;
+		copt.limits = options.limits;
 		copt
 	}
 }
@@ -88,17 +110,37 @@ fn filter_top_level_item<T: Id>(item: &Indexed<Object<T>>) -> bool {
 	}
 }
 
-pub fn expand<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(active_context: &'a C, element: &'a JsonValue, base_url: Option<Iri>, loader: &'a mut L, options: Options) -> impl 'a + Send + Future<Output=Result<HashSet<Indexed<Object<T>>>, Error>> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
+pub fn expand<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(active_context: &'a C, element: &'a JsonValue, base_url: Option<Iri>, loader: &'a mut L, options: Options) -> impl 'a + Send + Future<Output=Result<IndexSet<Indexed<Object<T>>>, Error>> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
+	expand_tracked(active_context, element, base_url, loader, options, Tracker::new())
+}
+
+/// Expands a document parsed with [`location::parse`](crate::location::parse),
+/// attaching to each expanded object the location of the JSON value it comes from
+/// (see [`Indexed::location`]).
+///
+/// Objects that do not directly come from a JSON value of the document,
+/// such as the values of a language map, have no location.
+pub fn expand_located<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(active_context: &'a C, element: &'a JsonValue, source_map: &SourceMap, base_url: Option<Iri>, loader: &'a mut L, options: Options) -> impl 'a + Send + Future<Output=Result<IndexSet<Indexed<Object<T>>>, Error>> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
+	expand_tracked(active_context, element, base_url, loader, options, Tracker::with_source_map(element, source_map))
+}
+
+fn expand_tracked<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(active_context: &'a C, element: &'a JsonValue, base_url: Option<Iri>, loader: &'a mut L, options: Options, mut tracker: Tracker) -> impl 'a + Send + Future<Output=Result<IndexSet<Indexed<Object<T>>>, Error>> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
 	let base_url = base_url.map(|url| IriBuf::from(url));
 
 	async move {
 		let base_url = base_url.as_ref().map(|url| url.as_iri());
-		let expanded = expand_element(active_context, None, element, base_url, loader, options, false).await?;
+		let expanded = match options.limits.max_remote_contexts {
+			Some(max) => {
+				let mut loader = CountingLoader::new(loader, max);
+				expand_element(active_context, None, element, base_url, &mut loader, &mut tracker, options, false).await?
+			},
+			None => expand_element(active_context, None, element, base_url, loader, &mut tracker, options, false).await?
+		};
 		if expanded.len() == 1 {
 			match expanded.into_iter().next().unwrap().into_unnamed_graph() {
 				Ok(graph) => Ok(graph),
 				Err(obj) => {
-					let mut set = HashSet::new();
+					let mut set = IndexSet::new();
 					if filter_top_level_item(&obj) {
 						set.insert(obj);
 					}
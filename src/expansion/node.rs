@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use indexmap::IndexSet;
 use futures::future::{BoxFuture, FutureExt};
 use mown::Mown;
 use iref::Iri;
@@ -28,7 +28,7 @@ use crate::{
 	}
 };
 use crate::util::as_array;
-use super::{Expanded, Entry, Options, expand_element, expand_literal, expand_iri, filter_top_level_item};
+use super::{Expanded, Entry, Options, Tracker, expand_element, expand_literal, expand_iri, filter_top_level_item};
 
 /// Convert a lenient term to a node id, if possible.
 /// Return `None` if the term is `null`.
@@ -41,12 +41,12 @@ pub fn node_id_of_term<T: Id>(term: Lenient<Term<T>>) -> Option<Lenient<Referenc
 	}
 }
 
-pub async fn expand_node<T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(active_context: &C, type_scoped_context: &C, active_property: Option<&str>, expanded_entries: Vec<Entry<'_, (&str, Term<T>)>>, base_url: Option<Iri<'_>>, loader: &mut L, options: Options) -> Result<Option<Indexed<Node<T>>>, Error> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
+pub async fn expand_node<T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(active_context: &C, type_scoped_context: &C, active_property: Option<&str>, expanded_entries: Vec<Entry<'_, (&str, Term<T>)>>, base_url: Option<Iri<'_>>, loader: &mut L, tracker: &mut Tracker, options: Options) -> Result<Option<Indexed<Node<T>>>, Error> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
 	// Initialize two empty maps, `result` and `nests`.
 	let mut result = Indexed::new(Node::new(), None);
 	let mut has_value_object_entries = false;
 
-	expand_node_entries(&mut result, &mut has_value_object_entries, active_context, type_scoped_context, active_property, expanded_entries, base_url, loader, options).await?;
+	expand_node_entries(&mut result, &mut has_value_object_entries, active_context, type_scoped_context, active_property, expanded_entries, base_url, loader, tracker, options).await?;
 
 	// If result contains the entry @value:
 	// The result must not contain any entries other than @direction, @index,
@@ -80,7 +80,7 @@ pub async fn expand_node<T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L:
 	Ok(Some(result))
 }
 
-fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(result: &'a mut Indexed<Node<T>>, has_value_object_entries: &'a mut bool, active_context: &'a C, type_scoped_context: &'a C, active_property: Option<&'a str>, expanded_entries: Vec<Entry<'a, (&'a str, Term<T>)>>, base_url: Option<Iri<'a>>, loader: &'a mut L, options: Options) -> BoxFuture<'a, Result<(), Error>> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
+fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(result: &'a mut Indexed<Node<T>>, has_value_object_entries: &'a mut bool, active_context: &'a C, type_scoped_context: &'a C, active_property: Option<&'a str>, expanded_entries: Vec<Entry<'a, (&'a str, Term<T>)>>, base_url: Option<Iri<'a>>, loader: &'a mut L, tracker: &'a mut Tracker, options: Options) -> BoxFuture<'a, Result<(), Error>> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
 	async move {
 		// For each `key` and `value` in `element`, ordered lexicographically by key
 		// if `ordered` is `true`:
@@ -154,7 +154,7 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 							// property, `value` for element, `base_url`, and the
 							// `frame_expansion` and `ordered` flags, ensuring that
 							// `expanded_value` is an array of one or more maps.
-							let expanded_value = expand_element(active_context, Some("@graph"), value, base_url, loader, options, false).await?;
+							let expanded_value = expand_element(active_context, Some("@graph"), value, base_url, loader, tracker, options, false).await?;
 							result.graph = Some(expanded_value.into_iter().filter(filter_top_level_item).collect());
 						},
 						// If expanded property is @included:
@@ -169,7 +169,7 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 							// recursively passing `active_context`, `active_property`,
 							// `value` for element, `base_url`, and the `frame_expansion`
 							// and `ordered` flags, ensuring that the result is an array.
-							let expanded_value = expand_element(active_context, Some("@included"), value, base_url, loader, options, false).await?;
+							let expanded_value = expand_element(active_context, Some("@included"), value, base_url, loader, tracker, options, false).await?;
 							let mut expanded_nodes = Vec::new();
 							for obj in expanded_value.into_iter() {
 								match obj.try_cast::<Node<T>>() {
@@ -224,7 +224,7 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 											return Err(ErrorCode::InvalidReversePropertyMap.into())
 										},
 										Lenient::Ok(Term::Ref(reverse_prop)) => {
-											let reverse_expanded_value = expand_element(active_context, Some(reverse_key), reverse_value, base_url, loader, options, false).await?;
+											let reverse_expanded_value = expand_element(active_context, Some(reverse_key), reverse_value, base_url, loader, tracker, options, false).await?;
 
 											let is_double_reversed = if let Some(reverse_key_definition) = active_context.get(reverse_key) {
 												reverse_key_definition.reverse_property
@@ -257,6 +257,10 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 						},
 						// If expanded property is @nest
 						Keyword::Nest => {
+							// Nested entries count as one nesting level.
+							let mut nested_options = options;
+							nested_options.limits = options.limits.nested()?;
+
 							for nested in as_array(value) {
 								if let JsonValue::Object(nested) = nested {
 									let mut nested_entries = Vec::new();
@@ -276,7 +280,7 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 										}
 									});
 
-									expand_node_entries(result, has_value_object_entries, active_context, type_scoped_context, active_property, nested_expanded_entries.collect(), base_url, loader, options).await?
+									expand_node_entries(result, has_value_object_entries, active_context, type_scoped_context, active_property, nested_expanded_entries.collect(), base_url, loader, tracker, nested_options).await?
 								} else {
 									return Err(ErrorCode::InvalidNestValue.into())
 								}
@@ -473,7 +477,7 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 							// index value as element, base URL, and the
 							// frameExpansion and ordered flags.
 							// And `true` for `from_map`.
-							let index_value = expand_element(map_context.as_ref(), Some(key), index_value, base_url, loader, options, true).await?;
+							let index_value = expand_element(map_context.as_ref(), Some(key), index_value, base_url, loader, tracker, options, true).await?;
 							// For each item in index value:
 							for mut item in index_value {
 								// If container mapping includes @graph,
@@ -483,7 +487,7 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 								// represented using an array.
 								if container_mapping.contains(ContainerType::Graph) && !item.is_graph() {
 									let mut node = Node::new();
-									let mut graph = HashSet::new();
+									let mut graph = IndexSet::new();
 									graph.insert(item);
 									node.graph = Some(graph);
 									item = Object::Node(node).into();
@@ -569,7 +573,7 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 						// Otherwise, initialize expanded value to the result of using this
 						// algorithm recursively, passing active context, key for active property,
 						// value for element, base URL, and the frameExpansion and ordered flags.
-						expand_element(active_context, Some(key), value, base_url, loader, options, false).await?
+						expand_element(active_context, Some(key), value, base_url, loader, tracker, options, false).await?
 					};
 
 					// If container mapping includes @list and expanded value is
@@ -589,7 +593,7 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 					if container_mapping.contains(ContainerType::Graph) && !container_mapping.contains(ContainerType::Id) && !container_mapping.contains(ContainerType::Index) {
 						expanded_value = Expanded::Array(expanded_value.into_iter().map(|ev| {
 							let mut node = Node::new();
-							let mut graph = HashSet::new();
+							let mut graph = IndexSet::new();
 							graph.insert(ev);
 							node.graph = Some(graph);
 							Object::Node(node).into()
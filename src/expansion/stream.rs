@@ -0,0 +1,468 @@
+use std::collections::VecDeque;
+use std::io::{self, BufRead, BufReader, Read};
+use futures::executor::block_on;
+use iref::{Iri, IriBuf};
+use json::JsonValue;
+use crate::{
+	Error,
+	ErrorCode,
+	Id,
+	Indexed,
+	Object,
+	Lenient,
+	CountingLoader,
+	SyncLoader,
+	ContextMut,
+	context::Local,
+	syntax::{
+		Keyword,
+		Term
+	}
+};
+use super::{
+	Options,
+	Tracker,
+	expand_element,
+	expand_iri,
+	filter_top_level_item
+};
+
+/// Streaming expansion of a document read from an [`io::Read`].
+///
+/// Expands very large documents without loading them in memory.
+/// The top-level value of the document must either be an array of nodes,
+/// or an object with only a `@context` entry followed by a `@graph` entry,
+/// such as
+/// ```json
+/// {
+///   "@context": ...,
+///   "@graph": [ ... ]
+/// }
+/// ```
+/// otherwise a [`NotStreamable`](ErrorCode::NotStreamable) error is returned.
+/// The top-level context is processed once, then each top-level node is read, expanded and
+/// yielded in turn: only one top-level node is held in memory at a time.
+///
+/// Contrarily to [`Document::expand`](crate::Document::expand), identical top-level nodes are not
+/// merged. Expansion stops at the first error.
+///
+/// # Example
+/// ```
+/// use json_ld::{JsonContext, NoLoader, expansion::{self, ExpandReader}};
+/// # use iref::IriBuf;
+/// let input = r#"{
+///     "@context": { "name": "http://schema.org/name" },
+///     "@graph": [
+///         { "name": "Jane Doe" },
+///         { "name": "John Smith" }
+///     ]
+/// }"#;
+///
+/// let mut loader = NoLoader;
+/// let nodes = ExpandReader::<_, IriBuf, JsonContext<IriBuf>, _>::new(input.as_bytes(), None, JsonContext::new(None), &mut loader, expansion::Options::default());
+/// assert_eq!(nodes.collect::<Result<Vec<_>, _>>().unwrap().len(), 2);
+/// ```
+///
+/// Strings are read as a whole, including escaped characters and brackets:
+/// ```
+/// use json_ld::{JsonContext, NoLoader, Object, Reference, expansion::{self, ExpandReader}};
+/// # use iref::IriBuf;
+/// let input = r#"[
+///     { "http://schema.org/name": "a \"quoted\" ] } [ { string" },
+///     { "http://schema.org/name": "back\\slash\\" }
+/// ]"#;
+///
+/// let mut loader = NoLoader;
+/// let nodes = ExpandReader::<_, IriBuf, JsonContext<IriBuf>, _>::new(input.as_bytes(), None, JsonContext::new(None), &mut loader, expansion::Options::default());
+/// let names: Vec<String> = nodes.map(|node| match node.unwrap().into_inner() {
+///     Object::Node(node) => node.get(&Reference::Id(IriBuf::new("http://schema.org/name").unwrap())).next().unwrap().as_str().unwrap().to_string(),
+///     _ => unreachable!()
+/// }).collect();
+///
+/// assert_eq!(names, vec!["a \"quoted\" ] } [ { string", "back\\slash\\"]);
+/// ```
+///
+/// # Errors
+///
+/// Documents that do not have the expected structure are rejected, and data
+/// after the end of the top-level value is reported once the preceding nodes are expanded:
+/// ```
+/// use json_ld::{JsonContext, NoLoader, ErrorCode, expansion::{self, ExpandReader}};
+/// # use iref::IriBuf;
+/// let expand = |input: &str| -> Vec<Result<(), ErrorCode>> {
+///     let mut loader = NoLoader;
+///     ExpandReader::<_, IriBuf, JsonContext<IriBuf>, _>::new(input.as_bytes(), None, JsonContext::new(None), &mut loader, expansion::Options::default())
+///         .map(|node| node.map(|_| ()).map_err(|e| e.code()))
+///         .collect()
+/// };
+///
+/// // The top-level value is not an array or object.
+/// assert_eq!(expand(r#""node""#), vec![Err(ErrorCode::NotStreamable)]);
+///
+/// // The top-level object has other entries than `@context` and `@graph`.
+/// assert_eq!(expand(r#"{ "http://schema.org/name": "Jane Doe" }"#), vec![Err(ErrorCode::NotStreamable)]);
+///
+/// // The context comes after the graph.
+/// assert_eq!(expand(r#"{ "@graph": [], "@context": {} }"#), vec![Err(ErrorCode::NotStreamable)]);
+///
+/// // Trailing data.
+/// assert_eq!(expand(r#"[ { "http://schema.org/name": "Jane Doe" } ] {"#), vec![Ok(()), Err(ErrorCode::LoadingDocumentFailed)]);
+/// ```
+pub struct ExpandReader<'l, R, T: Id, C, L> {
+	/// Input.
+	scanner: Scanner<R>,
+
+	/// Parser state.
+	state: State,
+
+	/// Active context.
+	context: C,
+
+	/// Document base URL.
+	base_url: Option<IriBuf>,
+
+	/// Document loader.
+	loader: &'l mut L,
+
+	/// Expansion options.
+	options: Options,
+
+	/// Expansion state.
+	tracker: Tracker,
+
+	/// Number of remote contexts that can still be loaded, if limited.
+	remote_contexts: Option<usize>,
+
+	/// Expanded nodes not yet returned.
+	pending: VecDeque<Indexed<Object<T>>>
+}
+
+/// Position of the parser in the top-level value.
+#[derive(Clone, Copy)]
+enum State {
+	/// Before the top-level value.
+	Start,
+
+	/// In the top-level array.
+	Array { first: bool },
+
+	/// In the top-level object.
+	Object { first: bool, graph: bool },
+
+	/// In the top-level `@graph` array.
+	Graph { first: bool },
+
+	/// After the top-level value.
+	End,
+
+	/// The whole document has been read, or an error occured.
+	Done
+}
+
+impl<'l, R: Read, T: Id, C: ContextMut<T>, L> ExpandReader<'l, R, T, C, L> {
+	/// Creates a new streaming expansion of the document read from `reader`,
+	/// using the given initial active context.
+	pub fn new(reader: R, base_url: Option<Iri>, context: C, loader: &'l mut L, options: Options) -> ExpandReader<'l, R, T, C, L> {
+		ExpandReader {
+			scanner: Scanner::new(reader),
+			state: State::Start,
+			context,
+			base_url: base_url.map(IriBuf::from),
+			loader,
+			options,
+			tracker: Tracker::new(),
+			remote_contexts: options.limits.max_remote_contexts,
+			pending: VecDeque::new()
+		}
+	}
+
+	/// Returns the current active context.
+	pub fn context(&self) -> &C {
+		&self.context
+	}
+}
+
+impl<'l, R: Read, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + SyncLoader<Document = JsonValue>> ExpandReader<'l, R, T, C, L> where C::LocalContext: Send + Sync + From<JsonValue> {
+	/// Reads the document until the next top-level value is expanded.
+	///
+	/// Returns `false` once the whole document has been read.
+	fn step(&mut self) -> Result<bool, Error> {
+		self.scanner.skip_whitespace()?;
+		match self.state {
+			State::Start => {
+				match self.scanner.next()? {
+					Some(b'[') => self.state = State::Array { first: true },
+					Some(b'{') => self.state = State::Object { first: true, graph: false },
+					Some(_) => return Err(ErrorCode::NotStreamable.into()),
+					None => return Err(syntax_error("empty document"))
+				}
+			},
+			State::Array { first } => {
+				if self.scanner.peek()? == Some(b']') {
+					self.scanner.bump();
+					self.state = State::End
+				} else {
+					self.separator(first)?;
+					let value = self.scanner.read_value()?;
+					self.expand(&value, None)?;
+					self.state = State::Array { first: false }
+				}
+			},
+			State::Object { first, graph } => {
+				if self.scanner.peek()? == Some(b'}') {
+					self.scanner.bump();
+					self.state = State::End
+				} else {
+					self.separator(first)?;
+					let key = self.scanner.read_value()?;
+					let key = key.as_str().ok_or_else(|| syntax_error("expected a key"))?;
+					self.scanner.skip_whitespace()?;
+					self.scanner.expect(b':')?;
+					self.scanner.skip_whitespace()?;
+
+					if key == "@context" {
+						// The context must apply to every node of the graph.
+						if graph {
+							return Err(ErrorCode::NotStreamable.into())
+						}
+
+						let local_context = self.scanner.read_value()?;
+						self.process_context(&local_context)?;
+						self.state = State::Object { first: false, graph }
+					} else {
+						match expand_iri(&self.context, key, false, true) {
+							Lenient::Ok(Term::Keyword(Keyword::Graph)) => {
+								if self.scanner.peek()? == Some(b'[') {
+									self.scanner.bump();
+									self.state = State::Graph { first: true }
+								} else {
+									let value = self.scanner.read_value()?;
+									self.expand(&value, Some("@graph"))?;
+									self.state = State::Object { first: false, graph: true }
+								}
+							},
+							_ => return Err(ErrorCode::NotStreamable.into())
+						}
+					}
+				}
+			},
+			State::Graph { first } => {
+				if self.scanner.peek()? == Some(b']') {
+					self.scanner.bump();
+					self.state = State::Object { first: false, graph: true }
+				} else {
+					self.separator(first)?;
+					let value = self.scanner.read_value()?;
+					self.expand(&value, Some("@graph"))?;
+					self.state = State::Graph { first: false }
+				}
+			},
+			State::End => {
+				if self.scanner.peek()?.is_some() {
+					return Err(syntax_error("unexpected data after the end of the document"))
+				}
+
+				self.state = State::Done
+			},
+			State::Done => return Ok(false)
+		}
+
+		Ok(true)
+	}
+
+	/// Reads the separator preceding the next entry or item, if it is not the first.
+	fn separator(&mut self, first: bool) -> Result<(), Error> {
+		if !first {
+			self.scanner.expect(b',')?;
+			self.scanner.skip_whitespace()?;
+		}
+
+		Ok(())
+	}
+
+	/// Processes the top-level context.
+	fn process_context(&mut self, local_context: &JsonValue) -> Result<(), Error> {
+		let base_url = self.base_url.as_ref().map(|url| url.as_iri());
+		let context = &self.context;
+		let options = self.options.into();
+		self.context = match &mut self.remote_contexts {
+			Some(remaining) => {
+				let mut loader = CountingLoader::new(&mut *self.loader, *remaining);
+				let result = block_on(local_context.process_with(context, &mut loader, base_url, options));
+				*remaining = loader.remaining();
+				result?.into_inner()
+			},
+			None => block_on(local_context.process_with(context, &mut *self.loader, base_url, options))?.into_inner()
+		};
+
+		Ok(())
+	}
+
+	/// Expands a top-level value, queuing the resulting nodes.
+	fn expand(&mut self, value: &JsonValue, active_property: Option<&str>) -> Result<(), Error> {
+		let base_url = self.base_url.as_ref().map(|url| url.as_iri());
+		let context = &self.context;
+		let tracker = &mut self.tracker;
+		let options = self.options;
+		let expanded = match &mut self.remote_contexts {
+			Some(remaining) => {
+				let mut loader = CountingLoader::new(&mut *self.loader, *remaining);
+				let result = block_on(expand_element(context, active_property, value, base_url, &mut loader, tracker, options, false));
+				*remaining = loader.remaining();
+				result?
+			},
+			None => block_on(expand_element(context, active_property, value, base_url, &mut *self.loader, tracker, options, false))?
+		};
+
+		// Remove dangling values.
+		for item in expanded {
+			if filter_top_level_item(&item) {
+				self.pending.push_back(item)
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl<'l, R: Read, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + SyncLoader<Document = JsonValue>> Iterator for ExpandReader<'l, R, T, C, L> where C::LocalContext: Send + Sync + From<JsonValue> {
+	type Item = Result<Indexed<Object<T>>, Error>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			if let Some(node) = self.pending.pop_front() {
+				return Some(Ok(node))
+			}
+
+			match self.step() {
+				Ok(true) => (),
+				Ok(false) => return None,
+				Err(e) => {
+					self.state = State::Done;
+					return Some(Err(e))
+				}
+			}
+		}
+	}
+}
+
+fn syntax_error(message: &str) -> Error {
+	Error::new(ErrorCode::LoadingDocumentFailed, io::Error::new(io::ErrorKind::InvalidData, message.to_string()))
+}
+
+/// JSON scanner, reading one top-level value at a time.
+struct Scanner<R> {
+	reader: BufReader<R>,
+
+	/// Buffer of the value being read.
+	buffer: Vec<u8>
+}
+
+impl<R: Read> Scanner<R> {
+	fn new(reader: R) -> Scanner<R> {
+		Scanner {
+			reader: BufReader::new(reader),
+			buffer: Vec::new()
+		}
+	}
+
+	fn peek(&mut self) -> Result<Option<u8>, Error> {
+		let buffer = self.reader.fill_buf().map_err(|e| Error::new(ErrorCode::LoadingDocumentFailed, e))?;
+		Ok(buffer.first().cloned())
+	}
+
+	fn bump(&mut self) {
+		self.reader.consume(1)
+	}
+
+	fn next(&mut self) -> Result<Option<u8>, Error> {
+		let c = self.peek()?;
+		if c.is_some() {
+			self.bump()
+		}
+
+		Ok(c)
+	}
+
+	fn expect(&mut self, expected: u8) -> Result<(), Error> {
+		match self.next()? {
+			Some(c) if c == expected => Ok(()),
+			_ => Err(syntax_error(&format!("expected `{}`", expected as char)))
+		}
+	}
+
+	fn skip_whitespace(&mut self) -> Result<(), Error> {
+		while let Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r') = self.peek()? {
+			self.bump()
+		}
+
+		Ok(())
+	}
+
+	/// Reads and parses the next JSON value.
+	fn read_value(&mut self) -> Result<JsonValue, Error> {
+		self.buffer.clear();
+		match self.peek()? {
+			Some(b'{') | Some(b'[') => {
+				let mut depth = 0usize;
+				let mut in_string = false;
+				loop {
+					let c = self.next()?.ok_or_else(|| syntax_error("unexpected end of document"))?;
+					self.buffer.push(c);
+					if in_string {
+						match c {
+							b'\\' => {
+								let escaped = self.next()?.ok_or_else(|| syntax_error("unexpected end of document"))?;
+								self.buffer.push(escaped)
+							},
+							b'"' => in_string = false,
+							_ => ()
+						}
+					} else {
+						match c {
+							b'"' => in_string = true,
+							b'{' | b'[' => depth += 1,
+							b'}' | b']' => {
+								depth -= 1;
+								if depth == 0 {
+									break
+								}
+							},
+							_ => ()
+						}
+					}
+				}
+			},
+			Some(b'"') => {
+				self.bump();
+				self.buffer.push(b'"');
+				loop {
+					let c = self.next()?.ok_or_else(|| syntax_error("unexpected end of document"))?;
+					self.buffer.push(c);
+					match c {
+						b'\\' => {
+							let escaped = self.next()?.ok_or_else(|| syntax_error("unexpected end of document"))?;
+							self.buffer.push(escaped)
+						},
+						b'"' => break,
+						_ => ()
+					}
+				}
+			},
+			_ => {
+				while let Some(c) = self.peek()? {
+					match c {
+						b',' | b']' | b'}' | b' ' | b'\t' | b'\n' | b'\r' => break,
+						_ => {
+							self.buffer.push(c);
+							self.bump()
+						}
+					}
+				}
+			}
+		}
+
+		let text = std::str::from_utf8(&self.buffer).map_err(|e| Error::new(ErrorCode::LoadingDocumentFailed, e))?;
+		json::parse(text).map_err(|e| Error::new(ErrorCode::LoadingDocumentFailed, e))
+	}
+}
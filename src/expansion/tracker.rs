@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+use json::JsonValue;
+use crate::{
+	Error,
+	Limits,
+	Indexed,
+	ObjectCount,
+	location::{
+		Location,
+		SourceMap
+	}
+};
+
+/// State of the expansion of a whole document.
+///
+/// Counts the expanded objects, checked against [`Limits::max_objects`],
+/// and attaches to each of them the location of the JSON value it comes from,
+/// when the document has been parsed with [`location::parse`](crate::location::parse).
+#[derive(Default)]
+pub struct Tracker {
+	/// Number of objects expanded so far.
+	objects: ObjectCount,
+
+	/// Locations of the input JSON values, indexed by address.
+	locations: HashMap<usize, Location>
+}
+
+impl Tracker {
+	/// Creates a new tracker, without location tracking.
+	pub fn new() -> Tracker {
+		Tracker::default()
+	}
+
+	/// Creates a new tracker attaching locations given by the source map of the expanded document.
+	///
+	/// The document must not be moved or modified until its expansion is done.
+	pub(crate) fn with_source_map(document: &JsonValue, source_map: &SourceMap) -> Tracker {
+		Tracker {
+			objects: ObjectCount::new(),
+			locations: source_map.locations(document)
+		}
+	}
+
+	/// Returns the number of objects expanded so far.
+	pub fn objects(&self) -> &ObjectCount {
+		&self.objects
+	}
+
+	/// Counts a new object expanded from the given JSON value, and attaches its location.
+	pub(crate) fn add<T>(&mut self, element: &JsonValue, object: &mut Indexed<T>, limits: &Limits) -> Result<(), Error> {
+		self.objects.add(limits)?;
+		if let Some(location) = self.locations.get(&(element as *const JsonValue as usize)) {
+			object.set_location(Some(location.clone()))
+		}
+
+		Ok(())
+	}
+}
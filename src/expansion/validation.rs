@@ -0,0 +1,132 @@
+use json::number::Number;
+use crate::{
+	Error,
+	ErrorCode,
+	Id,
+	object::{
+		Literal,
+		Value,
+		ExtractError
+	},
+	xsd::{
+		self,
+		Datatype
+	}
+};
+use super::Options;
+
+/// Validation of typed literal values during expansion.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum LiteralValidation {
+	/// Lexical forms are not validated.
+	#[default]
+	None,
+
+	/// Invalid lexical forms are reported with a warning.
+	Warn,
+
+	/// Invalid lexical forms cause an `InvalidLexicalForm` error.
+	///
+	/// The source of the error is an [`ExtractError::InvalidLexicalForm`]
+	/// giving the datatype and the invalid lexical form.
+	Strict
+}
+
+/// Validates and canonicalizes a typed literal value, according to the given options.
+///
+/// Strings, numbers and booleans typed with a well-known XSD datatype (see [`Datatype`]) are checked.
+/// Numbers are checked through their decimal representation, so that `1.0` is a valid
+/// `xsd:integer` but `1.5` is not.
+/// Canonicalized numbers and booleans are replaced by a string holding their canonical form.
+/// Other values are left untouched.
+///
+/// # Example
+/// ```
+/// use std::error::Error;
+/// use json_ld::{Document, JsonContext, NoLoader, ErrorCode, expansion::{Options, LiteralValidation}, util::AsJson};
+/// # use iref::IriBuf;
+/// # use futures::executor::block_on;
+/// let expand = |value: &str, options: Options| {
+///     let doc = json::parse(&format!(r#"{{
+///         "http://example.org/p": {{ "@value": {}, "@type": "http://www.w3.org/2001/XMLSchema#integer" }}
+///     }}"#, value)).unwrap();
+///     block_on(doc.expand_with(None, &JsonContext::<IriBuf>::new(None), &mut NoLoader, options))
+/// };
+///
+/// let strict = Options { literal_validation: LiteralValidation::Strict, ..Options::default() };
+/// let err = expand("1.5", strict).err().unwrap();
+/// assert_eq!(err.code(), ErrorCode::InvalidLexicalForm);
+/// assert_eq!(err.source().unwrap().to_string(), "invalid xsd:integer lexical form `1.5`");
+/// assert!(expand("true", strict).is_err());
+/// assert!(expand("1.0", strict).is_ok());
+///
+/// let canonicalize = Options { canonicalize_literals: true, ..Options::default() };
+/// let expanded = expand("1.0", canonicalize).unwrap();
+/// assert_eq!(expanded.as_json()[0]["http://example.org/p"][0]["@value"], "1");
+/// ```
+pub fn validate_value<T: Id>(value: &mut Value<T>, options: &Options) -> Result<(), Error> {
+	if options.literal_validation == LiteralValidation::None && !options.canonicalize_literals {
+		return Ok(())
+	}
+
+	if let Value::Literal(literal, Some(ty)) = value {
+		if let Some(datatype) = Datatype::from_iri(ty.as_iri()) {
+			let canonical = match literal {
+				Literal::String(lexical) => datatype.canonicalize(lexical),
+				Literal::Number(n) => datatype.canonicalize(&number_lexical_form(n)),
+				Literal::Boolean(b) => datatype.canonicalize(if *b { "true" } else { "false" }),
+				Literal::Null => return Ok(())
+			};
+
+			match canonical {
+				Some(canonical) => {
+					if options.canonicalize_literals {
+						*literal = Literal::String(canonical)
+					}
+				},
+				None => {
+					let lexical = match literal {
+						Literal::String(lexical) => lexical.clone(),
+						Literal::Number(n) => n.to_string(),
+						Literal::Boolean(b) => b.to_string(),
+						Literal::Null => unreachable!()
+					};
+
+					match options.literal_validation {
+						LiteralValidation::None => (),
+						LiteralValidation::Warn => warn!("invalid {} lexical form `{}`", datatype, lexical),
+						LiteralValidation::Strict => return Err(Error::new(ErrorCode::InvalidLexicalForm, ExtractError::InvalidLexicalForm { datatype, lexical }))
+					}
+				}
+			}
+		}
+	}
+
+	Ok(())
+}
+
+/// Decimal representation of a JSON number, in canonical `xsd:decimal` form.
+fn number_lexical_form(n: &Number) -> String {
+	if n.is_nan() {
+		return "NaN".to_string()
+	}
+
+	let (positive, mantissa, exponent) = n.as_parts();
+	let mut digits = mantissa.to_string();
+	if exponent >= 0 {
+		digits.push_str(&"0".repeat(exponent as usize))
+	} else {
+		let shift = -(exponent as i32) as usize;
+		if digits.len() <= shift {
+			digits.insert_str(0, &"0".repeat(shift + 1 - digits.len()))
+		}
+
+		digits.insert(digits.len() - shift, '.')
+	}
+
+	if !positive {
+		digits.insert(0, '-')
+	}
+
+	xsd::Decimal::parse(&digits).unwrap().to_string()
+}
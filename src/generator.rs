@@ -0,0 +1,278 @@
+use std::collections::HashMap;
+use std::hash::Hasher;
+use crate::{
+	Id,
+	BlankId,
+	Reference,
+	Lenient,
+	Object,
+	Node,
+	ExpandedDocument,
+	util::Fnv1a
+};
+
+/// Blank node identifier generator.
+///
+/// Generators are used by the algorithms that need to produce fresh blank node
+/// identifiers, such as the [RDF conversion](crate::rdf::to_rdf_with),
+/// [merging](crate::merge::Merger::with_generator) or [relabelling](relabel).
+///
+/// Expansion does not use generators: as required by the specification, it keeps the
+/// blank node identifiers of the input document and leaves anonymous nodes unlabelled.
+/// Flattening is not provided by this crate.
+/// The labels of an expanded document are controlled by applying [`relabel`] to it.
+pub trait BlankIdGenerator {
+	/// Generates a fresh blank node identifier.
+	fn next_blank_id(&mut self) -> BlankId;
+}
+
+impl<G: BlankIdGenerator> BlankIdGenerator for &mut G {
+	fn next_blank_id(&mut self) -> BlankId {
+		(**self).next_blank_id()
+	}
+}
+
+/// Sequential blank node identifier generator.
+///
+/// Generates identifiers of the form `_:{prefix}N` where `N` is a counter starting at `0`.
+/// The default prefix is `b`.
+pub struct Sequential {
+	prefix: String,
+	count: usize
+}
+
+impl Sequential {
+	/// Creates a new sequential generator with the default `b` prefix.
+	pub fn new() -> Sequential {
+		Self::with_prefix("b")
+	}
+
+	/// Creates a new sequential generator with the given prefix.
+	pub fn with_prefix(prefix: &str) -> Sequential {
+		Sequential {
+			prefix: prefix.to_string(),
+			count: 0
+		}
+	}
+}
+
+impl Default for Sequential {
+	fn default() -> Sequential {
+		Sequential::new()
+	}
+}
+
+impl BlankIdGenerator for Sequential {
+	fn next_blank_id(&mut self) -> BlankId {
+		let id = BlankId::new(&format!("{}{}", self.prefix, self.count));
+		self.count += 1;
+		id
+	}
+}
+
+/// Deterministic hash-based blank node identifier generator.
+///
+/// Generates identifiers of the form `_:h{hash}` where `hash` is the
+/// [FNV-1a](https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function)
+/// hash of the generator seed and a counter.
+/// The same seed always produces the same sequence of identifiers, on every platform,
+/// and different seeds (for instance the URL of each source document) produce
+/// different identifiers with high probability.
+pub struct Hashed {
+	seed: String,
+	count: u64
+}
+
+impl Hashed {
+	/// Creates a new generator with the given seed.
+	pub fn new(seed: &str) -> Hashed {
+		Hashed {
+			seed: seed.to_string(),
+			count: 0
+		}
+	}
+}
+
+impl BlankIdGenerator for Hashed {
+	fn next_blank_id(&mut self) -> BlankId {
+		let mut hasher = Fnv1a::new();
+		hasher.write(self.seed.as_bytes());
+		hasher.write_u8(0);
+		hasher.write_u64(self.count);
+		self.count += 1;
+		BlankId::new(&format!("h{:032x}", hasher.finish128()))
+	}
+}
+
+/// Random UUID-based blank node identifier generator.
+///
+/// Generates identifiers of the form `_:{uuid}` using version 4 UUIDs,
+/// which are globally unique with high probability.
+#[cfg(feature="uuid")]
+pub struct Uuid;
+
+#[cfg(feature="uuid")]
+impl BlankIdGenerator for Uuid {
+	fn next_blank_id(&mut self) -> BlankId {
+		BlankId::new(&uuid::Uuid::new_v4().to_string())
+	}
+}
+
+/// Blank node relabeller.
+///
+/// Assigns a fresh identifier, given by the underlying generator,
+/// to each distinct blank node identifier.
+pub struct Relabeller<G: BlankIdGenerator> {
+	generator: G,
+	labels: HashMap<BlankId, BlankId>
+}
+
+impl<G: BlankIdGenerator> Relabeller<G> {
+	/// Creates a new relabeller with the given generator.
+	pub fn new(generator: G) -> Relabeller<G> {
+		Relabeller {
+			generator,
+			labels: HashMap::new()
+		}
+	}
+
+	/// Returns the new label of the given blank node identifier.
+	pub fn relabel(&mut self, id: &BlankId) -> BlankId {
+		if let Some(label) = self.labels.get(id) {
+			return label.clone()
+		}
+
+		let label = self.generator.next_blank_id();
+		self.labels.insert(id.clone(), label.clone());
+		label
+	}
+
+	/// Relabels the given reference, if it is a blank node identifier.
+	pub fn relabel_reference<T: Id>(&mut self, r: Reference<T>) -> Reference<T> {
+		match r {
+			Reference::Blank(id) => Reference::Blank(self.relabel(&id)),
+			r => r
+		}
+	}
+
+	/// Generates a fresh blank node identifier, not associated to any previous identifier.
+	pub fn fresh(&mut self) -> BlankId {
+		self.generator.next_blank_id()
+	}
+
+	/// Returns the mapping from the previous identifiers to the new ones.
+	pub fn labels(&self) -> &HashMap<BlankId, BlankId> {
+		&self.labels
+	}
+
+	/// Returns the underlying generator.
+	pub fn into_generator(self) -> G {
+		self.generator
+	}
+}
+
+/// Relabels every blank node identifier of the given document using the given generator.
+///
+/// If `label_anonymous` is true, nodes without identifier are also given a fresh blank node
+/// identifier, distinct from the new labels of the identified blank nodes.
+///
+/// Expansion keeps the blank node identifiers of the input document as is and never
+/// generates new ones: this function can be applied to its result to enforce a labelling policy.
+///
+/// # Example
+/// ```
+/// use json_ld::{Reference, BlankId, Node, Object, ExpandedDocument, relabel, Hashed};
+/// # use iref::IriBuf;
+/// let doc: ExpandedDocument<IriBuf> = vec![
+///     Object::Node(Node::with_id(Reference::Blank(BlankId::new("a")).into())).into(),
+///     Object::Node(Node::new()).into()
+/// ].into_iter().collect();
+///
+/// let doc = relabel(doc, Hashed::new("https://example.org/source"), true);
+/// assert!(doc.iter().all(|object| object.id().unwrap().as_str().starts_with("_:h")));
+/// ```
+///
+/// Anonymous nodes are never merged with identified blank nodes,
+/// even if the generator would give them the label found in the input:
+/// ```
+/// use json_ld::{Document, JsonContext, NoLoader, relabel, Sequential, util::AsJson};
+/// let doc = json::parse(r#"{ "@id": "_:b0", "http://example.org/p": [{}] }"#).unwrap();
+/// let expanded = futures::executor::block_on(doc.expand::<JsonContext, _>(&mut NoLoader)).unwrap();
+///
+/// let relabelled = relabel(expanded, Sequential::new(), true);
+/// let expected = json::parse(r#"[{ "@id": "_:b0", "http://example.org/p": [{ "@id": "_:b1" }] }]"#).unwrap();
+/// assert_eq!(relabelled.as_json(), expected);
+/// ```
+///
+/// The [locations](crate::Indexed::location) of the relabelled objects are kept:
+/// ```
+/// use json_ld::{JsonContext, NoLoader, expansion, location, relabel, Sequential};
+/// # use iref::IriBuf;
+/// # use futures::executor::block_on;
+/// let (doc, source_map) = location::parse(r#"{ "@id": "_:a", "http://example.org/p": "a" }"#).unwrap();
+/// let context = JsonContext::<IriBuf>::new(None);
+/// let expanded = block_on(expansion::expand_located(&context, &doc, &source_map, None, &mut NoLoader, expansion::Options::default())).unwrap();
+///
+/// let relabelled = relabel(expanded, Sequential::new(), true);
+/// assert_eq!(relabelled.iter().next().unwrap().location().unwrap().pointer(), "");
+/// ```
+pub fn relabel<T: Id, G: BlankIdGenerator>(doc: ExpandedDocument<T>, generator: G, label_anonymous: bool) -> ExpandedDocument<T> {
+	let mut relabeller = Relabeller::new(generator);
+	doc.into_iter().map(|object| {
+		object.map_inner(|object| relabel_object(object, &mut relabeller, label_anonymous))
+	}).collect()
+}
+
+fn relabel_object<T: Id, G: BlankIdGenerator>(object: Object<T>, relabeller: &mut Relabeller<G>, label_anonymous: bool) -> Object<T> {
+	let object = object.map_references(&mut |r| relabeller.relabel_reference(r));
+
+	if label_anonymous {
+		label_anonymous_object(object, relabeller)
+	} else {
+		object
+	}
+}
+
+/// Gives a fresh identifier to every anonymous node of the given object.
+///
+/// This is done after relabelling the identified blank nodes:
+/// the fresh identifiers come from the same generator and are not mapped again.
+fn label_anonymous_object<T: Id, G: BlankIdGenerator>(object: Object<T>, relabeller: &mut Relabeller<G>) -> Object<T> {
+	match object {
+		Object::Value(value) => Object::Value(value),
+		Object::Node(node) => Object::Node(label_anonymous_node(node, relabeller)),
+		Object::List(items) => Object::List(items.into_iter().map(|item| {
+			item.map_inner(|item| label_anonymous_object(item, relabeller))
+		}).collect())
+	}
+}
+
+fn label_anonymous_node<T: Id, G: BlankIdGenerator>(mut node: Node<T>, relabeller: &mut Relabeller<G>) -> Node<T> {
+	if node.id.is_none() {
+		node.id = Some(Lenient::Ok(Reference::Blank(relabeller.fresh())))
+	}
+
+	for objects in node.properties.values_mut() {
+		let items = std::mem::take(objects);
+		*objects = items.into_iter().map(|object| {
+			object.map_inner(|object| label_anonymous_object(object, relabeller))
+		}).collect()
+	}
+
+	for nodes in node.reverse_properties.values_mut() {
+		let items = std::mem::take(nodes);
+		*nodes = items.into_iter().map(|n| {
+			n.map_inner(|n| label_anonymous_node(n, relabeller))
+		}).collect()
+	}
+
+	node.graph = node.graph.take().map(|graph| graph.into_iter().map(|object| {
+		object.map_inner(|object| label_anonymous_object(object, relabeller))
+	}).collect());
+
+	node.included = node.included.take().map(|included| included.into_iter().map(|n| {
+		n.map_inner(|n| label_anonymous_node(n, relabeller))
+	}).collect());
+
+	node
+}
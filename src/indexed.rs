@@ -4,6 +4,7 @@ use std::convert::{TryFrom, TryInto};
 use json::JsonValue;
 use crate::{
 	syntax::Keyword,
+	location::Location,
 	util::AsJson
 };
 
@@ -18,14 +19,17 @@ pub struct Indexed<T> {
 	index: Option<String>,
 
 	/// Value.
-	value: T
+	value: T,
+
+	/// Location of the JSON value it has been expanded from, if known.
+	location: Option<Box<Location>>
 }
 
 impl<T> Indexed<T> {
 	/// Create a new (maybe) indexed value.
 	pub fn new(value: T, index: Option<String>) -> Indexed<T> {
 		Indexed {
-			value, index
+			value, index, location: None
 		}
 	}
 
@@ -52,21 +56,47 @@ impl<T> Indexed<T> {
 		self.index = index
 	}
 
+	/// Get the location of the JSON value this value has been expanded from, if known.
+	///
+	/// Locations are only tracked by [`expand_located`](crate::expansion::expand_located).
+	/// They are not considered when comparing or hashing values.
+	pub fn location(&self) -> Option<&Location> {
+		self.location.as_deref()
+	}
+
+	/// Set the location of the JSON value this value has been expanded from.
+	pub fn set_location(&mut self, location: Option<Location>) {
+		self.location = location.map(Box::new)
+	}
+
 	/// Turn this indexed value into its components: inner value and index.
 	pub fn into_parts(self) -> (T, Option<String>) {
 		(self.value, self.index)
 	}
 
+	/// Map the inner value, keeping the index and location.
+	pub fn map_inner<U, F: FnOnce(T) -> U>(self, f: F) -> Indexed<U> {
+		Indexed {
+			value: f(self.value),
+			index: self.index,
+			location: self.location
+		}
+	}
+
 	/// Cast the inner value.
 	pub fn cast<U: From<T>>(self) -> Indexed<U> {
-		Indexed::new(self.value.into(), self.index)
+		Indexed {
+			value: self.value.into(),
+			index: self.index,
+			location: self.location
+		}
 	}
 
 	/// Try to cast the inner value.
 	pub fn try_cast<U: TryFrom<T>>(self) -> Result<Indexed<U>, Indexed<U::Error>> {
 		match self.value.try_into() {
-			Ok(value) => Ok(Indexed::new(value, self.index)),
-			Err(e) => Err(Indexed::new(e, self.index))
+			Ok(value) => Ok(Indexed { value, index: self.index, location: self.location }),
+			Err(e) => Err(Indexed { value: e, index: self.index, location: self.location })
 		}
 	}
 }
@@ -88,7 +118,11 @@ impl<T: Eq> Eq for Indexed<T> {}
 
 impl<T: Clone> Clone for Indexed<T> {
 	fn clone(&self) -> Self {
-		Indexed::new(self.value.clone(), self.index.clone())
+		Indexed {
+			value: self.value.clone(),
+			index: self.index.clone(),
+			location: self.location.clone()
+		}
 	}
 }
 
@@ -16,12 +16,22 @@ mod indexed;
 mod vocab;
 mod document;
 mod loader;
+mod node_map;
+mod generator;
+mod limits;
 pub mod syntax;
 pub mod object;
 pub mod context;
 pub mod expansion;
 pub mod compaction;
 pub mod util;
+pub mod xsd;
+pub mod query;
+pub mod rdf;
+pub mod diff;
+pub mod merge;
+pub mod skolem;
+pub mod location;
 
 #[cfg(feature="reqwest-loader")]
 pub mod reqwest;
@@ -39,6 +49,9 @@ pub use indexed::*;
 pub use vocab::*;
 pub use document::*;
 pub use loader::*;
+pub use node_map::*;
+pub use generator::*;
+pub use limits::*;
 pub use compaction::Compact;
 
 pub use object::{Object, Node, Value};
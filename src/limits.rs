@@ -0,0 +1,268 @@
+use futures::future::{BoxFuture, FutureExt};
+use iref::{Iri, IriBuf};
+use crate::{
+	Error,
+	ErrorCode,
+	LoaderId,
+	context::{
+		Loader,
+		RemoteContext
+	}
+};
+
+/// Resource limits.
+///
+/// Protects the expansion and context processing algorithms against hostile documents,
+/// that could otherwise exhaust the stack or the memory of the processor.
+/// Each limit is reported by its own error code when exceeded.
+///
+/// # Untrusted documents
+///
+/// By default, only the nesting depths are bounded
+/// (see [`DEFAULT_MAX_DEPTH`] and [`DEFAULT_MAX_SCOPED_CONTEXT_DEPTH`]):
+/// the number of objects and of remote contexts is unlimited.
+/// **Use [`Limits::untrusted`] to process documents from untrusted sources.**
+///
+/// The algorithms recurse on the stack, and the stack space used by each nesting level depends
+/// on the build profile: even bounded depths may exhaust small thread stacks in debug builds.
+///
+/// # Example
+/// ```
+/// use json_ld::{JsonContext, Document, NoLoader, ErrorCode, Limits, expansion};
+/// # use iref::IriBuf;
+/// # use futures::executor::block_on;
+/// let doc = json::parse(r#"{
+///     "http://example.org/a": { "http://example.org/a": { "http://example.org/a": "deep" } }
+/// }"#).unwrap();
+///
+/// let mut options = expansion::Options::default();
+/// options.limits = Limits {
+///     max_depth: Some(2),
+///     ..Limits::default()
+/// };
+///
+/// let result = block_on(doc.expand_with::<JsonContext<IriBuf>, _>(None, &JsonContext::new(None), &mut NoLoader, options));
+/// assert_eq!(result.err().unwrap().code(), ErrorCode::DepthLimitExceeded);
+///
+/// // Nested entries count as well.
+/// let doc = json::parse(r#"{
+///     "@context": { "@vocab": "http://example.org/" },
+///     "@nest": { "@nest": { "@nest": { "a": "deep" } } }
+/// }"#).unwrap();
+///
+/// let result = block_on(doc.expand_with::<JsonContext<IriBuf>, _>(None, &JsonContext::new(None), &mut NoLoader, options));
+/// assert_eq!(result.err().unwrap().code(), ErrorCode::DepthLimitExceeded);
+/// ```
+///
+/// Each other limit is reported by its own error code:
+/// ```
+/// use json_ld::{JsonContext, Document, SyncLoader, RemoteDocument, Error, ErrorCode, Limits, expansion::{self, ExpandReader}};
+/// use iref::{Iri, IriBuf};
+/// # use futures::executor::block_on;
+/// // Loader giving the same context for every URL.
+/// struct Contexts;
+///
+/// impl SyncLoader for Contexts {
+///     type Document = json::JsonValue;
+///
+///     fn load_sync(&mut self, url: Iri<'_>) -> Result<RemoteDocument, Error> {
+///         let doc = json::parse(r#"{ "@context": { "@vocab": "http://example.org/" } }"#).unwrap();
+///         Ok(RemoteDocument::new(doc, url))
+///     }
+/// }
+///
+/// let expand = |input: &str, limits: Limits| {
+///     let doc = json::parse(input).unwrap();
+///     let options = expansion::Options { limits, ..expansion::Options::default() };
+///     block_on(doc.expand_with::<JsonContext<IriBuf>, _>(None, &JsonContext::new(None), &mut Contexts, options)).err().map(|e| e.code())
+/// };
+///
+/// // Too many objects.
+/// let limits = Limits { max_objects: Some(2), ..Limits::default() };
+/// assert_eq!(expand(r#"{ "http://example.org/a": [ "1", "2", "3" ] }"#, limits), Some(ErrorCode::ObjectLimitExceeded));
+///
+/// // Too many remote contexts.
+/// let limits = Limits { max_remote_contexts: Some(1), ..Limits::default() };
+/// assert_eq!(expand(r#"{ "@context": [ "http://example.org/a", "http://example.org/b" ], "a": "1" }"#, limits), Some(ErrorCode::RemoteContextLimitExceeded));
+///
+/// // Too deeply nested scoped contexts.
+/// let limits = Limits { max_scoped_context_depth: Some(1), ..Limits::default() };
+/// let doc = r#"{
+///     "@context": {
+///         "a": {
+///             "@id": "http://example.org/a",
+///             "@context": { "b": { "@id": "http://example.org/b", "@context": {} } }
+///         }
+///     },
+///     "a": "1"
+/// }"#;
+/// assert_eq!(expand(doc, limits), Some(ErrorCode::ScopedContextDepthLimitExceeded));
+///
+/// // When streaming, remote contexts are counted across the whole document.
+/// let input = r#"{
+///     "@context": "http://example.org/a",
+///     "@graph": [ { "@context": "http://example.org/b", "a": "1" } ]
+/// }"#;
+/// let options = expansion::Options { limits: Limits { max_remote_contexts: Some(1), ..Limits::default() }, ..expansion::Options::default() };
+/// let mut loader = Contexts;
+/// let mut nodes = ExpandReader::<_, IriBuf, JsonContext<IriBuf>, _>::new(input.as_bytes(), None, JsonContext::new(None), &mut loader, options);
+/// assert_eq!(nodes.next().unwrap().err().unwrap().code(), ErrorCode::RemoteContextLimitExceeded);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Limits {
+	/// Maximum nesting depth of arrays and objects in an expanded document.
+	///
+	/// Objects nested with `@nest` count as a nesting level.
+	/// Exceeding it raises a [`DepthLimitExceeded`](ErrorCode::DepthLimitExceeded) error.
+	pub max_depth: Option<usize>,
+
+	/// Maximum number of objects (nodes, values and lists) produced by the expansion
+	/// of a document.
+	///
+	/// Exceeding it raises an [`ObjectLimitExceeded`](ErrorCode::ObjectLimitExceeded) error.
+	pub max_objects: Option<usize>,
+
+	/// Maximum nesting depth of scoped contexts in context definitions.
+	///
+	/// Exceeding it raises a [`ScopedContextDepthLimitExceeded`](ErrorCode::ScopedContextDepthLimitExceeded) error.
+	pub max_scoped_context_depth: Option<usize>,
+
+	/// Maximum number of remote contexts loaded during the expansion of a document.
+	///
+	/// Contexts found in the cache of the active context are not loaded, and not counted.
+	/// Exceeding it raises a [`RemoteContextLimitExceeded`](ErrorCode::RemoteContextLimitExceeded) error.
+	pub max_remote_contexts: Option<usize>
+}
+
+/// Default maximum nesting depth of arrays and objects in an expanded document.
+pub const DEFAULT_MAX_DEPTH: usize = 64;
+
+/// Default maximum nesting depth of scoped contexts.
+pub const DEFAULT_MAX_SCOPED_CONTEXT_DEPTH: usize = 64;
+
+impl Default for Limits {
+	fn default() -> Limits {
+		Limits {
+			max_depth: Some(DEFAULT_MAX_DEPTH),
+			max_objects: None,
+			max_scoped_context_depth: Some(DEFAULT_MAX_SCOPED_CONTEXT_DEPTH),
+			max_remote_contexts: None
+		}
+	}
+}
+
+impl Limits {
+	/// No limit at all.
+	///
+	/// Deeply nested documents may then overflow the stack.
+	pub fn unlimited() -> Limits {
+		Limits {
+			max_depth: None,
+			max_objects: None,
+			max_scoped_context_depth: None,
+			max_remote_contexts: None
+		}
+	}
+
+	/// Limits suitable to process untrusted documents.
+	pub fn untrusted() -> Limits {
+		Limits {
+			max_depth: Some(64),
+			max_objects: Some(1_000_000),
+			max_scoped_context_depth: Some(32),
+			max_remote_contexts: Some(64)
+		}
+	}
+
+	/// Returns the limits to use one nesting level deeper in the expanded document.
+	pub(crate) fn nested(mut self) -> Result<Limits, Error> {
+		self.max_depth = decrement(self.max_depth, ErrorCode::DepthLimitExceeded)?;
+		Ok(self)
+	}
+
+	/// Returns the limits to use one scoped context deeper.
+	pub(crate) fn scoped(mut self) -> Result<Limits, Error> {
+		self.max_scoped_context_depth = decrement(self.max_scoped_context_depth, ErrorCode::ScopedContextDepthLimitExceeded)?;
+		Ok(self)
+	}
+}
+
+fn decrement(limit: Option<usize>, code: ErrorCode) -> Result<Option<usize>, Error> {
+	match limit {
+		Some(0) => Err(code.into()),
+		Some(n) => Ok(Some(n - 1)),
+		None => Ok(None)
+	}
+}
+
+/// Number of objects produced by the expansion of a document,
+/// checked against [`Limits::max_objects`].
+#[derive(Clone, Copy, Default, Debug)]
+pub struct ObjectCount(usize);
+
+impl ObjectCount {
+	/// Creates a new counter, starting at zero.
+	pub fn new() -> ObjectCount {
+		ObjectCount(0)
+	}
+
+	/// Returns the number of objects counted so far.
+	pub fn get(&self) -> usize {
+		self.0
+	}
+
+	/// Counts a new object.
+	pub(crate) fn add(&mut self, limits: &Limits) -> Result<(), Error> {
+		self.0 += 1;
+		match limits.max_objects {
+			Some(max) if self.0 > max => Err(ErrorCode::ObjectLimitExceeded.into()),
+			_ => Ok(())
+		}
+	}
+}
+
+/// Loader counting the remote contexts it loads, checked against [`Limits::max_remote_contexts`].
+pub(crate) struct CountingLoader<'l, L> {
+	loader: &'l mut L,
+	remaining: usize
+}
+
+impl<'l, L> CountingLoader<'l, L> {
+	pub(crate) fn new(loader: &'l mut L, max: usize) -> CountingLoader<'l, L> {
+		CountingLoader {
+			loader,
+			remaining: max
+		}
+	}
+
+	/// Returns the number of remote contexts that can still be loaded.
+	pub(crate) fn remaining(&self) -> usize {
+		self.remaining
+	}
+}
+
+impl<'l, L: Send + Loader> Loader for CountingLoader<'l, L> {
+	type Output = L::Output;
+
+	fn load_context<'a>(&'a mut self, url: Iri) -> BoxFuture<'a, Result<RemoteContext<L::Output>, Error>> {
+		if self.remaining == 0 {
+			return async { Err(ErrorCode::RemoteContextLimitExceeded.into()) }.boxed()
+		}
+
+		self.remaining -= 1;
+		self.loader.load_context(url)
+	}
+
+	fn prefetch_contexts<'a>(&'a mut self, mut urls: Vec<IriBuf>) -> BoxFuture<'a, ()> {
+		urls.truncate(self.remaining);
+		self.loader.prefetch_contexts(urls)
+	}
+
+	fn supports_prefetch(&self) -> bool {
+		self.loader.supports_prefetch()
+	}
+
+	fn loader_id(&self) -> Option<LoaderId> {
+		self.loader.loader_id()
+	}
+}
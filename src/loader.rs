@@ -1,5 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, hash_map::DefaultHasher};
+use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::fs::File;
 use std::io::{Read, BufReader};
 use futures::future::{FutureExt, BoxFuture};
@@ -15,6 +18,41 @@ use crate::{
 	}
 };
 
+/// Loader identifier.
+///
+/// Processed contexts are cached (see [`ContextCache`](context::ContextCache)) along with
+/// the identifier of the loader used to load their remote contexts,
+/// since two loaders may resolve the same URL to different documents.
+/// Two loaders must only share an identifier if they load the same documents.
+///
+/// Loaders should derive their identifier from their configuration with [`LoaderId::of`],
+/// so that loaders created for each request share the cached contexts.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct LoaderId(u64);
+
+impl LoaderId {
+	/// Identifier of loaders that never load any document, such as [`NoLoader`].
+	pub const NO_LOADER: LoaderId = LoaderId(0);
+
+	/// Returns the identifier of the loaders having the given configuration.
+	///
+	/// Loaders with equal configurations must load the same documents.
+	pub fn of<D: Hash + ?Sized>(configuration: &D) -> LoaderId {
+		let mut hasher = DefaultHasher::new();
+		configuration.hash(&mut hasher);
+		// Odd identifiers are never given by `fresh`.
+		LoaderId(hasher.finish() | 1)
+	}
+
+	/// Returns a new unique loader identifier.
+	///
+	/// Contexts processed with a unique loader are only reused with the same loader.
+	pub fn fresh() -> LoaderId {
+		static COUNT: AtomicU64 = AtomicU64::new(2);
+		LoaderId(COUNT.fetch_add(2, Ordering::Relaxed))
+	}
+}
+
 /// Document loader.
 pub trait Loader {
 	/// The type of documents that can be loaded.
@@ -22,6 +60,137 @@ pub trait Loader {
 
 	/// Load the document behind the given URL.
 	fn load<'a>(&'a mut self, url: Iri<'_>) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>>;
+
+	/// Prefetches the documents behind the given URLs, that are about to be loaded in order.
+	///
+	/// Loaders that cache documents can use it to load them concurrently.
+	/// Errors must be ignored: they are reported when the document is actually loaded.
+	///
+	/// Prefetching is a capability of the loader: the default implementation does nothing.
+	/// Among the loaders of this crate, only the `reqwest` loader (`reqwest-loader` feature)
+	/// prefetches documents; [`FsLoader`] and every [`SyncLoader`] load them on demand.
+	fn prefetch<'a>(&'a mut self, _urls: Vec<IriBuf>) -> BoxFuture<'a, ()> {
+		async {}.boxed()
+	}
+
+	/// Checks if the loader prefetches documents.
+	///
+	/// Algorithms only look for the documents to [`prefetch`](Loader::prefetch)
+	/// when this returns `true`. The default implementation returns `false`.
+	fn supports_prefetch(&self) -> bool {
+		false
+	}
+
+	/// Identifier of the loader, if any.
+	///
+	/// Processed contexts are only cached for loaders having an identifier.
+	/// The default implementation returns `None`.
+	fn loader_id(&self) -> Option<LoaderId> {
+		None
+	}
+}
+
+/// Synchronous document loader.
+///
+/// Every synchronous loader is also a [`Loader`] whose futures complete without ever suspending,
+/// so that documents can be processed with a simple executor such as `futures::executor::block_on`.
+/// The algorithms themselves remain asynchronous.
+pub trait SyncLoader {
+	/// The type of documents that can be loaded.
+	type Document;
+
+	/// Load the document behind the given URL.
+	fn load_sync(&mut self, url: Iri<'_>) -> Result<RemoteDocument<Self::Document>, Error>;
+
+	/// Identifier of the loader, if any.
+	///
+	/// See [`Loader::loader_id`]. The default implementation returns `None`.
+	fn loader_id(&self) -> Option<LoaderId> {
+		None
+	}
+}
+
+impl<L: Send + SyncLoader> Loader for L where L::Document: Send {
+	type Document = L::Document;
+
+	fn load<'a>(&'a mut self, url: Iri<'_>) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		let url = IriBuf::from(url);
+		async move {
+			self.load_sync(url.as_iri())
+		}.boxed()
+	}
+
+	fn loader_id(&self) -> Option<LoaderId> {
+		SyncLoader::loader_id(self)
+	}
+}
+
+/// Shared document loader.
+///
+/// Unlike [`Loader`], documents are loaded through a shared reference,
+/// so that a single loader, and its cache, can serve concurrent expansions.
+/// Any `Arc<L>` where `L` is a shared loader is a [`Loader`]:
+/// each task can use its own clone of the `Arc`.
+///
+/// # Example
+/// ```
+/// use std::sync::Arc;
+/// use json_ld::{Document, JsonContext, FsLoader};
+/// # use futures::executor::block_on;
+///
+/// let loader = Arc::new(FsLoader::new());
+/// let doc = json::parse(r#"{ "http://xmlns.com/foaf/0.1/name": "Timothée Haudebourg" }"#).unwrap();
+/// let expanded_doc = block_on(doc.expand::<JsonContext, _>(&mut loader.clone())).unwrap();
+/// ```
+pub trait SharedLoader: Send + Sync {
+	/// The type of documents that can be loaded.
+	type Document;
+
+	/// Load the document behind the given URL.
+	fn load_shared<'a>(&'a self, url: Iri<'_>) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>>;
+
+	/// Prefetches the documents behind the given URLs, that are about to be loaded in order.
+	///
+	/// See [`Loader::prefetch`]. The default implementation does nothing.
+	fn prefetch_shared<'a>(&'a self, _urls: Vec<IriBuf>) -> BoxFuture<'a, ()> {
+		async {}.boxed()
+	}
+
+	/// Checks if the loader prefetches documents.
+	///
+	/// See [`Loader::supports_prefetch`]. The default implementation returns `false`.
+	fn supports_prefetch(&self) -> bool {
+		false
+	}
+
+	/// Identifier of the loader, if any.
+	///
+	/// See [`Loader::loader_id`]. The default implementation returns `None`.
+	fn loader_id(&self) -> Option<LoaderId> {
+		None
+	}
+}
+
+impl<L: SharedLoader> Loader for Arc<L> {
+	type Document = L::Document;
+
+	fn load<'a>(&'a mut self, url: Iri<'_>) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		let loader: &'a L = self;
+		loader.load_shared(url)
+	}
+
+	fn prefetch<'a>(&'a mut self, urls: Vec<IriBuf>) -> BoxFuture<'a, ()> {
+		let loader: &'a L = self;
+		loader.prefetch_shared(urls)
+	}
+
+	fn supports_prefetch(&self) -> bool {
+		SharedLoader::supports_prefetch(&**self)
+	}
+
+	fn loader_id(&self) -> Option<LoaderId> {
+		SharedLoader::loader_id(&**self)
+	}
 }
 
 impl<L: Send + Sync + Loader<Document = JsonValue>> context::Loader for L {
@@ -49,6 +218,18 @@ impl<L: Send + Sync + Loader<Document = JsonValue>> context::Loader for L {
 			}
 		}.boxed()
 	}
+
+	fn prefetch_contexts<'a>(&'a mut self, urls: Vec<IriBuf>) -> BoxFuture<'a, ()> {
+		self.prefetch(urls)
+	}
+
+	fn supports_prefetch(&self) -> bool {
+		Loader::supports_prefetch(self)
+	}
+
+	fn loader_id(&self) -> Option<LoaderId> {
+		Loader::loader_id(self)
+	}
 }
 
 /// Dummy loader.
@@ -59,13 +240,15 @@ impl<L: Send + Sync + Loader<Document = JsonValue>> context::Loader for L {
 /// Raises an `LoadingDocumentFailed` at every attempt to load a ressource.
 pub struct NoLoader;
 
-impl Loader for NoLoader {
+impl SyncLoader for NoLoader {
 	type Document = JsonValue;
 
-	fn load<'a>(&'a mut self, _url: Iri<'_>) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
-		async move {
-			Err(ErrorCode::LoadingDocumentFailed.into())
-		}.boxed()
+	fn load_sync(&mut self, _url: Iri<'_>) -> Result<RemoteDocument<Self::Document>, Error> {
+		Err(ErrorCode::LoadingDocumentFailed.into())
+	}
+
+	fn loader_id(&self) -> Option<LoaderId> {
+		Some(LoaderId::NO_LOADER)
 	}
 }
 
@@ -73,67 +256,132 @@ impl Loader for NoLoader {
 ///
 /// This is a special JSON-LD document loader that can load document from the file system by
 /// attaching a directory to specific URLs.
+///
+/// Loaded documents are cached. The cache is internally synchronized so that
+/// the loader can be shared between tasks as a [`SharedLoader`].
+///
+/// The [`LoaderId`] of the loader is derived from its mount points:
+/// loaders mounting the same directories on the same URLs share the cached contexts.
+///
+/// # Example
+/// ```
+/// use json_ld::{JsonContext, Document, FsLoader, context::ContextCache};
+/// # use iref::{Iri, IriBuf};
+/// # use futures::executor::block_on;
+/// let cache = ContextCache::new();
+/// let context: JsonContext<IriBuf> = JsonContext::with_cache(None, cache.clone());
+/// let doc = json::parse(r#"{
+///     "@context": { "name": "http://schema.org/name" },
+///     "name": "Jane Doe"
+/// }"#).unwrap();
+///
+/// let loader = || {
+///     let mut loader = FsLoader::new();
+///     loader.mount(Iri::new("https://example.org/contexts/").unwrap(), "contexts");
+///     loader
+/// };
+///
+/// // A new loader is used for each expansion.
+/// block_on(doc.expand_with(None, &context, &mut loader(), Default::default())).unwrap();
+/// block_on(doc.expand_with(None, &context, &mut loader(), Default::default())).unwrap();
+/// assert_eq!(cache.len(), 1);
+///
+/// // Loaders with other mount points do not share the cached contexts.
+/// block_on(doc.expand_with(None, &context, &mut FsLoader::new(), Default::default())).unwrap();
+/// assert_eq!(cache.len(), 2);
+/// ```
 pub struct FsLoader {
-	cache: HashMap<IriBuf, RemoteDocument>,
+	id: LoaderId,
+	cache: Mutex<HashMap<IriBuf, RemoteDocument>>,
 	mount_points: HashMap<PathBuf, IriBuf>
 }
 
 impl FsLoader {
 	pub fn new() -> FsLoader {
 		FsLoader {
-			cache: HashMap::new(),
+			id: LoaderId::of(&Vec::<(PathBuf, IriBuf)>::new()),
+			cache: Mutex::new(HashMap::new()),
 			mount_points: HashMap::new()
 		}
 	}
 
 	pub fn mount<P: AsRef<Path>>(&mut self, url: Iri, path: P) {
 		self.mount_points.insert(path.as_ref().into(), url.into());
+
+		let mut mount_points: Vec<_> = self.mount_points.iter().collect();
+		mount_points.sort();
+		self.id = LoaderId::of(&mount_points);
 	}
 }
 
-impl Loader for FsLoader {
-	type Document = JsonValue;
-
-	fn load<'a>(&'a mut self, url: Iri<'_>) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+impl FsLoader {
+	/// Load the document behind the given URL, through a shared reference.
+	fn load_ref(&self, url: Iri<'_>) -> Result<RemoteDocument, Error> {
 		let url: IriBuf = url.into();
-		async move {
-			match self.cache.get(&url) {
-				Some(doc) => Ok(doc.clone()),
-				None => {
-					for (path, target_url) in &self.mount_points {
-						let url_ref = url.as_iri_ref();
-						match url_ref.suffix(target_url.as_iri_ref()) {
-							Some((suffix, _, _)) => {
-								let mut filepath = path.clone();
-								for seg in suffix.as_path().segments() {
-									filepath.push(seg.as_str())
-								}
+		let cached = self.cache.lock().unwrap().get(&url).cloned();
+		match cached {
+			Some(doc) => Ok(doc),
+			None => {
+				for (path, target_url) in &self.mount_points {
+					let url_ref = url.as_iri_ref();
+					match url_ref.suffix(target_url.as_iri_ref()) {
+						Some((suffix, _, _)) => {
+							let mut filepath = path.clone();
+							for seg in suffix.as_path().segments() {
+								filepath.push(seg.as_str())
+							}
 
-								if let Ok(file) = File::open(filepath) {
-								    let mut buf_reader = BufReader::new(file);
-								    let mut contents = String::new();
-								    if buf_reader.read_to_string(&mut contents).is_ok() {
-										if let Ok(doc) = json::parse(contents.as_str()) {
-											let remote_doc = RemoteDocument::new(doc, url.as_iri());
-											self.cache.insert(url.clone(), remote_doc.clone());
-											return Ok(remote_doc)
-										} else {
-											return Err(ErrorCode::LoadingDocumentFailed.into())
-										}
+							if let Ok(file) = File::open(filepath) {
+							    let mut buf_reader = BufReader::new(file);
+							    let mut contents = String::new();
+							    if buf_reader.read_to_string(&mut contents).is_ok() {
+									if let Ok(doc) = json::parse(contents.as_str()) {
+										let remote_doc = RemoteDocument::new(doc, url.as_iri());
+										self.cache.lock().unwrap().insert(url.clone(), remote_doc.clone());
+										return Ok(remote_doc)
 									} else {
 										return Err(ErrorCode::LoadingDocumentFailed.into())
 									}
 								} else {
 									return Err(ErrorCode::LoadingDocumentFailed.into())
 								}
-							},
-							None => ()
-						}
+							} else {
+								return Err(ErrorCode::LoadingDocumentFailed.into())
+							}
+						},
+						None => ()
 					}
-
-					Err(ErrorCode::LoadingDocumentFailed.into())
 				}
+
+				Err(ErrorCode::LoadingDocumentFailed.into())
 			}
+		}
+	}
+}
+
+impl SyncLoader for FsLoader {
+	type Document = JsonValue;
+
+	fn load_sync(&mut self, url: Iri<'_>) -> Result<RemoteDocument<Self::Document>, Error> {
+		self.load_ref(url)
+	}
+
+	fn loader_id(&self) -> Option<LoaderId> {
+		Some(self.id)
+	}
+}
+
+impl SharedLoader for FsLoader {
+	type Document = JsonValue;
+
+	fn load_shared<'a>(&'a self, url: Iri<'_>) -> BoxFuture<'a, Result<RemoteDocument<Self::Document>, Error>> {
+		let url = IriBuf::from(url);
+		async move {
+			self.load_ref(url.as_iri())
 		}.boxed()
 	}
+
+	fn loader_id(&self) -> Option<LoaderId> {
+		Some(self.id)
+	}
 }
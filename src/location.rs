@@ -0,0 +1,486 @@
+//! Source locations.
+//!
+//! This module provides a span-aware JSON parser, [`parse`], that returns the parsed document
+//! along with a [`SourceMap`] giving the position of each of its values in the input text.
+//! Given to [`expand_located`](crate::expansion::expand_located), the source map allows each
+//! expanded object to be mapped back to the JSON value that produced it
+//! (see [`Indexed::location`](crate::Indexed::location)).
+//!
+//! # Example
+//! ```
+//! use json_ld::{JsonContext, NoLoader, expansion, location};
+//! # use iref::IriBuf;
+//! # use futures::executor::block_on;
+//! let input = "{\n  \"@id\": \"http://example.org/jane\",\n  \"http://schema.org/name\": \"Jane Doe\"\n}";
+//! let (doc, source_map) = location::parse(input).unwrap();
+//!
+//! let context: JsonContext<IriBuf> = JsonContext::new(None);
+//! let expanded = block_on(expansion::expand_located(&context, &doc, &source_map, None, &mut NoLoader, expansion::Options::default())).unwrap();
+//! let node = match expanded.iter().next().unwrap().inner() {
+//!     json_ld::Object::Node(node) => node,
+//!     _ => unreachable!()
+//! };
+//! let name = node.get(&json_ld::Reference::Id(IriBuf::new("http://schema.org/name").unwrap())).next().unwrap();
+//!
+//! let location = name.location().unwrap();
+//! assert_eq!(location.pointer(), "/http:~1~1schema.org~1name");
+//! assert_eq!(location.span().start().line(), 2);
+//! assert_eq!(location.span().start().column(), 28);
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use json::JsonValue;
+
+/// Maximum nesting depth of parsed documents, as with `json::parse`.
+const MAX_DEPTH: usize = 512;
+
+/// Position in a text.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct Position {
+	offset: usize,
+	line: usize,
+	column: usize
+}
+
+impl Position {
+	/// Byte offset from the start of the text.
+	pub fn offset(&self) -> usize {
+		self.offset
+	}
+
+	/// Line number, starting from 0.
+	pub fn line(&self) -> usize {
+		self.line
+	}
+
+	/// Column number in characters, starting from 0.
+	pub fn column(&self) -> usize {
+		self.column
+	}
+}
+
+impl fmt::Display for Position {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}:{}", self.line + 1, self.column + 1)
+	}
+}
+
+/// Span of text, from its start position (included) to its end position (excluded).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Span {
+	start: Position,
+	end: Position
+}
+
+impl Span {
+	/// Start position.
+	pub fn start(&self) -> Position {
+		self.start
+	}
+
+	/// End position.
+	pub fn end(&self) -> Position {
+		self.end
+	}
+
+	/// Byte range of the span.
+	pub fn range(&self) -> std::ops::Range<usize> {
+		self.start.offset..self.end.offset
+	}
+}
+
+/// Location of a JSON value in its source document.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Location {
+	pointer: String,
+	span: Span
+}
+
+impl Location {
+	/// Creates a new location.
+	pub fn new(pointer: String, span: Span) -> Location {
+		Location {
+			pointer,
+			span
+		}
+	}
+
+	/// JSON pointer ([RFC 6901](https://tools.ietf.org/html/rfc6901)) to the value.
+	pub fn pointer(&self) -> &str {
+		&self.pointer
+	}
+
+	/// Span of the value in the source text.
+	pub fn span(&self) -> Span {
+		self.span
+	}
+}
+
+/// Spans of the values of a parsed JSON document, indexed by JSON pointer.
+#[derive(Clone, Default, Debug)]
+pub struct SourceMap {
+	spans: HashMap<String, Span>
+}
+
+impl SourceMap {
+	/// Returns the span of the value pointed by the given JSON pointer.
+	pub fn get(&self, pointer: &str) -> Option<Span> {
+		self.spans.get(pointer).cloned()
+	}
+
+	/// Number of values in the source map.
+	pub fn len(&self) -> usize {
+		self.spans.len()
+	}
+
+	/// Checks if the source map is empty.
+	pub fn is_empty(&self) -> bool {
+		self.spans.is_empty()
+	}
+
+	/// Returns the location of every value of the given document,
+	/// indexed by the address of the value.
+	///
+	/// The document must be the one described by this source map.
+	pub(crate) fn locations(&self, document: &JsonValue) -> HashMap<usize, Location> {
+		let mut locations = HashMap::new();
+		self.collect_locations(document, &mut String::new(), &mut locations);
+		locations
+	}
+
+	fn collect_locations(&self, value: &JsonValue, pointer: &mut String, locations: &mut HashMap<usize, Location>) {
+		if let Some(span) = self.get(pointer) {
+			locations.insert(value as *const JsonValue as usize, Location::new(pointer.clone(), span));
+		}
+
+		let len = pointer.len();
+		match value {
+			JsonValue::Array(items) => {
+				for (i, item) in items.iter().enumerate() {
+					pointer.push('/');
+					pointer.push_str(&i.to_string());
+					self.collect_locations(item, pointer, locations);
+					pointer.truncate(len)
+				}
+			},
+			JsonValue::Object(obj) => {
+				for (key, item) in obj.iter() {
+					push_pointer_key(pointer, key);
+					self.collect_locations(item, pointer, locations);
+					pointer.truncate(len)
+				}
+			},
+			_ => ()
+		}
+	}
+}
+
+fn push_pointer_key(pointer: &mut String, key: &str) {
+	pointer.push('/');
+	for c in key.chars() {
+		match c {
+			'~' => pointer.push_str("~0"),
+			'/' => pointer.push_str("~1"),
+			c => pointer.push(c)
+		}
+	}
+}
+
+/// Parsing error.
+#[derive(Clone, Debug)]
+pub struct ParseError {
+	message: &'static str,
+	position: Position
+}
+
+impl ParseError {
+	/// Error message.
+	pub fn message(&self) -> &str {
+		self.message
+	}
+
+	/// Position of the error.
+	pub fn position(&self) -> Position {
+		self.position
+	}
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} at {}", self.message, self.position)
+	}
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses a JSON document, recording the span of each of its values.
+///
+/// As with [`json::parse`], the last value of a duplicate key replaces the previous ones.
+///
+/// # Example
+/// ```
+/// use json_ld::location;
+/// let (doc, source_map) = location::parse(r#"{ "a": { "b": 1 }, "a": 2 }"#).unwrap();
+/// assert_eq!(doc["a"], 2);
+/// assert_eq!(source_map.get("/a").unwrap().range(), 24..25);
+/// assert!(source_map.get("/a/b").is_none());
+/// assert_eq!(source_map.len(), 2);
+/// ```
+pub fn parse(input: &str) -> Result<(JsonValue, SourceMap), ParseError> {
+	let mut parser = Parser {
+		input,
+		position: Position {
+			offset: 0,
+			line: 0,
+			column: 0
+		},
+		pointer: String::new(),
+		spans: HashMap::new()
+	};
+
+	let value = parser.value(0)?;
+	parser.skip_whitespace();
+	if parser.peek().is_some() {
+		return Err(parser.error("unexpected character"))
+	}
+
+	Ok((value, SourceMap {
+		spans: parser.spans
+	}))
+}
+
+struct Parser<'a> {
+	input: &'a str,
+	position: Position,
+
+	/// JSON pointer to the value being parsed.
+	pointer: String,
+
+	spans: HashMap<String, Span>
+}
+
+impl<'a> Parser<'a> {
+	fn error(&self, message: &'static str) -> ParseError {
+		ParseError {
+			message,
+			position: self.position
+		}
+	}
+
+	fn peek(&self) -> Option<char> {
+		self.input[self.position.offset..].chars().next()
+	}
+
+	fn next(&mut self) -> Option<char> {
+		let c = self.peek()?;
+		self.position.offset += c.len_utf8();
+		if c == '\n' {
+			self.position.line += 1;
+			self.position.column = 0
+		} else {
+			self.position.column += 1
+		}
+
+		Some(c)
+	}
+
+	fn expect(&mut self, expected: char, message: &'static str) -> Result<(), ParseError> {
+		if self.peek() == Some(expected) {
+			self.next();
+			Ok(())
+		} else {
+			Err(self.error(message))
+		}
+	}
+
+	fn skip_whitespace(&mut self) {
+		while let Some(' ') | Some('\t') | Some('\n') | Some('\r') = self.peek() {
+			self.next();
+		}
+	}
+
+	fn value(&mut self, depth: usize) -> Result<JsonValue, ParseError> {
+		if depth > MAX_DEPTH {
+			return Err(self.error("exceeded depth limit"))
+		}
+
+		self.skip_whitespace();
+		let start = self.position;
+		let value = match self.peek() {
+			Some('{') => self.object(depth)?,
+			Some('[') => self.array(depth)?,
+			Some('"') => JsonValue::from(self.string()?.as_str()),
+			Some('t') => self.keyword("true", JsonValue::Boolean(true))?,
+			Some('f') => self.keyword("false", JsonValue::Boolean(false))?,
+			Some('n') => self.keyword("null", JsonValue::Null)?,
+			Some('-') | Some('0'..='9') => self.number()?,
+			Some(_) => return Err(self.error("unexpected character")),
+			None => return Err(self.error("unexpected end of input"))
+		};
+
+		self.spans.insert(self.pointer.clone(), Span {
+			start,
+			end: self.position
+		});
+
+		Ok(value)
+	}
+
+	fn object(&mut self, depth: usize) -> Result<JsonValue, ParseError> {
+		self.next();
+		let mut obj = json::object::Object::new();
+
+		self.skip_whitespace();
+		if self.peek() == Some('}') {
+			self.next();
+			return Ok(JsonValue::Object(obj))
+		}
+
+		loop {
+			self.skip_whitespace();
+			if self.peek() != Some('"') {
+				return Err(self.error("expected a key"))
+			}
+
+			let key = self.string()?;
+			self.skip_whitespace();
+			self.expect(':', "expected `:`")?;
+
+			let len = self.pointer.len();
+			push_pointer_key(&mut self.pointer, &key);
+
+			// The value of a duplicate key replaces the previous one, along with its nested values.
+			if obj.get(&key).is_some() {
+				let prefix = format!("{}/", self.pointer);
+				self.spans.retain(|pointer, _| !pointer.starts_with(&prefix))
+			}
+
+			let value = self.value(depth + 1)?;
+			self.pointer.truncate(len);
+			obj.insert(&key, value);
+
+			self.skip_whitespace();
+			match self.next() {
+				Some(',') => (),
+				Some('}') => break,
+				_ => return Err(self.error("expected `,` or `}`"))
+			}
+		}
+
+		Ok(JsonValue::Object(obj))
+	}
+
+	fn array(&mut self, depth: usize) -> Result<JsonValue, ParseError> {
+		self.next();
+		let mut items = Vec::new();
+
+		self.skip_whitespace();
+		if self.peek() == Some(']') {
+			self.next();
+			return Ok(JsonValue::Array(items))
+		}
+
+		loop {
+			let len = self.pointer.len();
+			self.pointer.push('/');
+			self.pointer.push_str(&items.len().to_string());
+			let item = self.value(depth + 1)?;
+			self.pointer.truncate(len);
+			items.push(item);
+
+			self.skip_whitespace();
+			match self.next() {
+				Some(',') => (),
+				Some(']') => break,
+				_ => return Err(self.error("expected `,` or `]`"))
+			}
+		}
+
+		Ok(JsonValue::Array(items))
+	}
+
+	fn keyword(&mut self, keyword: &str, value: JsonValue) -> Result<JsonValue, ParseError> {
+		if self.input[self.position.offset..].starts_with(keyword) {
+			for _ in keyword.chars() {
+				self.next();
+			}
+
+			Ok(value)
+		} else {
+			Err(self.error("unexpected character"))
+		}
+	}
+
+	fn number(&mut self) -> Result<JsonValue, ParseError> {
+		let start = self.position;
+		while let Some('0'..='9') | Some('-') | Some('+') | Some('.') | Some('e') | Some('E') = self.peek() {
+			self.next();
+		}
+
+		match json::parse(&self.input[start.offset..self.position.offset]) {
+			Ok(value @ JsonValue::Number(_)) => Ok(value),
+			_ => Err(ParseError {
+				message: "invalid number",
+				position: start
+			})
+		}
+	}
+
+	fn string(&mut self) -> Result<String, ParseError> {
+		self.next();
+		let mut result = String::new();
+		loop {
+			match self.next() {
+				Some('"') => break,
+				Some('\\') => {
+					let c = match self.next() {
+						Some('"') => '"',
+						Some('\\') => '\\',
+						Some('/') => '/',
+						Some('b') => '\u{8}',
+						Some('f') => '\u{c}',
+						Some('n') => '\n',
+						Some('r') => '\r',
+						Some('t') => '\t',
+						Some('u') => {
+							let high = self.hex()?;
+							if (0xd800..0xdc00).contains(&high) {
+								if self.next() != Some('\\') || self.next() != Some('u') {
+									return Err(self.error("expected a low surrogate"))
+								}
+
+								let low = self.hex()?;
+								if !(0xdc00..0xe000).contains(&low) {
+									return Err(self.error("invalid low surrogate"))
+								}
+
+								std::char::from_u32(0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)).unwrap()
+							} else {
+								std::char::from_u32(high).ok_or_else(|| self.error("invalid unicode escape"))?
+							}
+						},
+						_ => return Err(self.error("invalid escape sequence"))
+					};
+
+					result.push(c)
+				},
+				Some(c) if c < ' ' => return Err(self.error("unescaped control character")),
+				Some(c) => result.push(c),
+				None => return Err(self.error("unexpected end of input"))
+			}
+		}
+
+		Ok(result)
+	}
+
+	fn hex(&mut self) -> Result<u32, ParseError> {
+		let mut n = 0;
+		for _ in 0..4 {
+			let digit = self.next().and_then(|c| c.to_digit(16)).ok_or_else(|| self.error("invalid unicode escape"))?;
+			n = n * 16 + digit
+		}
+
+		Ok(n)
+	}
+}
@@ -0,0 +1,450 @@
+//! Merging of expanded documents.
+//!
+//! Nodes of each graph sharing the same identifier are unified into a single top-level node
+//! object, whose types and property values are the union of the merged nodes.
+//! Identified nodes embedded in other nodes are moved to the top level of their graph
+//! and replaced by a reference (a node object with only an `@id`), as done by flattening.
+//! Anonymous nodes are kept in place.
+//! Blank node identifiers are local to each source document: they are relabelled before
+//! merging so that blank nodes of different sources are kept distinct.
+//!
+//! As with node map generation, merging nodes with different `@index` values
+//! fails with a [`ConflictingIndexes`](crate::ErrorCode::ConflictingIndexes) error.
+//!
+//! Merged nodes keep the [location](crate::Indexed::location) of their first occurrence:
+//! ```
+//! use json_ld::{JsonContext, NoLoader, expansion, location, merge};
+//! # use iref::IriBuf;
+//! # use futures::executor::block_on;
+//! let expand = |input: &str| {
+//!     let (doc, source_map) = location::parse(input).unwrap();
+//!     let context = JsonContext::<IriBuf>::new(None);
+//!     block_on(expansion::expand_located(&context, &doc, &source_map, None, &mut NoLoader, expansion::Options::default())).unwrap()
+//! };
+//!
+//! let a = expand(r#"{ "@id": "_:a", "http://example.org/p": "a" }"#);
+//! let b = expand(r#"[ { "@id": "http://example.org/b", "http://example.org/p": "b" } ]"#);
+//! let merged = merge::merge(vec![a, b], merge::Options::default()).unwrap();
+//!
+//! let pointers: Vec<_> = merged.iter().map(|object| object.location().unwrap().pointer()).collect();
+//! assert_eq!(pointers, vec!["", "/0"]);
+//! ```
+
+use std::collections::HashMap;
+use indexmap::IndexSet;
+use crate::{
+	Id,
+	BlankId,
+	BlankIdGenerator,
+	Sequential,
+	Reference,
+	Lenient,
+	Indexed,
+	Object,
+	Node,
+	ExpandedDocument,
+	Error,
+	ErrorCode
+};
+
+/// Blank node relabelling strategy.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum Relabelling {
+	/// Blank node `_:name` of the `i`-th source becomes `_:s{i}-name`.
+	#[default]
+	Prefix,
+
+	/// Blank nodes are relabelled using the merger's [`BlankIdGenerator`]:
+	/// `_:bN` by default, `N` being incremented for each new blank node across all sources.
+	Sequential,
+
+	/// Blank node identifiers are kept as is.
+	///
+	/// Blank nodes with the same identifier in different sources are then merged.
+	Keep
+}
+
+/// Merge options.
+#[derive(Clone, Copy, Default)]
+pub struct Options {
+	/// Blank node relabelling strategy.
+	pub relabelling: Relabelling
+}
+
+/// Documents merger.
+///
+/// # Example
+/// ```
+/// use json_ld::{Reference, Node, Object, ExpandedDocument, merge::Merger};
+/// # use iref::IriBuf;
+/// let iri = |s: &str| Reference::Id(IriBuf::new(s).unwrap());
+/// let doc = |ty: &str| -> ExpandedDocument<IriBuf> {
+///     let mut node = Node::with_id(iri("http://example.org/alice").into());
+///     node.types_mut().push(iri(ty).into());
+///     vec![Object::Node(node).into()].into_iter().collect()
+/// };
+///
+/// let mut merger = Merger::new(Default::default());
+/// merger.add(doc("http://schema.org/Person")).unwrap();
+/// merger.add(doc("http://xmlns.com/foaf/0.1/Person")).unwrap();
+/// let merged = merger.finish();
+///
+/// assert_eq!(merged.len(), 1);
+/// ```
+///
+/// Embedded nodes are merged with the top-level nodes having the same identifier:
+/// ```
+/// use json_ld::{Document, JsonContext, NoLoader, merge, util::AsJson};
+/// # use iref::IriBuf;
+/// # use futures::executor::block_on;
+/// let a = json::parse(r#"{
+///     "@id": "http://example.org/alice",
+///     "http://xmlns.com/foaf/0.1/knows": {
+///         "@id": "http://example.org/bob",
+///         "http://xmlns.com/foaf/0.1/name": "Bob"
+///     }
+/// }"#).unwrap();
+/// let b = json::parse(r#"{
+///     "@id": "http://example.org/bob",
+///     "http://xmlns.com/foaf/0.1/age": 42
+/// }"#).unwrap();
+///
+/// let a = block_on(a.expand::<JsonContext<IriBuf>, _>(&mut NoLoader)).unwrap();
+/// let b = block_on(b.expand::<JsonContext<IriBuf>, _>(&mut NoLoader)).unwrap();
+/// let merged = merge::merge(vec![a, b], Default::default()).unwrap();
+///
+/// let expected = json::parse(r#"[
+///     {
+///         "@id": "http://example.org/alice",
+///         "http://xmlns.com/foaf/0.1/knows": [ { "@id": "http://example.org/bob" } ]
+///     },
+///     {
+///         "@id": "http://example.org/bob",
+///         "http://xmlns.com/foaf/0.1/name": [ { "@value": "Bob" } ],
+///         "http://xmlns.com/foaf/0.1/age": [ { "@value": 42 } ]
+///     }
+/// ]"#).unwrap();
+/// assert_eq!(merged.as_json(), expected);
+/// ```
+pub struct Merger<T: Id, G: BlankIdGenerator = Sequential> {
+	options: Options,
+
+	/// Blank node identifier generator for the `Sequential` strategy.
+	generator: G,
+
+	/// Number of merged sources.
+	sources: usize,
+
+	/// Blank node labels for the `Sequential` strategy.
+	labels: HashMap<(usize, BlankId), BlankId>,
+
+	/// Merged top-level objects.
+	graph: Graph<T>
+}
+
+impl<T: Id> Merger<T> {
+	/// Creates a new merger.
+	pub fn new(options: Options) -> Merger<T> {
+		Self::with_generator(options, Sequential::new())
+	}
+}
+
+impl<T: Id, G: BlankIdGenerator> Merger<T, G> {
+	/// Creates a new merger using the given blank node identifier generator
+	/// with the `Sequential` relabelling strategy.
+	pub fn with_generator(options: Options, generator: G) -> Merger<T, G> {
+		Merger {
+			options,
+			generator,
+			sources: 0,
+			labels: HashMap::new(),
+			graph: Graph::new()
+		}
+	}
+
+	/// Relabels the blank nodes of the next source.
+	fn relabel(&mut self, doc: ExpandedDocument<T>) -> Vec<Indexed<Object<T>>> {
+		let source = self.sources;
+		self.sources += 1;
+
+		let relabelling = self.options.relabelling;
+		let labels = &mut self.labels;
+		let generator = &mut self.generator;
+		let mut f = |r: Reference<T>| match r {
+			Reference::Blank(id) => match relabelling {
+				Relabelling::Prefix => Reference::Blank(BlankId::new(&format!("s{}-{}", source, id.name()))),
+				Relabelling::Sequential => Reference::Blank(labels.entry((source, id)).or_insert_with(|| generator.next_blank_id()).clone()),
+				Relabelling::Keep => Reference::Blank(id)
+			},
+			r => r
+		};
+
+		doc.into_iter().map(|object| {
+			object.map_inner(|object| object.map_references(&mut f))
+		}).collect()
+	}
+
+	/// Adds a document to merge into the default graph.
+	///
+	/// Returns a `ConflictingIndexes` error if a node of the document is
+	/// merged with a node having a different `@index`,
+	/// in which case the document may have been partially merged.
+	///
+	/// # Example
+	/// ```
+	/// use json_ld::{Reference, Node, Object, Indexed, ExpandedDocument, ErrorCode, merge::Merger};
+	/// # use iref::IriBuf;
+	/// let doc = |index: Option<&str>| -> ExpandedDocument<IriBuf> {
+	///     let node = Node::with_id(Reference::Id(IriBuf::new("http://example.org/alice").unwrap()).into());
+	///     vec![Indexed::new(Object::Node(node), index.map(String::from))].into_iter().collect()
+	/// };
+	///
+	/// let mut merger = Merger::new(Default::default());
+	/// merger.add(doc(Some("a"))).unwrap();
+	/// merger.add(doc(None)).unwrap();
+	/// merger.add(doc(Some("a"))).unwrap();
+	/// assert_eq!(merger.add(doc(Some("b"))).err().unwrap().code(), ErrorCode::ConflictingIndexes);
+	/// assert_eq!(merger.finish().iter().next().unwrap().index(), Some("a"));
+	/// ```
+	pub fn add(&mut self, doc: ExpandedDocument<T>) -> Result<(), Error> {
+		let objects = self.relabel(doc);
+		self.graph.extend(objects)
+	}
+
+	/// Adds a document to merge into the given named graph.
+	///
+	/// This can be used to record the provenance of each source.
+	/// Named graphs with the same name are merged.
+	pub fn add_with_graph(&mut self, doc: ExpandedDocument<T>, graph: Reference<T>) -> Result<(), Error> {
+		let objects = self.relabel(doc);
+		let mut node = Node::with_id(Lenient::Ok(graph));
+		node.graph = Some(objects.into_iter().collect());
+		self.graph.insert(Object::Node(node).into())
+	}
+
+	/// Returns the merged document.
+	pub fn finish(self) -> ExpandedDocument<T> {
+		self.graph.into_objects().into_iter().collect()
+	}
+}
+
+/// Merges the given documents into the default graph.
+pub fn merge<T: Id, D: IntoIterator<Item=ExpandedDocument<T>>>(docs: D, options: Options) -> Result<ExpandedDocument<T>, Error> {
+	let mut merger = Merger::new(options);
+	for doc in docs {
+		merger.add(doc)?
+	}
+
+	Ok(merger.finish())
+}
+
+/// Merges the given documents, putting each of them in the associated named graph.
+pub fn merge_with_provenance<T: Id, D: IntoIterator<Item=(ExpandedDocument<T>, Reference<T>)>>(docs: D, options: Options) -> Result<ExpandedDocument<T>, Error> {
+	let mut merger = Merger::new(options);
+	for (doc, graph) in docs {
+		merger.add_with_graph(doc, graph)?
+	}
+
+	Ok(merger.finish())
+}
+
+/// Graph under construction, with its top-level nodes indexed by identifier.
+struct Graph<T: Id> {
+	nodes: HashMap<Reference<T>, Indexed<Node<T>>>,
+
+	/// Node identifiers, in insertion order.
+	order: Vec<Reference<T>>,
+
+	/// Objects that are not identified nodes.
+	others: Vec<Indexed<Object<T>>>
+}
+
+impl<T: Id> Graph<T> {
+	fn new() -> Graph<T> {
+		Graph {
+			nodes: HashMap::new(),
+			order: Vec::new(),
+			others: Vec::new()
+		}
+	}
+
+	fn extend<O: IntoIterator<Item=Indexed<Object<T>>>>(&mut self, objects: O) -> Result<(), Error> {
+		for object in objects {
+			self.insert(object)?
+		}
+
+		Ok(())
+	}
+
+	fn insert(&mut self, mut object: Indexed<Object<T>>) -> Result<(), Error> {
+		let mut embedded = Vec::new();
+		if let Object::Node(node) = object.as_mut() {
+			flatten_node(node, &mut embedded)?
+		}
+
+		let id = match object.inner() {
+			Object::Node(node) => match &node.id {
+				Some(Lenient::Ok(id)) => Some(id.clone()),
+				_ => None
+			},
+			_ => None
+		};
+
+		match id {
+			Some(id) => {
+				let node = object.map_inner(|object| match object {
+					Object::Node(node) => node,
+					_ => unreachable!()
+				});
+
+				match self.nodes.get_mut(&id) {
+					Some(current) => {
+						if let Some(index) = node.index() {
+							match current.index() {
+								Some(current_index) if current_index != index => return Err(ErrorCode::ConflictingIndexes.into()),
+								Some(_) => (),
+								None => current.set_index(Some(index.to_string()))
+							}
+						}
+
+						merge_nodes(current, node.into_inner())?
+					},
+					None => {
+						self.order.push(id.clone());
+						self.nodes.insert(id, node);
+					}
+				}
+			},
+			None => self.others.push(object)
+		}
+
+		self.extend(embedded)
+	}
+
+	fn into_objects(mut self) -> Vec<Indexed<Object<T>>> {
+		let mut objects = Vec::new();
+		for id in self.order {
+			objects.push(self.nodes.remove(&id).unwrap().cast())
+		}
+
+		objects.extend(self.others);
+		objects
+	}
+}
+
+/// Moves the identified nodes embedded in the given node to `embedded`, replacing them by references,
+/// and merges the nodes of its graph, if any.
+fn flatten_node<T: Id>(node: &mut Node<T>, embedded: &mut Vec<Indexed<Object<T>>>) -> Result<(), Error> {
+	flatten_properties(node, embedded);
+
+	if let Some(included) = node.included.take() {
+		let mut kept = IndexSet::new();
+		for mut node in included {
+			flatten_embedded_node(&mut node, embedded);
+			if !is_reference(&node) {
+				kept.insert(node);
+			}
+		}
+
+		if !kept.is_empty() {
+			node.included = Some(kept)
+		}
+	}
+
+	if let Some(graph) = node.graph.take() {
+		let mut merged = Graph::new();
+		merged.extend(graph)?;
+		node.graph = Some(merged.into_objects().into_iter().collect())
+	}
+
+	Ok(())
+}
+
+fn flatten_object<T: Id>(object: &mut Indexed<Object<T>>, embedded: &mut Vec<Indexed<Object<T>>>) {
+	match object.as_mut() {
+		Object::Node(node) => {
+			match reference_to(node) {
+				Some(reference) => embedded.push(std::mem::replace(object, Indexed::new(Object::Node(reference), None))),
+				None => flatten_properties(node, embedded)
+			}
+		},
+		Object::List(items) => {
+			for item in items {
+				flatten_object(item, embedded)
+			}
+		},
+		Object::Value(_) => ()
+	}
+}
+
+fn flatten_embedded_node<T: Id>(node: &mut Indexed<Node<T>>, embedded: &mut Vec<Indexed<Object<T>>>) {
+	match reference_to(node) {
+		Some(reference) => embedded.push(std::mem::replace(node, Indexed::new(reference, None)).cast()),
+		None => flatten_properties(node, embedded)
+	}
+}
+
+/// Moves the identified nodes embedded in the properties of the given node to `embedded`.
+fn flatten_properties<T: Id>(node: &mut Node<T>, embedded: &mut Vec<Indexed<Object<T>>>) {
+	for objects in node.properties.values_mut() {
+		for object in objects {
+			flatten_object(object, embedded)
+		}
+	}
+
+	for nodes in node.reverse_properties.values_mut() {
+		for node in nodes {
+			flatten_embedded_node(node, embedded)
+		}
+	}
+}
+
+/// Returns a reference to the given node, if it is identified and not already a reference.
+fn reference_to<T: Id>(node: &Node<T>) -> Option<Node<T>> {
+	match &node.id {
+		Some(Lenient::Ok(id)) if !node.is_empty() => Some(Node::with_id(Lenient::Ok(id.clone()))),
+		_ => None
+	}
+}
+
+/// Checks if the given node is only a reference to another node.
+fn is_reference<T: Id>(node: &Node<T>) -> bool {
+	node.id.is_some() && node.is_empty()
+}
+
+/// Appends the given values, skipping duplicates.
+fn extend_dedup<V: PartialEq>(values: &mut Vec<V>, new_values: Vec<V>) {
+	for value in new_values {
+		if !values.contains(&value) {
+			values.push(value)
+		}
+	}
+}
+
+/// Merges `node` into `target`.
+fn merge_nodes<T: Id>(target: &mut Node<T>, node: Node<T>) -> Result<(), Error> {
+	extend_dedup(&mut target.types, node.types);
+
+	for (prop, objects) in node.properties {
+		extend_dedup(target.properties.entry(prop).or_default(), objects)
+	}
+
+	for (prop, nodes) in node.reverse_properties {
+		extend_dedup(target.reverse_properties.entry(prop).or_default(), nodes)
+	}
+
+	if let Some(included) = node.included {
+		target.included.get_or_insert_with(IndexSet::new).extend(included)
+	}
+
+	if let Some(graph) = node.graph {
+		let mut merged = Graph::new();
+		if let Some(target_graph) = target.graph.take() {
+			merged.extend(target_graph)?
+		}
+		merged.extend(graph)?;
+		target.graph = Some(merged.into_objects().into_iter().collect())
+	}
+
+	Ok(())
+}
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use crate::{
+	Id,
+	Reference,
+	Lenient,
+	Indexed,
+	Object,
+	Node,
+	ExpandedDocument
+};
+
+/// Borrowed index of the nodes of an expanded document.
+///
+/// Every node object found in the document is indexed,
+/// including nested nodes (property values, list items, reverse properties,
+/// `@graph` and `@included` entries).
+/// Since nodes are borrowed and not merged, the same identifier may be associated to
+/// multiple node objects.
+/// Nodes without identifier are said to be anonymous.
+pub struct NodeMap<'a, T: Id> {
+	/// Identified nodes.
+	nodes: HashMap<&'a Reference<T>, Vec<&'a Node<T>>>,
+
+	/// Anonymous nodes.
+	anonymous: Vec<&'a Node<T>>
+}
+
+impl<'a, T: Id> NodeMap<'a, T> {
+	/// Creates an empty node map.
+	pub fn new() -> NodeMap<'a, T> {
+		NodeMap {
+			nodes: HashMap::new(),
+			anonymous: Vec::new()
+		}
+	}
+
+	/// Index every node of the given expanded document.
+	pub fn from_document(doc: &'a ExpandedDocument<T>) -> NodeMap<'a, T> {
+		let mut map = NodeMap::new();
+		map.insert_objects(doc.iter());
+		map
+	}
+
+	/// Index the given objects and the nodes they contain.
+	pub fn insert_objects<O: IntoIterator<Item=&'a Indexed<Object<T>>>>(&mut self, objects: O) {
+		for object in objects {
+			self.insert_object(object.inner())
+		}
+	}
+
+	/// Index the given object and the nodes it contains.
+	pub fn insert_object(&mut self, object: &'a Object<T>) {
+		match object {
+			Object::Value(_) => (),
+			Object::Node(node) => self.insert(node),
+			Object::List(items) => self.insert_objects(items.iter())
+		}
+	}
+
+	/// Index the given node and the nodes it contains.
+	pub fn insert(&mut self, node: &'a Node<T>) {
+		match &node.id {
+			Some(Lenient::Ok(id)) => self.nodes.entry(id).or_default().push(node),
+			_ => self.anonymous.push(node)
+		}
+
+		for objects in node.properties.values() {
+			self.insert_objects(objects.iter())
+		}
+
+		for nodes in node.reverse_properties.values() {
+			for reverse_node in nodes {
+				self.insert(reverse_node.inner())
+			}
+		}
+
+		if let Some(graph) = &node.graph {
+			self.insert_objects(graph.iter())
+		}
+
+		if let Some(included) = &node.included {
+			for included_node in included {
+				self.insert(included_node.inner())
+			}
+		}
+	}
+
+	/// Returns the node objects with the given identifier.
+	pub fn get(&self, id: &Reference<T>) -> &[&'a Node<T>] {
+		match self.nodes.get(id) {
+			Some(nodes) => nodes.as_slice(),
+			None => &[]
+		}
+	}
+
+	/// Checks if some node is identified by the given reference.
+	pub fn contains(&self, id: &Reference<T>) -> bool {
+		self.nodes.contains_key(id)
+	}
+
+	/// Returns an iterator over the node identifiers.
+	pub fn ids(&self) -> impl Iterator<Item=&'a Reference<T>> + '_ {
+		self.nodes.keys().copied()
+	}
+
+	/// Returns the anonymous nodes.
+	pub fn anonymous(&self) -> &[&'a Node<T>] {
+		&self.anonymous
+	}
+
+	/// Returns an iterator over all the indexed nodes, identified or not.
+	pub fn nodes(&self) -> impl Iterator<Item=&'a Node<T>> + '_ {
+		self.nodes.values().flatten().chain(self.anonymous.iter()).copied()
+	}
+}
+
+impl<'a, T: Id> Default for NodeMap<'a, T> {
+	fn default() -> NodeMap<'a, T> {
+		NodeMap::new()
+	}
+}
+
+impl<'a, T: Id> From<&'a ExpandedDocument<T>> for NodeMap<'a, T> {
+	fn from(doc: &'a ExpandedDocument<T>) -> NodeMap<'a, T> {
+		NodeMap::from_document(doc)
+	}
+}
@@ -0,0 +1,654 @@
+//! Typed extraction of values.
+//!
+//! Value objects hold JSON literals associated to an optional `@type`.
+//! The [`FromValue`] trait defines how to convert such literal into a Rust value,
+//! according to its type.
+
+use std::fmt;
+use std::convert::TryFrom;
+use iref::IriBuf;
+use json::JsonValue;
+use crate::{
+	Id,
+	LangString,
+	xsd::{
+		self,
+		Datatype
+	}
+};
+use super::{
+	Literal,
+	Value
+};
+
+/// Kind of value found in a value object.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ValueKind {
+	/// The `null` value.
+	Null,
+
+	/// A boolean.
+	Boolean,
+
+	/// A JSON number.
+	Number,
+
+	/// A string.
+	String,
+
+	/// A language tagged string.
+	LangString,
+
+	/// A JSON literal.
+	Json
+}
+
+impl ValueKind {
+	/// Returns the kind of the given value.
+	pub fn of<T: Id>(value: &Value<T>) -> ValueKind {
+		match value {
+			Value::Literal(lit, _) => Self::of_literal(lit),
+			Value::LangString(_) => ValueKind::LangString,
+			Value::Json(_) => ValueKind::Json
+		}
+	}
+
+	/// Returns the kind of the given literal.
+	pub fn of_literal(lit: &Literal) -> ValueKind {
+		match lit {
+			Literal::Null => ValueKind::Null,
+			Literal::Boolean(_) => ValueKind::Boolean,
+			Literal::Number(_) => ValueKind::Number,
+			Literal::String(_) => ValueKind::String
+		}
+	}
+
+	/// Returns a textual description of the kind.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			ValueKind::Null => "null",
+			ValueKind::Boolean => "boolean",
+			ValueKind::Number => "number",
+			ValueKind::String => "string",
+			ValueKind::LangString => "language tagged string",
+			ValueKind::Json => "JSON literal"
+		}
+	}
+}
+
+impl fmt::Display for ValueKind {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.as_str().fmt(f)
+	}
+}
+
+/// Value extraction error.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ExtractError {
+	/// The object is not a value object.
+	NotAValue,
+
+	/// The value is not of the expected kind.
+	UnexpectedValue {
+		/// Expected kind of value.
+		expected: ValueKind,
+
+		/// Actual kind of value.
+		found: ValueKind
+	},
+
+	/// The value `@type` is not compatible with the expected datatype.
+	UnexpectedDatatype {
+		/// Expected datatype.
+		expected: Datatype,
+
+		/// Actual `@type` IRI, if any.
+		found: Option<String>
+	},
+
+	/// The value is not a valid lexical form of its datatype.
+	InvalidLexicalForm {
+		/// Datatype of the value.
+		datatype: Datatype,
+
+		/// Invalid lexical form.
+		lexical: String
+	},
+
+	/// The value is valid, but cannot be represented by the target type.
+	OutOfRange {
+		/// Datatype of the value.
+		datatype: Datatype,
+
+		/// Lexical representation of the value.
+		lexical: String
+	},
+
+	/// The value is valid but has no timezone, which is required by the target type.
+	MissingTimezone {
+		/// Datatype of the value.
+		datatype: Datatype,
+
+		/// Lexical representation of the value.
+		lexical: String
+	},
+
+	/// The value is valid but has a timezone, which cannot be represented by the target type.
+	UnexpectedTimezone {
+		/// Datatype of the value.
+		datatype: Datatype,
+
+		/// Lexical representation of the value.
+		lexical: String
+	}
+}
+
+impl fmt::Display for ExtractError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			ExtractError::NotAValue => write!(f, "not a value object"),
+			ExtractError::UnexpectedValue { expected, found } => write!(f, "expected {}, found {}", expected, found),
+			ExtractError::UnexpectedDatatype { expected, found: Some(found) } => write!(f, "expected {} value, found <{}>", expected, found),
+			ExtractError::UnexpectedDatatype { expected, found: None } => write!(f, "expected {} value, found untyped value", expected),
+			ExtractError::InvalidLexicalForm { datatype, lexical } => write!(f, "invalid {} lexical form `{}`", datatype, lexical),
+			ExtractError::OutOfRange { datatype, lexical } => write!(f, "{} value `{}` is out of range", datatype, lexical),
+			ExtractError::MissingTimezone { datatype, lexical } => write!(f, "{} value `{}` has no timezone", datatype, lexical),
+			ExtractError::UnexpectedTimezone { datatype, lexical } => write!(f, "{} value `{}` has an unexpected timezone", datatype, lexical)
+		}
+	}
+}
+
+impl std::error::Error for ExtractError {}
+
+/// Types that can be extracted from a value object.
+///
+/// The conversion is driven by the `@type` of the value object.
+/// For instance, a value typed `xsd:integer` can be extracted as an `i64`,
+/// either from a JSON number or from a string holding a valid `xsd:integer` lexical form.
+///
+/// # Example
+/// ```
+/// use json_ld::{Value, object::Literal};
+/// # use iref::IriBuf;
+///
+/// let xsd_integer = IriBuf::new("http://www.w3.org/2001/XMLSchema#integer").unwrap();
+/// let value: Value = Value::Literal(Literal::String("42".to_string()), Some(xsd_integer));
+/// assert_eq!(value.extract::<i64>(), Ok(42));
+/// ```
+pub trait FromValue<T: Id>: Sized {
+	/// Extract from a literal value with the given type.
+	fn from_literal(literal: &Literal, ty: Option<&T>) -> Result<Self, ExtractError>;
+
+	/// Extract from a value object.
+	fn from_value(value: &Value<T>) -> Result<Self, ExtractError> {
+		match value {
+			Value::Literal(lit, ty) => Self::from_literal(lit, ty.as_ref()),
+			Value::LangString(_) => Err(ExtractError::UnexpectedValue { expected: ValueKind::String, found: ValueKind::LangString }),
+			Value::Json(_) => Err(ExtractError::UnexpectedValue { expected: ValueKind::String, found: ValueKind::Json })
+		}
+	}
+}
+
+/// Finds the datatype of the given `@type`.
+///
+/// Returns `Ok(None)` if there is no `@type`, and an `UnexpectedDatatype` error if the
+/// datatype is unknown, or if `accept` returns `false`.
+fn datatype<T: Id, F: Fn(Datatype) -> bool>(expected: Datatype, ty: Option<&T>, accept: F) -> Result<Option<Datatype>, ExtractError> {
+	match ty {
+		Some(ty) => match Datatype::from_iri(ty.as_iri()) {
+			Some(dt) if accept(dt) => Ok(Some(dt)),
+			_ => Err(ExtractError::UnexpectedDatatype {
+				expected,
+				found: Some(ty.as_iri().as_str().to_string())
+			})
+		},
+		None => Ok(None)
+	}
+}
+
+fn unexpected(expected: ValueKind, literal: &Literal) -> ExtractError {
+	ExtractError::UnexpectedValue {
+		expected,
+		found: ValueKind::of_literal(literal)
+	}
+}
+
+/// Converts a JSON number into an integer, if it is integral and fits in a `i128`.
+fn number_to_i128(n: &json::number::Number) -> Option<i128> {
+	let (positive, mantissa, exponent) = n.as_parts();
+	let mut value = mantissa as i128;
+
+	if exponent >= 0 {
+		for _ in 0..exponent {
+			value = value.checked_mul(10)?
+		}
+	} else {
+		for _ in 0..(-exponent) {
+			if value % 10 != 0 {
+				return None
+			}
+
+			value /= 10
+		}
+	}
+
+	Some(if positive { value } else { -value })
+}
+
+impl<T: Id> FromValue<T> for bool {
+	fn from_literal(literal: &Literal, ty: Option<&T>) -> Result<bool, ExtractError> {
+		let dt = datatype(Datatype::Boolean, ty, |dt| dt == Datatype::Boolean)?;
+		match literal {
+			Literal::Boolean(b) => Ok(*b),
+			Literal::String(s) if dt.is_some() => match s.as_str() {
+				"true" | "1" => Ok(true),
+				"false" | "0" => Ok(false),
+				_ => Err(ExtractError::InvalidLexicalForm {
+					datatype: Datatype::Boolean,
+					lexical: s.clone()
+				})
+			},
+			_ => Err(unexpected(ValueKind::Boolean, literal))
+		}
+	}
+}
+
+/// Extract an integer as a `i128` along with its datatype, keeping the lexical form around
+/// for error reporting.
+fn extract_integer<T: Id>(literal: &Literal, ty: Option<&T>) -> Result<(Datatype, i128, String), ExtractError> {
+	let dt = datatype(Datatype::Integer, ty, |dt| dt.is_decimal())?;
+	match literal {
+		Literal::Number(n) => {
+			let datatype = dt.unwrap_or(Datatype::Integer);
+			match number_to_i128(n) {
+				Some(i) => Ok((datatype, i, n.to_string())),
+				None => {
+					let f = f64::from(*n);
+					if f.is_finite() && f.fract() == 0.0 {
+						Err(ExtractError::OutOfRange { datatype, lexical: n.to_string() })
+					} else {
+						Err(ExtractError::InvalidLexicalForm { datatype, lexical: n.to_string() })
+					}
+				}
+			}
+		},
+		Literal::String(s) => match dt {
+			Some(datatype) if datatype.is_integer() => {
+				if xsd::is_integer(s) {
+					match s.parse::<i128>() {
+						Ok(i) => Ok((datatype, i, s.clone())),
+						Err(_) => Err(ExtractError::OutOfRange { datatype, lexical: s.clone() })
+					}
+				} else {
+					Err(ExtractError::InvalidLexicalForm { datatype, lexical: s.clone() })
+				}
+			},
+			Some(datatype) => Err(ExtractError::UnexpectedDatatype {
+				expected: Datatype::Integer,
+				found: Some(datatype.as_str().to_string())
+			}),
+			None => Err(unexpected(ValueKind::Number, literal))
+		},
+		_ => Err(unexpected(ValueKind::Number, literal))
+	}
+}
+
+macro_rules! integer_from_value {
+	($($ty:ty),*) => {
+		$(
+			impl<T: Id> FromValue<T> for $ty {
+				fn from_literal(literal: &Literal, ty: Option<&T>) -> Result<$ty, ExtractError> {
+					let (datatype, i, lexical) = extract_integer(literal, ty)?;
+					<$ty>::try_from(i).map_err(|_| ExtractError::OutOfRange { datatype, lexical })
+				}
+			}
+		)*
+	};
+}
+
+integer_from_value!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, isize, usize);
+
+impl<T: Id> FromValue<T> for f64 {
+	fn from_literal(literal: &Literal, ty: Option<&T>) -> Result<f64, ExtractError> {
+		let dt = datatype(Datatype::Double, ty, |dt| dt.is_numeric())?;
+		match literal {
+			Literal::Number(n) => Ok(f64::from(*n)),
+			Literal::String(s) => match dt {
+				Some(datatype) => {
+					let value = if datatype.is_decimal() {
+						xsd::Decimal::parse(s).map(|d| d.to_f64())
+					} else {
+						xsd::parse_double(s)
+					};
+
+					value.ok_or_else(|| ExtractError::InvalidLexicalForm { datatype, lexical: s.clone() })
+				},
+				None => Err(unexpected(ValueKind::Number, literal))
+			},
+			_ => Err(unexpected(ValueKind::Number, literal))
+		}
+	}
+}
+
+/// Values are rounded to the nearest `f32`.
+///
+/// Finite values whose magnitude is too large or too small to be represented
+/// are rejected with an `OutOfRange` error.
+///
+/// # Example
+/// ```
+/// use json_ld::{Value, object::{Literal, ExtractError}, xsd::Datatype};
+/// # use iref::IriBuf;
+/// let xsd_double = IriBuf::new("http://www.w3.org/2001/XMLSchema#double").unwrap();
+/// let double = |lexical: &str| -> Value { Value::Literal(Literal::String(lexical.to_string()), Some(xsd_double.clone())) };
+///
+/// assert_eq!(double("0.1").extract::<f32>(), Ok(0.1));
+/// assert_eq!(double("INF").extract::<f32>(), Ok(f32::INFINITY));
+/// assert_eq!(double("1e39").extract::<f32>(), Err(ExtractError::OutOfRange { datatype: Datatype::Double, lexical: "1e39".to_string() }));
+/// assert_eq!(double("1e-50").extract::<f32>(), Err(ExtractError::OutOfRange { datatype: Datatype::Double, lexical: "1e-50".to_string() }));
+/// ```
+impl<T: Id> FromValue<T> for f32 {
+	fn from_literal(literal: &Literal, ty: Option<&T>) -> Result<f32, ExtractError> {
+		let value: f64 = FromValue::<T>::from_literal(literal, ty)?;
+		let result = value as f32;
+		if (value.is_finite() && result.is_infinite()) || (value != 0.0 && result == 0.0) {
+			let datatype = datatype(Datatype::Double, ty, |dt| dt.is_numeric())?.unwrap_or(Datatype::Double);
+			let lexical = match literal {
+				Literal::Number(n) => n.to_string(),
+				_ => literal.as_str().unwrap_or_default().to_string()
+			};
+
+			return Err(ExtractError::OutOfRange { datatype, lexical })
+		}
+
+		Ok(result)
+	}
+}
+
+impl<T: Id> FromValue<T> for xsd::Decimal {
+	fn from_literal(literal: &Literal, ty: Option<&T>) -> Result<xsd::Decimal, ExtractError> {
+		let dt = datatype(Datatype::Decimal, ty, |dt| dt.is_decimal())?;
+		let datatype = dt.unwrap_or(Datatype::Decimal);
+		match literal {
+			Literal::Number(n) => {
+				let (positive, mantissa, exponent) = n.as_parts();
+				let mut lexical = String::new();
+				if !positive {
+					lexical.push('-');
+				}
+
+				let digits = mantissa.to_string();
+				if exponent >= 0 {
+					lexical.push_str(&digits);
+					for _ in 0..exponent {
+						lexical.push('0')
+					}
+				} else {
+					let point = digits.len() as i64 + exponent as i64;
+					if point > 0 {
+						lexical.push_str(&digits[..(point as usize)]);
+						lexical.push('.');
+						lexical.push_str(&digits[(point as usize)..]);
+					} else {
+						lexical.push_str("0.");
+						for _ in 0..(-point) {
+							lexical.push('0')
+						}
+						lexical.push_str(&digits);
+					}
+				}
+
+				xsd::Decimal::parse(&lexical).ok_or(ExtractError::OutOfRange { datatype, lexical: n.to_string() })
+			},
+			Literal::String(s) if dt.is_some() => {
+				xsd::Decimal::parse(s).ok_or_else(|| ExtractError::InvalidLexicalForm { datatype, lexical: s.clone() })
+			},
+			_ => Err(unexpected(ValueKind::Number, literal))
+		}
+	}
+}
+
+impl<T: Id> FromValue<T> for String {
+	fn from_literal(literal: &Literal, ty: Option<&T>) -> Result<String, ExtractError> {
+		datatype(Datatype::String, ty, |dt| dt == Datatype::String)?;
+		match literal {
+			Literal::String(s) => Ok(s.clone()),
+			_ => Err(unexpected(ValueKind::String, literal))
+		}
+	}
+}
+
+impl<T: Id> FromValue<T> for IriBuf {
+	fn from_literal(literal: &Literal, ty: Option<&T>) -> Result<IriBuf, ExtractError> {
+		match datatype(Datatype::AnyUri, ty, |dt| dt == Datatype::AnyUri)? {
+			Some(datatype) => match literal {
+				Literal::String(s) => IriBuf::new(s).map_err(|_| ExtractError::InvalidLexicalForm { datatype, lexical: s.clone() }),
+				_ => Err(unexpected(ValueKind::String, literal))
+			},
+			None => Err(ExtractError::UnexpectedDatatype {
+				expected: Datatype::AnyUri,
+				found: None
+			})
+		}
+	}
+}
+
+impl<T: Id> FromValue<T> for LangString {
+	fn from_literal(literal: &Literal, _ty: Option<&T>) -> Result<LangString, ExtractError> {
+		Err(unexpected(ValueKind::LangString, literal))
+	}
+
+	fn from_value(value: &Value<T>) -> Result<LangString, ExtractError> {
+		match value {
+			Value::LangString(s) => Ok(s.clone()),
+			Value::Literal(lit, _) => Err(unexpected(ValueKind::LangString, lit)),
+			Value::Json(_) => Err(ExtractError::UnexpectedValue { expected: ValueKind::LangString, found: ValueKind::Json })
+		}
+	}
+}
+
+/// JSON literals, typed `@json` (`rdf:JSON`).
+impl<T: Id> FromValue<T> for JsonValue {
+	fn from_literal(literal: &Literal, _ty: Option<&T>) -> Result<JsonValue, ExtractError> {
+		Err(unexpected(ValueKind::Json, literal))
+	}
+
+	fn from_value(value: &Value<T>) -> Result<JsonValue, ExtractError> {
+		match value {
+			Value::Json(json) => Ok(json.clone()),
+			Value::Literal(lit, _) => Err(unexpected(ValueKind::Json, lit)),
+			Value::LangString(_) => Err(ExtractError::UnexpectedValue { expected: ValueKind::Json, found: ValueKind::LangString })
+		}
+	}
+}
+
+#[cfg(feature="num-bigint")]
+impl<T: Id> FromValue<T> for num_bigint::BigInt {
+	fn from_literal(literal: &Literal, ty: Option<&T>) -> Result<num_bigint::BigInt, ExtractError> {
+		let dt = datatype(Datatype::Integer, ty, |dt| dt.is_decimal())?;
+		match literal {
+			Literal::String(s) => match dt {
+				Some(datatype) if datatype.is_integer() => {
+					if xsd::is_integer(s) {
+						Ok(s.trim_start_matches('+').parse().unwrap())
+					} else {
+						Err(ExtractError::InvalidLexicalForm { datatype, lexical: s.clone() })
+					}
+				},
+				_ => extract_integer(literal, ty).map(|(_, i, _)| i.into())
+			},
+			_ => extract_integer(literal, ty).map(|(_, i, _)| i.into())
+		}
+	}
+}
+
+#[cfg(feature="chrono")]
+mod date_time {
+	use chrono::{
+		DateTime,
+		FixedOffset,
+		NaiveDate,
+		NaiveDateTime,
+		NaiveTime,
+		TimeZone,
+		Utc
+	};
+	use crate::Id;
+	use super::*;
+
+	fn lexical<'l, T: Id>(datatype: Datatype, literal: &'l Literal, ty: Option<&T>) -> Result<&'l str, ExtractError> {
+		match super::datatype(datatype, ty, |dt| dt == datatype)? {
+			Some(_) => match literal {
+				Literal::String(s) => Ok(s.as_str()),
+				_ => Err(unexpected(ValueKind::String, literal))
+			},
+			None => Err(ExtractError::UnexpectedDatatype {
+				expected: datatype,
+				found: None
+			})
+		}
+	}
+
+	/// Parses a `xsd:dateTime` or `xsd:date` value.
+	fn parse<'l, T: Id>(datatype: Datatype, literal: &'l Literal, ty: Option<&T>) -> Result<(xsd::DateTime, &'l str), ExtractError> {
+		let s = lexical(datatype, literal, ty)?;
+		let valid = match datatype {
+			Datatype::Date => xsd::is_date(s),
+			_ => xsd::is_date_time(s)
+		};
+
+		if !valid {
+			return Err(ExtractError::InvalidLexicalForm { datatype, lexical: s.to_string() })
+		}
+
+		let value = match datatype {
+			Datatype::Date => xsd::DateTime::parse_date(s),
+			_ => xsd::DateTime::parse(s)
+		};
+
+		match value {
+			Some(value) => Ok((value, s)),
+			None => Err(ExtractError::OutOfRange { datatype, lexical: s.to_string() })
+		}
+	}
+
+	/// Converts a parsed value into a date and time, ignoring the timezone.
+	fn naive_date_time(datatype: Datatype, value: &xsd::DateTime, lexical: &str) -> Result<NaiveDateTime, ExtractError> {
+		let out_of_range = || ExtractError::OutOfRange { datatype, lexical: lexical.to_string() };
+		let year = i32::try_from(value.year).map_err(|_| out_of_range())?;
+		let date = NaiveDate::from_ymd_opt(year, value.month, value.day).ok_or_else(out_of_range)?;
+
+		// `24:00:00` is the first instant of the next day.
+		if value.hour == 24 {
+			date.succ_opt().map(|date| date.and_time(NaiveTime::MIN)).ok_or_else(out_of_range)
+		} else {
+			date.and_hms_nano_opt(value.hour, value.minute, value.second, value.nanosecond).ok_or_else(out_of_range)
+		}
+	}
+
+	/// `xsd:dateTime` values with a timezone.
+	///
+	/// Values without timezone cause a [`ExtractError::MissingTimezone`] error.
+	///
+	/// # Example
+	/// ```
+	/// use json_ld::{Value, object::{Literal, ExtractError}, xsd::Datatype};
+	/// use chrono::{DateTime, FixedOffset, TimeZone};
+	/// # use iref::IriBuf;
+	/// let xsd_date_time = IriBuf::new("http://www.w3.org/2001/XMLSchema#dateTime").unwrap();
+	/// let date_time = |lexical: &str| -> Value { Value::Literal(Literal::String(lexical.to_string()), Some(xsd_date_time.clone())) };
+	/// let utc = FixedOffset::east_opt(0).unwrap();
+	///
+	/// assert_eq!(date_time("2020-01-01T24:00:00Z").extract::<DateTime<FixedOffset>>(), Ok(utc.with_ymd_and_hms(2020, 1, 2, 0, 0, 0).unwrap()));
+	/// assert_eq!(date_time("12020-01-01T00:00:00Z").extract::<DateTime<FixedOffset>>(), Ok(utc.with_ymd_and_hms(12020, 1, 1, 0, 0, 0).unwrap()));
+	/// assert!(date_time("2020-01-01 00:00:00Z").extract::<DateTime<FixedOffset>>().is_err());
+	/// assert!(date_time("2020-01-01t00:00:00z").extract::<DateTime<FixedOffset>>().is_err());
+	/// assert_eq!(date_time("2020-01-01T00:00:00").extract::<DateTime<FixedOffset>>(), Err(ExtractError::MissingTimezone {
+	///     datatype: Datatype::DateTime,
+	///     lexical: "2020-01-01T00:00:00".to_string()
+	/// }));
+	/// ```
+	impl<T: Id> FromValue<T> for DateTime<FixedOffset> {
+		fn from_literal(literal: &Literal, ty: Option<&T>) -> Result<DateTime<FixedOffset>, ExtractError> {
+			let (value, s) = parse(Datatype::DateTime, literal, ty)?;
+			let out_of_range = || ExtractError::OutOfRange { datatype: Datatype::DateTime, lexical: s.to_string() };
+			match value.timezone {
+				Some(timezone) => {
+					let date_time = naive_date_time(Datatype::DateTime, &value, s)?;
+					let offset = FixedOffset::east_opt(timezone * 60).ok_or_else(out_of_range)?;
+					offset.from_local_datetime(&date_time).single().ok_or_else(out_of_range)
+				},
+				None => Err(ExtractError::MissingTimezone { datatype: Datatype::DateTime, lexical: s.to_string() })
+			}
+		}
+	}
+
+	/// `xsd:dateTime` values with a timezone.
+	impl<T: Id> FromValue<T> for DateTime<Utc> {
+		fn from_literal(literal: &Literal, ty: Option<&T>) -> Result<DateTime<Utc>, ExtractError> {
+			let date_time: DateTime<FixedOffset> = FromValue::<T>::from_literal(literal, ty)?;
+			Ok(date_time.with_timezone(&Utc))
+		}
+	}
+
+	/// `xsd:dateTime` values without timezone.
+	///
+	/// Values with a timezone cause a [`ExtractError::UnexpectedTimezone`] error.
+	impl<T: Id> FromValue<T> for NaiveDateTime {
+		fn from_literal(literal: &Literal, ty: Option<&T>) -> Result<NaiveDateTime, ExtractError> {
+			let (value, s) = parse(Datatype::DateTime, literal, ty)?;
+			match value.timezone {
+				Some(_) => Err(ExtractError::UnexpectedTimezone { datatype: Datatype::DateTime, lexical: s.to_string() }),
+				None => naive_date_time(Datatype::DateTime, &value, s)
+			}
+		}
+	}
+
+	/// `xsd:date` values without timezone.
+	///
+	/// Values with a timezone cause a [`ExtractError::UnexpectedTimezone`] error.
+	///
+	/// # Example
+	/// ```
+	/// use json_ld::{Value, object::{Literal, ExtractError}, xsd::Datatype};
+	/// use chrono::NaiveDate;
+	/// # use iref::IriBuf;
+	/// let xsd_date = IriBuf::new("http://www.w3.org/2001/XMLSchema#date").unwrap();
+	/// let date = |lexical: &str| -> Value { Value::Literal(Literal::String(lexical.to_string()), Some(xsd_date.clone())) };
+	///
+	/// assert_eq!(date("2020-02-29").extract::<NaiveDate>(), Ok(NaiveDate::from_ymd(2020, 2, 29)));
+	/// assert_eq!(date("-0044-03-15").extract::<NaiveDate>(), Ok(NaiveDate::from_ymd(-44, 3, 15)));
+	/// assert_eq!(date("12345-01-01").extract::<NaiveDate>(), Ok(NaiveDate::from_ymd(12345, 1, 1)));
+	/// assert_eq!(date("2020-02-29Z").extract::<NaiveDate>(), Err(ExtractError::UnexpectedTimezone {
+	///     datatype: Datatype::Date,
+	///     lexical: "2020-02-29Z".to_string()
+	/// }));
+	/// assert!(date("044-03-15").extract::<NaiveDate>().is_err());
+	/// assert!(date("2021-02-29").extract::<NaiveDate>().is_err());
+	/// ```
+	impl<T: Id> FromValue<T> for NaiveDate {
+		fn from_literal(literal: &Literal, ty: Option<&T>) -> Result<NaiveDate, ExtractError> {
+			let (value, s) = parse(Datatype::Date, literal, ty)?;
+			match value.timezone {
+				Some(_) => Err(ExtractError::UnexpectedTimezone { datatype: Datatype::Date, lexical: s.to_string() }),
+				None => naive_date_time(Datatype::Date, &value, s).map(|date_time| date_time.date())
+			}
+		}
+	}
+}
+
+macro_rules! try_from_value {
+	($($ty:ty),*) => {
+		$(
+			impl<'a, T: Id> TryFrom<&'a Value<T>> for $ty {
+				type Error = ExtractError;
+
+				fn try_from(value: &'a Value<T>) -> Result<$ty, ExtractError> {
+					<$ty as FromValue<T>>::from_value(value)
+				}
+			}
+		)*
+	};
+}
+
+try_from_value!(bool, i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, isize, usize, f32, f64, xsd::Decimal, String, IriBuf, LangString, JsonValue);
@@ -2,8 +2,10 @@
 
 pub mod value;
 pub mod node;
+pub mod extract;
+pub mod path;
 
-use std::collections::HashSet;
+use indexmap::IndexSet;
 use std::hash::Hash;
 use std::fmt;
 use iref::{Iri, IriBuf};
@@ -23,6 +25,14 @@ pub use value::{
 	Value
 };
 pub use node::Node;
+pub use extract::{
+	FromValue,
+	ExtractError
+};
+pub use path::{
+	Path,
+	Step
+};
 
 pub trait Any<T: Id>: AsJson {
 	fn as_ref(&self) -> Ref<T>;
@@ -88,6 +98,45 @@ pub enum Ref<'a, T: Id> {
 	List(&'a [Indexed<Object<T>>])
 }
 
+impl<'a, T: Id> Ref<'a, T> {
+	/// Returns the referenced node, if any.
+	pub fn as_node(&self) -> Option<&'a Node<T>> {
+		match self {
+			Ref::Node(node) => Some(node),
+			_ => None
+		}
+	}
+
+	/// Returns the referenced value, if any.
+	pub fn as_value(&self) -> Option<&'a Value<T>> {
+		match self {
+			Ref::Value(value) => Some(value),
+			_ => None
+		}
+	}
+
+	/// Get the object as a string.
+	///
+	/// If the object is a value that is a string, returns this string.
+	/// If the object is a node that is identified, returns the identifier as a string.
+	/// Returns `None` otherwise.
+	pub fn as_str(&self) -> Option<&'a str> {
+		match self {
+			Ref::Value(value) => value.as_str(),
+			Ref::Node(node) => node.as_str(),
+			Ref::List(_) => None
+		}
+	}
+}
+
+impl<'a, T: Id> Clone for Ref<'a, T> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<'a, T: Id> Copy for Ref<'a, T> {}
+
 /// Object.
 ///
 /// JSON-LD connects together multiple kinds of data objects.
@@ -185,6 +234,29 @@ impl<T: Id> Object<T> {
 		}
 	}
 
+	/// Extract the value as a `V`, according to its type.
+	///
+	/// Returns [`ExtractError::NotAValue`] if the object is not a value object.
+	pub fn extract<V: FromValue<T>>(&self) -> Result<V, ExtractError> {
+		match self {
+			Object::Value(value) => value.extract(),
+			_ => Err(ExtractError::NotAValue)
+		}
+	}
+
+	/// Maps every node reference of this object with the given function.
+	///
+	/// See [`Node::map_references`].
+	pub fn map_references<F: FnMut(Reference<T>) -> Reference<T>>(self, f: &mut F) -> Object<T> {
+		match self {
+			Object::Value(value) => Object::Value(value),
+			Object::Node(node) => Object::Node(node.map_references(f)),
+			Object::List(items) => Object::List(items.into_iter().map(|item| {
+				item.map_inner(|item| item.map_references(f))
+			}).collect())
+		}
+	}
+
 	/// If the objat is a language-tagged value,
 	/// Return its associated language.
 	pub fn language(&self) -> Option<LanguageTag> {
@@ -197,17 +269,21 @@ impl<T: Id> Object<T> {
 
 impl <T: Id> Indexed<Object<T>> {
 	/// Try to convert this object into an unnamed graph.
-	pub fn into_unnamed_graph(self: Indexed<Object<T>>) -> Result<HashSet<Self>, Self> {
+	pub fn into_unnamed_graph(self: Indexed<Object<T>>) -> Result<IndexSet<Self>, Self> {
+		let location = self.location().cloned();
 		let (obj, index) = self.into_parts();
-		match obj {
+		let mut obj = match obj {
 			Object::Node(n) => {
 				match n.into_unnamed_graph() {
-					Ok(g) => Ok(g),
-					Err(n) => Err(Indexed::new(Object::Node(n), index))
+					Ok(g) => return Ok(g),
+					Err(n) => Indexed::new(Object::Node(n), index)
 				}
 			},
-			obj => Err(Indexed::new(obj, index))
-		}
+			obj => Indexed::new(obj, index)
+		};
+
+		obj.set_location(location);
+		Err(obj)
 	}
 }
 
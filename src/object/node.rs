@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet};
+use indexmap::{IndexMap, IndexSet};
 use std::hash::{Hash, Hasher};
 use std::convert::TryFrom;
 use std::borrow::Borrow;
@@ -39,22 +39,22 @@ pub struct Node<T: Id = IriBuf> {
 	/// Associated graph.
 	///
 	/// This is the `@graph` field.
-	pub(crate) graph: Option<HashSet<Indexed<Object<T>>>>,
+	pub(crate) graph: Option<IndexSet<Indexed<Object<T>>>>,
 
 	/// Included nodes.
 	///
 	/// This is the `@included` field.
-	pub(crate) included: Option<HashSet<Indexed<Node<T>>>>,
+	pub(crate) included: Option<IndexSet<Indexed<Node<T>>>>,
 
 	/// Properties.
 	///
 	/// Any non-keyword field.
-	pub(crate) properties: HashMap<Reference<T>, Vec<Indexed<Object<T>>>>,
+	pub(crate) properties: IndexMap<Reference<T>, Vec<Indexed<Object<T>>>>,
 
 	/// Reverse properties.
 	///
 	/// This is the `@reverse` field.
-	pub(crate) reverse_properties: HashMap<Reference<T>, Vec<Indexed<Node<T>>>>
+	pub(crate) reverse_properties: IndexMap<Reference<T>, Vec<Indexed<Node<T>>>>
 }
 
 /// Iterator through indexed objects.
@@ -79,8 +79,8 @@ impl<T: Id> Node<T> {
 			types: Vec::new(),
 			graph: None,
 			included: None,
-			properties: HashMap::new(),
-			reverse_properties: HashMap::new()
+			properties: IndexMap::new(),
+			reverse_properties: IndexMap::new()
 		}
 	}
 
@@ -91,8 +91,8 @@ impl<T: Id> Node<T> {
 			types: Vec::new(),
 			graph: None,
 			included: None,
-			properties: HashMap::new(),
-			reverse_properties: HashMap::new()
+			properties: IndexMap::new(),
+			reverse_properties: IndexMap::new()
 		}
 	}
 
@@ -156,6 +156,11 @@ impl<T: Id> Node<T> {
 		self.types.as_ref()
 	}
 
+	/// Get a mutable reference to the list of the node's types.
+	pub fn types_mut(&mut self) -> &mut Vec<Lenient<Reference<T>>> {
+		&mut self.types
+	}
+
 	/// Checks if the node has the given type.
 	pub fn has_type<U>(&self, ty: &U) -> bool where Lenient<Reference<T>>: PartialEq<U> {
 		for self_ty in &self.types {
@@ -195,36 +200,36 @@ impl<T: Id> Node<T> {
 	}
 
 	/// If the node is a graph object, get the graph.
-	pub fn graph(&self) -> Option<&HashSet<Indexed<Object<T>>>> {
+	pub fn graph(&self) -> Option<&IndexSet<Indexed<Object<T>>>> {
 		self.graph.as_ref()
 	}
 
 	/// If the node is a graph object, get the mutable graph.
-	pub fn graph_mut(&mut self) -> Option<&mut HashSet<Indexed<Object<T>>>> {
+	pub fn graph_mut(&mut self) -> Option<&mut IndexSet<Indexed<Object<T>>>> {
 		self.graph.as_mut()
 	}
 
 	/// Set the graph.
-	pub fn set_graph(&mut self, graph: Option<HashSet<Indexed<Object<T>>>>) {
+	pub fn set_graph(&mut self, graph: Option<IndexSet<Indexed<Object<T>>>>) {
 		self.graph = graph
 	}
 
 	/// Get the set of nodes included by this node.
 	///
 	/// This correspond to the `@included` field in the JSON representation.
-	pub fn included(&self) -> Option<&HashSet<Indexed<Node<T>>>> {
+	pub fn included(&self) -> Option<&IndexSet<Indexed<Node<T>>>> {
 		self.included.as_ref()
 	}
 
 	/// Get the mutable set of nodes included by this node.
 	///
 	/// This correspond to the `@included` field in the JSON representation.
-	pub fn included_mut(&mut self) -> Option<&mut HashSet<Indexed<Node<T>>>> {
+	pub fn included_mut(&mut self) -> Option<&mut IndexSet<Indexed<Node<T>>>> {
 		self.included.as_mut()
 	}
 
 	/// Set the set of nodes included by the node.
-	pub fn set_included(&mut self, included: Option<HashSet<Indexed<Node<T>>>>) {
+	pub fn set_included(&mut self, included: Option<IndexSet<Indexed<Node<T>>>>) {
 		self.included = included
 	}
 
@@ -302,13 +307,55 @@ impl<T: Id> Node<T> {
 	///
 	/// The unnamed graph is returned as a set of indexed objects.
 	/// Fails and returns itself if the node is *not* an unnamed graph.
-	pub fn into_unnamed_graph(self) -> Result<HashSet<Indexed<Object<T>>>, Node<T>> {
+	pub fn into_unnamed_graph(self) -> Result<IndexSet<Indexed<Object<T>>>, Node<T>> {
 		if self.is_unnamed_graph() {
 			Ok(self.graph.unwrap())
 		} else {
 			Err(self)
 		}
 	}
+
+	/// Maps every node reference of this node with the given function.
+	///
+	/// This includes the node identifier, types, properties, reverse properties,
+	/// and the references of every nested object, graph and included node.
+	pub fn map_references<F: FnMut(Reference<T>) -> Reference<T>>(self, f: &mut F) -> Node<T> {
+		let map_lenient = |r: Lenient<Reference<T>>, f: &mut F| match r {
+			Lenient::Ok(r) => Lenient::Ok(f(r)),
+			unknown => unknown
+		};
+
+		let mut properties: IndexMap<Reference<T>, Vec<Indexed<Object<T>>>> = IndexMap::new();
+		for (prop, objects) in self.properties {
+			let prop = f(prop);
+			let objects = objects.into_iter().map(|object| {
+				object.map_inner(|object| object.map_references(f))
+			});
+			properties.entry(prop).or_default().extend(objects)
+		}
+
+		let mut reverse_properties: IndexMap<Reference<T>, Vec<Indexed<Node<T>>>> = IndexMap::new();
+		for (prop, nodes) in self.reverse_properties {
+			let prop = f(prop);
+			let nodes = nodes.into_iter().map(|node| {
+				node.map_inner(|node| node.map_references(f))
+			});
+			reverse_properties.entry(prop).or_default().extend(nodes)
+		}
+
+		Node {
+			id: self.id.map(|id| map_lenient(id, f)),
+			types: self.types.into_iter().map(|ty| map_lenient(ty, f)).collect(),
+			graph: self.graph.map(|graph| graph.into_iter().map(|object| {
+				object.map_inner(|object| object.map_references(f))
+			}).collect()),
+			included: self.included.map(|included| included.into_iter().map(|node| {
+				node.map_inner(|node| node.map_references(f))
+			}).collect()),
+			properties,
+			reverse_properties
+		}
+	}
 }
 
 impl<T: Id> object::Any<T> for Node<T> {
@@ -0,0 +1,367 @@
+//! Property paths.
+//!
+//! A [`Path`] is a sequence of [`Step`]s walking from a node to the objects reachable
+//! through properties, reverse properties, list items, graphs and included nodes.
+//! Node references (nodes only defined by their `@id`) can be resolved using a
+//! [`NodeMap`], in which case each step is applied to every node object with the same
+//! identifier in the map.
+
+use std::collections::HashSet;
+use std::borrow::Borrow;
+use crate::{
+	Id,
+	Reference,
+	ToReference,
+	Lenient,
+	Indexed,
+	NodeMap
+};
+use super::{
+	Object,
+	Node,
+	Ref
+};
+
+/// Path step.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub enum Step<T: Id> {
+	/// Objects associated to the node with the given property.
+	Property(Reference<T>),
+
+	/// Nodes listed in the `@reverse` entry of the node for the given property.
+	Reverse(Reference<T>),
+
+	/// Nodes associated to the current node with the given property.
+	///
+	/// This includes the `@reverse` entries of the node (like [`Step::Reverse`]),
+	/// and, if a node map is provided, every node of the map pointing to the current node
+	/// through the given property.
+	Inverse(Reference<T>),
+
+	/// Items of the current list.
+	ListItems,
+
+	/// Objects of the node `@graph` entry.
+	Graph,
+
+	/// Nodes of the node `@included` entry.
+	Included,
+
+	/// Zero or more repetitions of the given path (`*`).
+	ZeroOrMore(Path<T>),
+
+	/// One or more repetitions of the given path (`+`).
+	OneOrMore(Path<T>)
+}
+
+/// Property path.
+///
+/// # Example
+/// ```
+/// use json_ld::{Reference, Node, Object, object::Path};
+/// # use iref::IriBuf;
+/// let iri = |s: &str| Reference::Id(IriBuf::new(s).unwrap());
+/// let author = iri("http://schema.org/author");
+/// let name = iri("http://schema.org/name");
+///
+/// let mut alice: Node = Node::with_id(iri("http://example.org/alice").into());
+/// alice.insert(name.clone(), Object::Value(json_ld::Value::Literal(json_ld::object::Literal::String("Alice".to_string()), None)).into());
+/// let mut book: Node = Node::new();
+/// book.insert(author.clone(), Object::Node(alice).into());
+///
+/// let path = Path::properties(vec![&author, &name]);
+/// let names: Vec<_> = book.path(&path).filter_map(|name| name.as_str()).collect();
+/// assert_eq!(names, vec!["Alice"]);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Path<T: Id> {
+	steps: Vec<Step<T>>
+}
+
+impl<T: Id> Path<T> {
+	/// Creates the empty path, leading to the starting node.
+	pub fn new() -> Path<T> {
+		Path {
+			steps: Vec::new()
+		}
+	}
+
+	/// Creates a path following the given properties.
+	pub fn properties<Q: ToReference<T>, P: IntoIterator<Item=Q>>(properties: P) -> Path<T> {
+		Path {
+			steps: properties.into_iter().map(|prop| Step::Property(prop.to_ref().borrow().clone())).collect()
+		}
+	}
+
+	/// Returns the steps of the path.
+	pub fn steps(&self) -> &[Step<T>] {
+		&self.steps
+	}
+
+	/// Adds a step at the end of the path.
+	pub fn push(&mut self, step: Step<T>) {
+		self.steps.push(step)
+	}
+
+	/// Follows the given property.
+	pub fn property<Q: ToReference<T>>(mut self, prop: Q) -> Path<T> {
+		self.steps.push(Step::Property(prop.to_ref().borrow().clone()));
+		self
+	}
+
+	/// Follows the given `@reverse` property.
+	pub fn reverse<Q: ToReference<T>>(mut self, prop: Q) -> Path<T> {
+		self.steps.push(Step::Reverse(prop.to_ref().borrow().clone()));
+		self
+	}
+
+	/// Follows the given property backward.
+	pub fn inverse<Q: ToReference<T>>(mut self, prop: Q) -> Path<T> {
+		self.steps.push(Step::Inverse(prop.to_ref().borrow().clone()));
+		self
+	}
+
+	/// Enters the current list.
+	pub fn list_items(mut self) -> Path<T> {
+		self.steps.push(Step::ListItems);
+		self
+	}
+
+	/// Enters the current node graph.
+	pub fn graph(mut self) -> Path<T> {
+		self.steps.push(Step::Graph);
+		self
+	}
+
+	/// Enters the current node included nodes.
+	pub fn included(mut self) -> Path<T> {
+		self.steps.push(Step::Included);
+		self
+	}
+
+	/// Follows the given path zero or more times.
+	pub fn zero_or_more(mut self, path: Path<T>) -> Path<T> {
+		self.steps.push(Step::ZeroOrMore(path));
+		self
+	}
+
+	/// Follows the given path one or more times.
+	pub fn one_or_more(mut self, path: Path<T>) -> Path<T> {
+		self.steps.push(Step::OneOrMore(path));
+		self
+	}
+
+	/// Walks the path from the given object.
+	///
+	/// If a node map is given, it is used to resolve node references.
+	pub fn walk<'a>(&self, start: Ref<'a, T>, node_map: Option<&NodeMap<'a, T>>) -> Objects<'a, T> {
+		Objects(walk(vec![start], &self.steps, node_map).into_iter())
+	}
+}
+
+impl<T: Id> Default for Path<T> {
+	fn default() -> Path<T> {
+		Path::new()
+	}
+}
+
+impl<T: Id> From<Vec<Step<T>>> for Path<T> {
+	fn from(steps: Vec<Step<T>>) -> Path<T> {
+		Path {
+			steps
+		}
+	}
+}
+
+/// Objects reached by a path.
+///
+/// Objects are given as [`Ref`]s rather than `&Indexed<Object<T>>`,
+/// since the starting node, the nodes of `@reverse` and `@included` entries
+/// and the nodes of a node map are not stored as indexed objects.
+/// Use [`Ref::as_node`] or [`Ref::as_value`] to access them.
+///
+/// Inverse steps, and steps resolving node references with a node map,
+/// reach each object at most once.
+pub struct Objects<'a, T: Id>(std::vec::IntoIter<Ref<'a, T>>);
+
+impl<'a, T: Id> Iterator for Objects<'a, T> {
+	type Item = Ref<'a, T>;
+
+	fn next(&mut self) -> Option<Ref<'a, T>> {
+		self.0.next()
+	}
+}
+
+impl<T: Id> Node<T> {
+	/// Returns the objects reached from this node by following the given path.
+	pub fn path<'a>(&'a self, path: &Path<T>) -> Objects<'a, T> {
+		path.walk(Ref::Node(self), None)
+	}
+
+	/// Returns the objects reached from this node by following the given path,
+	/// resolving node references with the given node map.
+	///
+	/// # Example
+	/// ```
+	/// use json_ld::{Reference, Node, Object, Value, NodeMap, ExpandedDocument, object::{Literal, Path}};
+	/// # use iref::IriBuf;
+	/// let iri = |s: &str| Reference::Id(IriBuf::new(s).unwrap());
+	/// let knows = iri("http://xmlns.com/foaf/0.1/knows");
+	/// let name = iri("http://xmlns.com/foaf/0.1/name");
+	/// let string = |s: &str| Object::Value(Value::Literal(Literal::String(s.to_string()), None)).into();
+	///
+	/// let mut alice = Node::with_id(iri("http://example.org/alice").into());
+	/// alice.insert(name.clone(), string("Alice"));
+	///
+	/// // Bob is described twice, each time pointing to Alice.
+	/// let bob = || {
+	///     let mut bob = Node::with_id(iri("http://example.org/bob").into());
+	///     bob.insert(knows.clone(), Object::Node(Node::with_id(iri("http://example.org/alice").into())).into());
+	///     bob
+	/// };
+	/// let mut bob_again = bob();
+	/// bob_again.insert(name.clone(), string("Bob"));
+	///
+	/// let doc: ExpandedDocument<IriBuf> = vec![Object::Node(alice).into(), Object::Node(bob()).into(), Object::Node(bob_again).into()].into_iter().collect();
+	/// let node_map = NodeMap::from_document(&doc);
+	/// let alice = match doc.iter().next().unwrap().inner() {
+	///     Object::Node(node) => node,
+	///     _ => unreachable!()
+	/// };
+	///
+	/// let path = Path::new().inverse(&knows).property(&name);
+	/// let names: Vec<_> = alice.path_with(&path, &node_map).filter_map(|name| name.as_str()).collect();
+	/// assert_eq!(names, vec!["Bob"]);
+	/// ```
+	pub fn path_with<'a>(&'a self, path: &Path<T>, node_map: &NodeMap<'a, T>) -> Objects<'a, T> {
+		path.walk(Ref::Node(self), Some(node_map))
+	}
+}
+
+/// Object identity, used to detect cycles.
+#[derive(PartialEq, Eq, Hash)]
+enum Key<'a, T: Id> {
+	Id(&'a Reference<T>),
+	Address(usize)
+}
+
+fn key<'a, T: Id>(object: Ref<'a, T>) -> Key<'a, T> {
+	match object {
+		Ref::Node(node) => match &node.id {
+			Some(Lenient::Ok(id)) => Key::Id(id),
+			_ => Key::Address(node as *const Node<T> as usize)
+		},
+		Ref::Value(value) => Key::Address(value as *const _ as usize),
+		Ref::List(items) => Key::Address(items.as_ptr() as usize)
+	}
+}
+
+fn object_ref<T: Id>(object: &Indexed<Object<T>>) -> Ref<'_, T> {
+	match object.inner() {
+		Object::Value(value) => Ref::Value(value),
+		Object::Node(node) => Ref::Node(node),
+		Object::List(items) => Ref::List(items.as_slice())
+	}
+}
+
+/// Returns the node objects describing the given node.
+fn descriptions<'a, T: Id>(node: &'a Node<T>, node_map: Option<&NodeMap<'a, T>>) -> Vec<&'a Node<T>> {
+	if let (Some(node_map), Some(Lenient::Ok(id))) = (node_map, &node.id) {
+		let nodes = node_map.get(id);
+		if !nodes.is_empty() {
+			return nodes.to_vec()
+		}
+	}
+
+	vec![node]
+}
+
+fn walk<'a, T: Id>(mut current: Vec<Ref<'a, T>>, steps: &[Step<T>], node_map: Option<&NodeMap<'a, T>>) -> Vec<Ref<'a, T>> {
+	for step in steps {
+		let mut next = Vec::new();
+		for object in current {
+			apply(object, step, node_map, &mut next)
+		}
+
+		// References resolved to the same node, or subjects pointing to the same node
+		// through different nodes, would otherwise be reached more than once.
+		if node_map.is_some() || matches!(step, Step::Inverse(_)) {
+			let mut visited = HashSet::new();
+			next.retain(|object| visited.insert(key(*object)))
+		}
+
+		current = next
+	}
+
+	current
+}
+
+fn apply<'a, T: Id>(object: Ref<'a, T>, step: &Step<T>, node_map: Option<&NodeMap<'a, T>>, result: &mut Vec<Ref<'a, T>>) {
+	match step {
+		Step::ListItems => {
+			if let Ref::List(items) = object {
+				result.extend(items.iter().map(object_ref))
+			}
+		},
+		Step::ZeroOrMore(path) => repeat(vec![object], path, node_map, result),
+		Step::OneOrMore(path) => repeat(walk(vec![object], &path.steps, node_map), path, node_map, result),
+		_ => {
+			if let Ref::Node(node) = object {
+				for desc in descriptions(node, node_map) {
+					match step {
+						Step::Property(prop) => result.extend(desc.get(prop).map(object_ref)),
+						Step::Reverse(prop) | Step::Inverse(prop) => {
+							if let Some(nodes) = desc.reverse_properties.get(prop) {
+								result.extend(nodes.iter().map(|node| Ref::Node(node.inner())))
+							}
+						},
+						Step::Graph => {
+							if let Some(graph) = &desc.graph {
+								result.extend(graph.iter().map(object_ref))
+							}
+						},
+						Step::Included => {
+							if let Some(included) = &desc.included {
+								result.extend(included.iter().map(|node| Ref::Node(node.inner())))
+							}
+						},
+						_ => ()
+					}
+				}
+
+				if let (Step::Inverse(prop), Some(node_map)) = (step, node_map) {
+					let target = key(object);
+					for subject in node_map.nodes() {
+						if subject.get(prop).any(|o| key(object_ref(o)) == target) {
+							result.push(Ref::Node(subject))
+						}
+					}
+				}
+			}
+		}
+	}
+}
+
+/// Repeatedly follows the given path from the given objects, until no new object is found.
+fn repeat<'a, T: Id>(start: Vec<Ref<'a, T>>, path: &Path<T>, node_map: Option<&NodeMap<'a, T>>, result: &mut Vec<Ref<'a, T>>) {
+	let mut visited = HashSet::new();
+	let mut current = Vec::new();
+	for object in start {
+		if visited.insert(key(object)) {
+			result.push(object);
+			current.push(object)
+		}
+	}
+
+	while !current.is_empty() {
+		let mut next = Vec::new();
+		for object in walk(current, &path.steps, node_map) {
+			if visited.insert(key(object)) {
+				result.push(object);
+				next.push(object)
+			}
+		}
+
+		current = next
+	}
+}
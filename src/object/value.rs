@@ -119,6 +119,13 @@ impl<T: Id> Value<T> {
 		}
 	}
 
+	/// Extract the value as a `V`, according to its type.
+	///
+	/// See [`FromValue`](object::FromValue) for more details.
+	pub fn extract<V: object::FromValue<T>>(&self) -> Result<V, object::ExtractError> {
+		V::from_value(self)
+	}
+
 	/// Return the type of the value if any.
 	///
 	/// This will return `Some(Type::Json)` for JSON literal values.
@@ -0,0 +1,536 @@
+//! Basic graph pattern matching.
+//!
+//! This module provides a small query engine able to evaluate
+//! [basic graph patterns](https://www.w3.org/TR/sparql11-query/#BasicGraphPatterns)
+//! over the nodes of an expanded document, indexed by a [`NodeMap`].
+//! A query is a conjunction of triple patterns whose subject, predicate and object can be
+//! variables.
+//! Evaluating the query returns every assignment of the variables such that each pattern
+//! matches a triple of the document.
+//!
+//! Node types are matched using the `rdf:type` predicate ([`Predicate::Type`]).
+//! Reverse properties are matched as regular properties from the reverse node.
+//! All the graphs of the document are queried at once.
+//!
+//! # Example
+//! ```
+//! use json_ld::{Reference, Node, Object, Value, NodeMap, object::Literal};
+//! use json_ld::query::{Query, Pattern, Atom, Predicate};
+//! # use iref::IriBuf;
+//! let iri = |s: &str| Reference::Id(IriBuf::new(s).unwrap());
+//! let person = iri("http://schema.org/Person");
+//! let name = iri("http://schema.org/name");
+//!
+//! let mut alice = Node::with_id(iri("http://example.org/alice").into());
+//! alice.types_mut().push(person.clone().into());
+//! alice.insert(name.clone(), Object::Value(Value::Literal(Literal::String("Alice".to_string()), None)).into());
+//! let doc: json_ld::ExpandedDocument<IriBuf> = vec![Object::Node(alice).into()].into_iter().collect();
+//!
+//! // All persons and their names.
+//! let mut query = Query::new();
+//! query.push(Pattern::new(Atom::var("p"), Predicate::Type, Atom::Ref(person)));
+//! query.push(Pattern::new(Atom::var("p"), Predicate::Property(name), Atom::var("name")));
+//!
+//! let node_map = NodeMap::from_document(&doc);
+//! let solutions = query.evaluate(&node_map);
+//! assert_eq!(solutions.len(), 1);
+//! assert_eq!(solutions[0].get("name").and_then(|name| name.as_str()), Some("Alice"));
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use crate::{
+	Id,
+	Reference,
+	Lenient,
+	Indexed,
+	Object,
+	Node,
+	Value,
+	NodeMap,
+	ExpandedDocument,
+	util::AsJson
+};
+
+pub use crate::rdf::RDF_TYPE;
+
+/// Subject or object of a triple pattern.
+#[derive(Clone, PartialEq, Eq)]
+pub enum Atom<T: Id> {
+	/// Variable.
+	Var(String),
+
+	/// Node identifier.
+	Ref(Reference<T>),
+
+	/// Value.
+	Value(Value<T>)
+}
+
+impl<T: Id> Atom<T> {
+	/// Creates a new variable.
+	pub fn var<S: Into<String>>(name: S) -> Atom<T> {
+		Atom::Var(name.into())
+	}
+}
+
+/// Predicate of a triple pattern.
+#[derive(Clone, PartialEq, Eq)]
+pub enum Predicate<T: Id> {
+	/// Variable.
+	Var(String),
+
+	/// The `rdf:type` predicate, matching node types.
+	Type,
+
+	/// Property.
+	Property(Reference<T>)
+}
+
+impl<T: Id> Predicate<T> {
+	/// Creates a new variable.
+	pub fn var<S: Into<String>>(name: S) -> Predicate<T> {
+		Predicate::Var(name.into())
+	}
+
+	/// Creates a predicate from a property.
+	///
+	/// Returns [`Predicate::Type`] if the property is `rdf:type`.
+	pub fn property(prop: Reference<T>) -> Predicate<T> {
+		if prop.as_str() == RDF_TYPE {
+			Predicate::Type
+		} else {
+			Predicate::Property(prop)
+		}
+	}
+}
+
+/// Triple pattern.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Pattern<T: Id> {
+	/// Subject.
+	pub subject: Atom<T>,
+
+	/// Predicate.
+	pub predicate: Predicate<T>,
+
+	/// Object.
+	pub object: Atom<T>
+}
+
+impl<T: Id> Pattern<T> {
+	/// Creates a new triple pattern.
+	pub fn new(subject: Atom<T>, predicate: Predicate<T>, object: Atom<T>) -> Pattern<T> {
+		Pattern {
+			subject,
+			predicate,
+			object
+		}
+	}
+
+	/// Number of positions of the pattern that are constants or bound variables.
+	fn bound_count(&self, bindings: &Bindings<T>) -> usize {
+		let atom = |a: &Atom<T>| match a {
+			Atom::Var(x) => bindings.contains(x),
+			_ => true
+		};
+
+		let predicate = match &self.predicate {
+			Predicate::Var(x) => bindings.contains(x),
+			_ => true
+		};
+
+		atom(&self.subject) as usize + predicate as usize + atom(&self.object) as usize
+	}
+}
+
+/// Value bound to a variable.
+pub enum Binding<'a, T: Id> {
+	/// Node object.
+	Node(&'a Node<T>),
+
+	/// Node identifier, such as a node type or a property.
+	Ref(&'a Reference<T>),
+
+	/// The `rdf:type` predicate.
+	Type,
+
+	/// Value object.
+	Value(&'a Value<T>),
+
+	/// List object.
+	List(&'a [Indexed<Object<T>>])
+}
+
+impl<'a, T: Id> Binding<'a, T> {
+	/// Creates a binding from an object.
+	pub fn from_object(object: &'a Object<T>) -> Binding<'a, T> {
+		match object {
+			Object::Node(node) => Binding::Node(node),
+			Object::Value(value) => Binding::Value(value),
+			Object::List(items) => Binding::List(items.as_slice())
+		}
+	}
+
+	/// Returns the identifier of the bound node, if any.
+	pub fn id(&self) -> Option<&'a Reference<T>> {
+		match self {
+			Binding::Node(node) => match &node.id {
+				Some(Lenient::Ok(id)) => Some(id),
+				_ => None
+			},
+			Binding::Ref(id) => Some(id),
+			_ => None
+		}
+	}
+
+	/// Returns the bound node object, if any.
+	pub fn as_node(&self) -> Option<&'a Node<T>> {
+		match self {
+			Binding::Node(node) => Some(node),
+			_ => None
+		}
+	}
+
+	/// Returns the bound value, if any.
+	pub fn as_value(&self) -> Option<&'a Value<T>> {
+		match self {
+			Binding::Value(value) => Some(value),
+			_ => None
+		}
+	}
+
+	/// Returns the bound value or identifier as a string.
+	pub fn as_str(&self) -> Option<&'a str> {
+		match self {
+			Binding::Value(value) => value.as_str(),
+			Binding::Type => Some(RDF_TYPE),
+			_ => self.id().map(|id| id.as_str())
+		}
+	}
+
+	/// Checks if this binding matches the given atom, ignoring variables.
+	fn matches(&self, atom: &Atom<T>) -> bool {
+		match atom {
+			Atom::Var(_) => true,
+			Atom::Ref(r) => self.id() == Some(r),
+			Atom::Value(v) => self.as_value() == Some(v)
+		}
+	}
+}
+
+impl<'a, T: Id> Clone for Binding<'a, T> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<'a, T: Id> Copy for Binding<'a, T> {}
+
+/// Two bindings are equal if they refer to the same node (by identifier, or by address for
+/// anonymous nodes), the same value or the same list.
+impl<'a, T: Id> PartialEq for Binding<'a, T> {
+	fn eq(&self, other: &Self) -> bool {
+		match (self.id(), other.id()) {
+			(Some(a), Some(b)) => a == b,
+			(None, None) => match (self, other) {
+				(Binding::Node(a), Binding::Node(b)) => std::ptr::eq(*a, *b),
+				(Binding::Type, Binding::Type) => true,
+				(Binding::Value(a), Binding::Value(b)) => a == b,
+				(Binding::List(a), Binding::List(b)) => a == b,
+				_ => false
+			},
+			_ => false
+		}
+	}
+}
+
+impl<'a, T: Id> fmt::Debug for Binding<'a, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Binding::Node(node) => write!(f, "Binding::Node({:?})", node.id),
+			Binding::Ref(id) => write!(f, "Binding::Ref({})", id),
+			Binding::Type => write!(f, "Binding::Type"),
+			Binding::Value(value) => write!(f, "Binding::Value({})", value.as_json().dump()),
+			Binding::List(items) => write!(f, "Binding::List({})", items.as_json().dump())
+		}
+	}
+}
+
+/// Variable bindings, solution of a query.
+pub struct Bindings<'a, T: Id> {
+	map: HashMap<String, Binding<'a, T>>
+}
+
+impl<'a, T: Id> Bindings<'a, T> {
+	/// Creates an empty set of bindings.
+	pub fn new() -> Bindings<'a, T> {
+		Bindings {
+			map: HashMap::new()
+		}
+	}
+
+	/// Returns the binding of the given variable, if any.
+	pub fn get(&self, var: &str) -> Option<Binding<'a, T>> {
+		self.map.get(var).copied()
+	}
+
+	/// Checks if the given variable is bound.
+	pub fn contains(&self, var: &str) -> bool {
+		self.map.contains_key(var)
+	}
+
+	/// Number of bound variables.
+	pub fn len(&self) -> usize {
+		self.map.len()
+	}
+
+	/// Checks if no variable is bound.
+	pub fn is_empty(&self) -> bool {
+		self.map.is_empty()
+	}
+
+	/// Returns an iterator over the variables and their binding.
+	pub fn iter(&self) -> impl Iterator<Item=(&str, Binding<'a, T>)> {
+		self.map.iter().map(|(x, b)| (x.as_str(), *b))
+	}
+
+	/// Binds the given variable.
+	///
+	/// Returns `false` if the variable is already bound to a different value.
+	fn bind(&mut self, var: &str, binding: Binding<'a, T>) -> bool {
+		match self.map.get(var) {
+			Some(current) => *current == binding,
+			None => {
+				self.map.insert(var.to_string(), binding);
+				true
+			}
+		}
+	}
+
+	/// Binds the given atom if it is a variable, or checks that it matches the binding.
+	fn unify(&mut self, atom: &Atom<T>, binding: Binding<'a, T>) -> bool {
+		match atom {
+			Atom::Var(x) => self.bind(x, binding),
+			_ => binding.matches(atom)
+		}
+	}
+
+	/// Returns the binding of the given atom, if it is a bound variable.
+	fn resolve(&self, atom: &Atom<T>) -> Option<Binding<'a, T>> {
+		match atom {
+			Atom::Var(x) => self.get(x),
+			_ => None
+		}
+	}
+}
+
+impl<'a, T: Id> Clone for Bindings<'a, T> {
+	fn clone(&self) -> Self {
+		Bindings {
+			map: self.map.clone()
+		}
+	}
+}
+
+impl<'a, T: Id> Default for Bindings<'a, T> {
+	fn default() -> Self {
+		Bindings::new()
+	}
+}
+
+impl<'a, T: Id> fmt::Debug for Bindings<'a, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_map().entries(self.map.iter()).finish()
+	}
+}
+
+/// Triple of the queried document.
+struct Triple<'a, T: Id> {
+	subject: &'a Node<T>,
+	predicate: Binding<'a, T>,
+	object: Binding<'a, T>
+}
+
+/// Triples of a node map, indexed by subject identifier and predicate.
+struct Triples<'a, T: Id> {
+	triples: Vec<Triple<'a, T>>,
+	by_subject: HashMap<&'a Reference<T>, Vec<usize>>,
+	by_predicate: HashMap<&'a Reference<T>, Vec<usize>>,
+	types: Vec<usize>
+}
+
+impl<'a, T: Id> Triples<'a, T> {
+	fn new(node_map: &NodeMap<'a, T>) -> Triples<'a, T> {
+		let mut triples = Vec::new();
+
+		for node in node_map.nodes() {
+			for ty in &node.types {
+				if let Lenient::Ok(ty) = ty {
+					triples.push(Triple { subject: node, predicate: Binding::Type, object: Binding::Ref(ty) })
+				}
+			}
+
+			for (prop, objects) in &node.properties {
+				for object in objects {
+					triples.push(Triple { subject: node, predicate: Binding::Ref(prop), object: Binding::from_object(object.inner()) })
+				}
+			}
+
+			for (prop, nodes) in &node.reverse_properties {
+				for reverse_node in nodes {
+					triples.push(Triple { subject: reverse_node.inner(), predicate: Binding::Ref(prop), object: Binding::Node(node) })
+				}
+			}
+		}
+
+		let mut by_subject: HashMap<_, Vec<usize>> = HashMap::new();
+		let mut by_predicate: HashMap<_, Vec<usize>> = HashMap::new();
+		let mut types = Vec::new();
+		for (i, triple) in triples.iter().enumerate() {
+			if let Some(id) = Binding::Node(triple.subject).id() {
+				by_subject.entry(id).or_default().push(i)
+			}
+
+			match triple.predicate {
+				Binding::Ref(prop) => by_predicate.entry(prop).or_default().push(i),
+				_ => types.push(i)
+			}
+		}
+
+		Triples {
+			triples,
+			by_subject,
+			by_predicate,
+			types
+		}
+	}
+
+	/// Returns the indexes of the triples that may match the given pattern.
+	fn candidates(&self, pattern: &Pattern<T>, bindings: &Bindings<'a, T>) -> Candidates<'_> {
+		let subject_id = match &pattern.subject {
+			Atom::Ref(r) => Some(Some(r)),
+			Atom::Var(_) => bindings.resolve(&pattern.subject).map(|b| b.id()),
+			Atom::Value(_) => return Candidates::None
+		};
+
+		match subject_id {
+			Some(Some(id)) => self.by_subject.get(id).map(|i| Candidates::Some(i.as_slice())).unwrap_or(Candidates::None),
+			// Anonymous node, or non-node subject.
+			Some(None) => Candidates::All(self.triples.len()),
+			None => match &pattern.predicate {
+				Predicate::Type => Candidates::Some(self.types.as_slice()),
+				Predicate::Property(prop) if prop.as_str() == RDF_TYPE => Candidates::Some(self.types.as_slice()),
+				Predicate::Property(prop) => self.by_predicate.get(prop).map(|i| Candidates::Some(i.as_slice())).unwrap_or(Candidates::None),
+				Predicate::Var(_) => Candidates::All(self.triples.len())
+			}
+		}
+	}
+}
+
+enum Candidates<'i> {
+	None,
+	All(usize),
+	Some(&'i [usize])
+}
+
+impl<'i> Candidates<'i> {
+	fn iter(&self) -> Box<dyn Iterator<Item=usize> + 'i> {
+		match self {
+			Candidates::None => Box::new(std::iter::empty()),
+			Candidates::All(len) => Box::new(0..*len),
+			Candidates::Some(indexes) => Box::new(indexes.iter().copied())
+		}
+	}
+}
+
+/// Basic graph pattern.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Query<T: Id> {
+	patterns: Vec<Pattern<T>>
+}
+
+impl<T: Id> Query<T> {
+	/// Creates an empty query, matching anything.
+	pub fn new() -> Query<T> {
+		Query {
+			patterns: Vec::new()
+		}
+	}
+
+	/// Returns the triple patterns of the query.
+	pub fn patterns(&self) -> &[Pattern<T>] {
+		&self.patterns
+	}
+
+	/// Adds a triple pattern to the query.
+	pub fn push(&mut self, pattern: Pattern<T>) {
+		self.patterns.push(pattern)
+	}
+
+	/// Evaluates the query over the nodes of the given node map.
+	///
+	/// Returns every solution of the query.
+	/// Patterns are evaluated in the order maximizing the number of bound positions,
+	/// and joined on their common variables.
+	pub fn evaluate<'a>(&self, node_map: &NodeMap<'a, T>) -> Vec<Bindings<'a, T>> {
+		let triples = Triples::new(node_map);
+		let mut remaining: Vec<&Pattern<T>> = self.patterns.iter().collect();
+		let mut solutions = vec![Bindings::new()];
+
+		while !remaining.is_empty() && !solutions.is_empty() {
+			// Select the most constrained pattern.
+			let (index, _) = remaining.iter().enumerate().max_by_key(|(i, p)| (p.bound_count(&solutions[0]), std::cmp::Reverse(*i))).unwrap();
+			let pattern = remaining.remove(index);
+
+			let mut next_solutions = Vec::new();
+			for bindings in &solutions {
+				for i in triples.candidates(pattern, bindings).iter() {
+					let triple = &triples.triples[i];
+					let mut bindings = bindings.clone();
+					if matches(pattern, triple, &mut bindings) {
+						next_solutions.push(bindings)
+					}
+				}
+			}
+
+			solutions = next_solutions
+		}
+
+		solutions
+	}
+
+	/// Evaluates the query over the given expanded document.
+	pub fn evaluate_document<'a>(&self, doc: &'a ExpandedDocument<T>) -> Vec<Bindings<'a, T>> {
+		self.evaluate(&NodeMap::from_document(doc))
+	}
+}
+
+impl<T: Id> Default for Query<T> {
+	fn default() -> Self {
+		Query::new()
+	}
+}
+
+impl<T: Id> From<Vec<Pattern<T>>> for Query<T> {
+	fn from(patterns: Vec<Pattern<T>>) -> Query<T> {
+		Query {
+			patterns
+		}
+	}
+}
+
+/// Matches a triple against a pattern, extending the given bindings.
+fn matches<'a, T: Id>(pattern: &Pattern<T>, triple: &Triple<'a, T>, bindings: &mut Bindings<'a, T>) -> bool {
+	let predicate_matches = match (&pattern.predicate, triple.predicate) {
+		(Predicate::Var(x), predicate) => bindings.bind(x, predicate),
+		(Predicate::Type, Binding::Type) => true,
+		(Predicate::Property(prop), Binding::Type) => prop.as_str() == RDF_TYPE,
+		(Predicate::Property(prop), Binding::Ref(p)) => prop == p,
+		_ => false
+	};
+
+	predicate_matches
+	&& bindings.unify(&pattern.subject, Binding::Node(triple.subject))
+	&& bindings.unify(&pattern.object, triple.object)
+}
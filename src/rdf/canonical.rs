@@ -0,0 +1,221 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hasher;
+use crate::{
+	Id,
+	BlankId,
+	Reference,
+	util::Fnv1a
+};
+use super::{
+	Quad,
+	Term,
+	Dataset,
+	BlankMapping,
+	isomorphism::{
+		colors,
+		refine,
+		blanks_of
+	}
+};
+
+/// Computes a canonical labelling of the blank nodes of the given dataset.
+///
+/// The labelling does not depend on the blank node identifiers of the dataset:
+/// isomorphic datasets receive the same labelling, up to hash collisions.
+///
+/// Blank nodes are labelled `_:c14nH`, where `H` is a hash of the canonical form of their
+/// connected component and of their position in it.
+/// Hashes are computed with FNV-1a over the N-Quads form of the quads:
+/// labels do not depend on the platform, nor on the representation of the identifiers.
+/// Adding or removing blank nodes hence only renames the blank nodes connected to them.
+///
+/// The canonical form of a component is computed by hashing the neighbourhood of its blank nodes.
+/// Blank nodes that cannot be distinguished this way are individualized in turn,
+/// keeping the choice giving the smallest relabelled component.
+///
+/// # Example
+/// ```
+/// use json_ld::{Reference, BlankId, rdf::{self, Quad, Term, Dataset}};
+/// # use iref::IriBuf;
+/// let p = IriBuf::new("http://example.org/p").unwrap();
+/// let dataset = |a: &str, b: &str| -> Dataset<IriBuf> {
+///     let blank = |id: &str| Reference::Blank(BlankId::new(id));
+///     vec![
+///         Quad::new(blank(a), p.clone(), Term::Ref(blank(b)), None),
+///         Quad::new(blank(b), p.clone(), Term::Ref(blank(a)), None)
+///     ].into_iter().collect()
+/// };
+///
+/// assert_eq!(rdf::canonicalize(&dataset("a", "b")), rdf::canonicalize(&dataset("y", "x")));
+///
+/// // Labels are the same on every platform.
+/// let dataset: Dataset<IriBuf> = vec![
+///     Quad::new(Reference::Blank(BlankId::new("a")), p.clone(), Term::Ref(Reference::Blank(BlankId::new("b"))), None)
+/// ].into_iter().collect();
+/// let labelling = rdf::canonical_labelling(&dataset);
+/// assert_eq!(labelling[&BlankId::new("a")].as_str(), "_:c14n292d8e2f0f451d10");
+/// ```
+pub fn canonical_labelling<T: Id>(dataset: &Dataset<T>) -> BlankMapping {
+	let quads: Vec<_> = dataset.iter().filter(|q| q.has_blank()).collect();
+
+	let mut mapping = BlankMapping::new();
+	let mut isomorphic_components: HashMap<Vec<String>, usize> = HashMap::new();
+	for component in components(&quads) {
+		let (form, blanks) = canonical_form(&component);
+		let rank = isomorphic_components.entry(form.clone()).or_default();
+
+		for (i, id) in blanks.into_iter().enumerate() {
+			let mut hasher = Fnv1a::new();
+			for quad in &form {
+				hasher.write(quad.as_bytes());
+				hasher.write_u8(b'\n')
+			}
+			hasher.write_u64(*rank as u64);
+			hasher.write_u64(i as u64);
+			mapping.insert(id.clone(), BlankId::new(&format!("c14n{:016x}", hasher.finish())));
+		}
+
+		// Isomorphic components are interchangeable: they are told apart by their rank.
+		*rank += 1
+	}
+
+	mapping
+}
+
+/// Splits the given quads into connected components,
+/// two quads being connected when they share a blank node.
+fn components<'a, T: Id>(quads: &[&'a Quad<T>]) -> Vec<Vec<&'a Quad<T>>> {
+	let mut index: HashMap<&BlankId, usize> = HashMap::new();
+	let mut parents: Vec<usize> = Vec::new();
+
+	fn find(parents: &mut [usize], mut i: usize) -> usize {
+		while parents[i] != i {
+			parents[i] = parents[parents[i]];
+			i = parents[i]
+		}
+
+		i
+	}
+
+	for quad in quads {
+		let mut root = None;
+		for (_, id) in blanks_of(quad) {
+			let i = *index.entry(id).or_insert_with(|| {
+				parents.push(parents.len());
+				parents.len() - 1
+			});
+
+			let i = find(&mut parents, i);
+			match root {
+				Some(root) => parents[i] = root,
+				None => root = Some(i)
+			}
+		}
+	}
+
+	let mut components: HashMap<usize, Vec<&Quad<T>>> = HashMap::new();
+	for quad in quads {
+		let (_, id) = blanks_of(quad).next().unwrap();
+		let root = find(&mut parents, index[id]);
+		components.entry(root).or_default().push(quad)
+	}
+
+	components.into_values().collect()
+}
+
+/// Computes the canonical form of a connected component,
+/// given as the sorted list of its relabelled quads, along with its blank nodes in canonical order.
+fn canonical_form<'a, T: Id>(quads: &[&'a Quad<T>]) -> (Vec<String>, Vec<&'a BlankId>) {
+	let set: HashSet<&Quad<T>> = quads.iter().copied().collect();
+	search(quads, &set, colors(quads))
+}
+
+fn search<'a, T: Id>(quads: &[&'a Quad<T>], set: &HashSet<&'a Quad<T>>, colors: HashMap<&'a BlankId, u64>) -> (Vec<String>, Vec<&'a BlankId>) {
+	let mut classes: HashMap<u64, Vec<&BlankId>> = HashMap::new();
+	for (id, color) in &colors {
+		classes.entry(*color).or_default().push(id)
+	}
+
+	// Smallest ambiguous class.
+	let ambiguous = classes.into_iter().filter(|(_, ids)| ids.len() > 1).min_by_key(|(color, ids)| (ids.len(), *color));
+	match ambiguous {
+		Some((color, ids)) => {
+			let mut best: Option<(Vec<String>, Vec<&BlankId>)> = None;
+			let mut explored: Vec<&BlankId> = Vec::new();
+			for id in ids {
+				// Exchangeable blank nodes give the same canonical form.
+				if explored.iter().any(|other| is_transposition_automorphism(quads, set, id, other)) {
+					continue
+				}
+
+				explored.push(id);
+
+				let mut hasher = Fnv1a::new();
+				hasher.write_u64(color);
+				hasher.write(b"individualized");
+				let mut initial = colors.clone();
+				initial.insert(id, hasher.finish());
+
+				let candidate = search(quads, set, refine(quads, initial));
+				if best.as_ref().map(|(form, _)| candidate.0 < *form).unwrap_or(true) {
+					best = Some(candidate)
+				}
+			}
+
+			best.unwrap()
+		},
+		None => {
+			let mut blanks: Vec<_> = colors.into_iter().collect();
+			blanks.sort_by_key(|(_, color)| *color);
+			let blanks: Vec<&BlankId> = blanks.into_iter().map(|(id, _)| id).collect();
+
+			let mapping: BlankMapping = blanks.iter().enumerate().map(|(i, id)| ((*id).clone(), BlankId::new(&format!("c14n{}", i)))).collect();
+			let mut form: Vec<String> = quads.iter().map(|quad| relabel_quad(quad, &mapping).to_string()).collect();
+			form.sort_unstable();
+
+			(form, blanks)
+		}
+	}
+}
+
+/// Checks if exchanging the two given blank nodes leaves the quads unchanged.
+fn is_transposition_automorphism<T: Id>(quads: &[&Quad<T>], set: &HashSet<&Quad<T>>, a: &BlankId, b: &BlankId) -> bool {
+	let mut mapping = BlankMapping::new();
+	mapping.insert(a.clone(), b.clone());
+	mapping.insert(b.clone(), a.clone());
+
+	quads.iter()
+		.filter(|quad| blanks_of(quad).any(|(_, id)| id == a || id == b))
+		.all(|quad| set.contains(&relabel_quad(quad, &mapping)))
+}
+
+fn relabel_quad<T: Id>(quad: &Quad<T>, mapping: &BlankMapping) -> Quad<T> {
+	let map_reference = |r: &Reference<T>| match r {
+		Reference::Blank(id) => Reference::Blank(mapping.get(id).cloned().unwrap_or_else(|| id.clone())),
+		r => r.clone()
+	};
+
+	Quad {
+		subject: map_reference(&quad.subject),
+		predicate: quad.predicate.clone(),
+		object: match &quad.object {
+			Term::Ref(r) => Term::Ref(map_reference(r)),
+			lit => lit.clone()
+		},
+		graph: quad.graph.as_ref().map(map_reference)
+	}
+}
+
+/// Relabels the blank nodes of the given dataset with the given mapping.
+///
+/// Blank nodes absent from the mapping are left untouched.
+pub fn relabel<T: Id>(dataset: &Dataset<T>, mapping: &BlankMapping) -> Dataset<T> {
+	dataset.iter().map(|quad| relabel_quad(quad, mapping)).collect()
+}
+
+/// Relabels the blank nodes of the given dataset using its canonical labelling.
+///
+/// Isomorphic datasets are given the same canonical form.
+pub fn canonicalize<T: Id>(dataset: &Dataset<T>) -> Dataset<T> {
+	relabel(dataset, &canonical_labelling(dataset))
+}
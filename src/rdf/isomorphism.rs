@@ -0,0 +1,294 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::hash::Hasher;
+use crate::{
+	Id,
+	BlankId,
+	Reference,
+	ExpandedDocument,
+	util::Fnv1a
+};
+use super::{
+	Quad,
+	Term,
+	Dataset,
+	to_rdf
+};
+
+/// Blank node mapping.
+pub type BlankMapping = HashMap<BlankId, BlankId>;
+
+/// Finds a bijection between the blank nodes of the two given datasets making them equal.
+///
+/// Returns `None` if the datasets are not isomorphic.
+/// Blank nodes are first partitioned by iteratively hashing their neighbourhood,
+/// then candidate mappings are explored between blank nodes of the same partition.
+pub fn isomorphism<T: Id>(a: &Dataset<T>, b: &Dataset<T>) -> Option<BlankMapping> {
+	if a.len() != b.len() {
+		return None
+	}
+
+	let a_ground: HashSet<_> = a.iter().filter(|q| !q.has_blank()).collect();
+	let b_ground: HashSet<_> = b.iter().filter(|q| !q.has_blank()).collect();
+	if a_ground != b_ground {
+		return None
+	}
+
+	let a_quads: Vec<_> = a.iter().filter(|q| q.has_blank()).collect();
+	let b_quads: Vec<_> = b.iter().filter(|q| q.has_blank()).collect();
+
+	let a_colors = colors(&a_quads);
+	let b_colors = colors(&b_quads);
+
+	// Both datasets must have the same color distribution.
+	let mut a_histogram: Vec<_> = a_colors.values().copied().collect();
+	let mut b_histogram: Vec<_> = b_colors.values().copied().collect();
+	a_histogram.sort_unstable();
+	b_histogram.sort_unstable();
+	if a_histogram != b_histogram {
+		return None
+	}
+
+	// Blank nodes of `a`, most discriminated first.
+	let mut class_size: HashMap<u64, usize> = HashMap::new();
+	for color in a_colors.values() {
+		*class_size.entry(*color).or_default() += 1
+	}
+
+	let mut blanks: Vec<_> = a_colors.iter().collect();
+	blanks.sort_by_key(|(id, color)| (class_size[color], *color, id.as_str()));
+	let blanks: Vec<&BlankId> = blanks.into_iter().map(|(id, _)| *id).collect();
+
+	let mut candidates: HashMap<u64, Vec<&BlankId>> = HashMap::new();
+	for (id, color) in &b_colors {
+		candidates.entry(*color).or_default().push(id)
+	}
+
+	let mut occurrences: HashMap<&BlankId, Vec<&Quad<T>>> = HashMap::new();
+	for quad in &a_quads {
+		for (_, id) in blanks_of(quad) {
+			occurrences.entry(id).or_default().push(quad)
+		}
+	}
+
+	let b_set: HashSet<&Quad<T>> = b_quads.iter().copied().collect();
+	let mut search = Search {
+		occurrences: &occurrences,
+		b_set: &b_set,
+		a_colors: &a_colors,
+		candidates: &candidates,
+		mapping: HashMap::new(),
+		used: HashSet::new()
+	};
+
+	if search.explore(&blanks) {
+		Some(search.mapping.into_iter().map(|(a, b)| (a.clone(), b.clone())).collect())
+	} else {
+		None
+	}
+}
+
+/// Checks if the two given datasets are equal up to blank node renaming.
+pub fn is_isomorphic<T: Id>(a: &Dataset<T>, b: &Dataset<T>) -> bool {
+	isomorphism(a, b).is_some()
+}
+
+/// Checks if the two given expanded documents denote the same RDF dataset,
+/// up to blank node renaming.
+///
+/// Since the comparison is performed on the RDF form of the documents,
+/// information that is not preserved by the RDF conversion, such as `@index`
+/// or free-floating values, is ignored.
+///
+/// # Example
+/// ```
+/// use json_ld::{Reference, BlankId, Node, Object, ExpandedDocument, rdf};
+/// # use iref::IriBuf;
+/// let knows = Reference::Id(IriBuf::new("http://xmlns.com/foaf/0.1/knows").unwrap());
+/// let doc = |a: &str, b: &str| -> ExpandedDocument<IriBuf> {
+///     let mut node = Node::with_id(Reference::Blank(BlankId::new(a)).into());
+///     node.insert(knows.clone(), Object::Node(Node::with_id(Reference::Blank(BlankId::new(b)).into())).into());
+///     vec![Object::Node(node).into()].into_iter().collect()
+/// };
+///
+/// assert!(rdf::documents_isomorphic(&doc("alice", "bob"), &doc("x", "y")));
+/// assert!(!rdf::documents_isomorphic(&doc("alice", "bob"), &doc("x", "x")));
+/// ```
+pub fn documents_isomorphic<T: Id>(a: &ExpandedDocument<T>, b: &ExpandedDocument<T>) -> bool {
+	is_isomorphic(&to_rdf(a), &to_rdf(b))
+}
+
+fn blank_of<T: Id>(r: &Reference<T>) -> Option<&BlankId> {
+	match r {
+		Reference::Blank(id) => Some(id),
+		_ => None
+	}
+}
+
+/// Blank nodes of a quad with their position.
+pub(crate) fn blanks_of<T: Id>(quad: &Quad<T>) -> impl Iterator<Item=(u8, &BlankId)> {
+	let subject = blank_of(&quad.subject).map(|id| (0, id));
+	let object = quad.object.as_blank().map(|id| (1, id));
+	let graph = quad.graph.as_ref().and_then(blank_of).map(|id| (2, id));
+	subject.into_iter().chain(object).chain(graph)
+}
+
+/// Hashes a reference through its string form, replacing blank nodes with their color.
+fn hash_reference<T: Id>(r: &Reference<T>, colors: &HashMap<&BlankId, u64>, hasher: &mut Fnv1a) {
+	match blank_of(r) {
+		Some(id) => {
+			hasher.write_u8(0);
+			hasher.write_u64(colors[id])
+		},
+		None => {
+			hasher.write_u8(1);
+			hasher.write(r.as_str().as_bytes());
+			hasher.write_u8(0)
+		}
+	}
+}
+
+/// Hashes a quad, replacing blank nodes with their color.
+///
+/// The hash only depends on the string form of the terms,
+/// and not on their representation or on the platform.
+fn hash_quad<T: Id>(quad: &Quad<T>, colors: &HashMap<&BlankId, u64>, position: u8) -> u64 {
+	let mut hasher = Fnv1a::new();
+	hasher.write_u8(position);
+	hash_reference(&quad.subject, colors, &mut hasher);
+	hasher.write(quad.predicate.as_iri().as_str().as_bytes());
+	hasher.write_u8(0);
+	match &quad.object {
+		Term::Ref(r) => hash_reference(r, colors, &mut hasher),
+		Term::Literal(lit) => {
+			hasher.write_u8(2);
+			write!(hasher, "{}", lit).unwrap();
+			hasher.write_u8(0)
+		}
+	}
+	match &quad.graph {
+		Some(g) => hash_reference(g, colors, &mut hasher),
+		None => hasher.write_u8(3)
+	}
+	hasher.finish()
+}
+
+/// Computes a color for each blank node by iteratively refining the colors with the colors
+/// of the neighbourhood, until the partition is stable.
+pub(crate) fn colors<'a, T: Id>(quads: &[&'a Quad<T>]) -> HashMap<&'a BlankId, u64> {
+	let mut initial = HashMap::new();
+	for quad in quads {
+		for (_, id) in blanks_of(quad) {
+			initial.insert(id, 0);
+		}
+	}
+
+	refine(quads, initial)
+}
+
+/// Refines the given blank node colors until the partition is stable.
+pub(crate) fn refine<'a, T: Id>(quads: &[&'a Quad<T>], mut colors: HashMap<&'a BlankId, u64>) -> HashMap<&'a BlankId, u64> {
+	let mut occurrences: HashMap<&BlankId, Vec<(u8, usize)>> = HashMap::new();
+	for (i, quad) in quads.iter().enumerate() {
+		for (position, id) in blanks_of(quad) {
+			occurrences.entry(id).or_default().push((position, i))
+		}
+	}
+
+	let mut class_count = colors.values().collect::<HashSet<_>>().len();
+
+	loop {
+		let mut new_colors = HashMap::new();
+		for (id, occ) in &occurrences {
+			let mut signatures: Vec<u64> = occ.iter().map(|(position, i)| hash_quad(quads[*i], &colors, *position)).collect();
+			signatures.sort_unstable();
+
+			let mut hasher = Fnv1a::new();
+			hasher.write_u64(colors[id]);
+			for signature in signatures {
+				hasher.write_u64(signature)
+			}
+			new_colors.insert(*id, hasher.finish());
+		}
+
+		let new_class_count = new_colors.values().collect::<HashSet<_>>().len();
+		colors = new_colors;
+		if new_class_count <= class_count {
+			break colors
+		}
+
+		class_count = new_class_count
+	}
+}
+
+struct Search<'a, 'q, T: Id> {
+	occurrences: &'q HashMap<&'a BlankId, Vec<&'a Quad<T>>>,
+	b_set: &'q HashSet<&'a Quad<T>>,
+	a_colors: &'q HashMap<&'a BlankId, u64>,
+	candidates: &'q HashMap<u64, Vec<&'a BlankId>>,
+	mapping: HashMap<&'a BlankId, &'a BlankId>,
+	used: HashSet<&'a BlankId>
+}
+
+impl<'a, 'q, T: Id> Search<'a, 'q, T> {
+	fn explore(&mut self, blanks: &[&'a BlankId]) -> bool {
+		match blanks.split_first() {
+			None => true,
+			Some((id, rest)) => {
+				for candidate in &self.candidates[&self.a_colors[id]] {
+					if self.used.contains(candidate) {
+						continue
+					}
+
+					self.mapping.insert(id, candidate);
+					self.used.insert(candidate);
+
+					if self.is_consistent(id) && self.explore(rest) {
+						return true
+					}
+
+					self.mapping.remove(id);
+					self.used.remove(candidate);
+				}
+
+				false
+			}
+		}
+	}
+
+	/// Checks that every quad involving the given blank node, and whose blank nodes are all
+	/// mapped, is mapped to a quad of the other dataset.
+	fn is_consistent(&self, id: &BlankId) -> bool {
+		for quad in &self.occurrences[id] {
+			if let Some(mapped) = self.map_quad(quad) {
+				if !self.b_set.contains(&mapped) {
+					return false
+				}
+			}
+		}
+
+		true
+	}
+
+	fn map_reference(&self, r: &Reference<T>) -> Option<Reference<T>> {
+		match r {
+			Reference::Blank(id) => self.mapping.get(id).map(|b| Reference::Blank((*b).clone())),
+			r => Some(r.clone())
+		}
+	}
+
+	fn map_quad(&self, quad: &Quad<T>) -> Option<Quad<T>> {
+		Some(Quad {
+			subject: self.map_reference(&quad.subject)?,
+			predicate: quad.predicate.clone(),
+			object: match &quad.object {
+				Term::Ref(r) => Term::Ref(self.map_reference(r)?),
+				lit => lit.clone()
+			},
+			graph: match &quad.graph {
+				Some(g) => Some(self.map_reference(g)?),
+				None => None
+			}
+		})
+	}
+}
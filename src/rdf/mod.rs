@@ -0,0 +1,420 @@
+//! RDF datasets.
+//!
+//! This module provides a conversion of expanded documents into RDF quads,
+//! following the [Deserialize JSON-LD to RDF](https://www.w3.org/TR/json-ld11-api/#deserialize-json-ld-to-rdf-algorithm)
+//! algorithm, and a blank-node-aware isomorphism check between datasets.
+//!
+//! Blank nodes are relabelled during the conversion: every anonymous node and every
+//! blank node identifier of the document is given a fresh label, `_:bN` by default,
+//! or given by a custom [`BlankIdGenerator`] with [`to_rdf_with`].
+//! Base directions of language-tagged strings are dropped, as with the default `rdfDirection`
+//! option of the specification.
+//!
+//! Quads can also be generated on the fly from a stream of expanded top-level objects
+//! with [`quads`], without building the dataset.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::marker::PhantomData;
+use iref::Iri;
+use crate::{
+	Id,
+	BlankId,
+	Reference,
+	Lenient,
+	Indexed,
+	Object,
+	Node,
+	Value,
+	ExpandedDocument,
+	BlankIdGenerator,
+	Sequential,
+	Relabeller,
+	object::Literal as JsonLiteral,
+	util,
+	xsd
+};
+
+mod isomorphism;
+mod canonical;
+mod stream;
+
+pub use isomorphism::*;
+pub use canonical::*;
+pub use stream::*;
+
+/// RDF namespace.
+pub const RDF: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+
+/// `rdf:type` IRI.
+pub const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+
+/// `rdf:first` IRI.
+pub const RDF_FIRST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#first";
+
+/// `rdf:rest` IRI.
+pub const RDF_REST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#rest";
+
+/// `rdf:nil` IRI.
+pub const RDF_NIL: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#nil";
+
+/// `rdf:langString` IRI.
+pub const RDF_LANG_STRING: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString";
+
+/// `rdf:JSON` IRI.
+pub const RDF_JSON: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#JSON";
+
+fn iri<T: Id>(iri: &str) -> T {
+	T::from_iri(Iri::new(iri).unwrap())
+}
+
+/// RDF literal.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub enum Literal<T: Id> {
+	/// Typed literal, with its lexical form and datatype.
+	Typed(String, T),
+
+	/// Language-tagged string (`rdf:langString`).
+	LangString(String, String)
+}
+
+impl<T: Id> Literal<T> {
+	/// Returns the lexical form of the literal.
+	pub fn lexical_form(&self) -> &str {
+		match self {
+			Literal::Typed(s, _) => s,
+			Literal::LangString(s, _) => s
+		}
+	}
+
+	/// Returns the datatype IRI of the literal.
+	///
+	/// This is `rdf:langString` for language-tagged strings.
+	pub fn datatype(&self) -> Iri<'_> {
+		match self {
+			Literal::Typed(_, ty) => ty.as_iri(),
+			Literal::LangString(_, _) => Iri::new(RDF_LANG_STRING).unwrap()
+		}
+	}
+}
+
+impl<T: Id> fmt::Display for Literal<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "\"")?;
+		for c in self.lexical_form().chars() {
+			match c {
+				'"' => write!(f, "\\\"")?,
+				'\\' => write!(f, "\\\\")?,
+				'\n' => write!(f, "\\n")?,
+				'\r' => write!(f, "\\r")?,
+				c => write!(f, "{}", c)?
+			}
+		}
+		write!(f, "\"")?;
+
+		match self {
+			Literal::Typed(_, ty) if ty.as_iri().as_str() == xsd::Datatype::String.as_str() => Ok(()),
+			Literal::Typed(_, ty) => write!(f, "^^<{}>", ty.as_iri()),
+			Literal::LangString(_, lang) => write!(f, "@{}", lang)
+		}
+	}
+}
+
+/// RDF term in object position.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub enum Term<T: Id> {
+	/// IRI or blank node.
+	Ref(Reference<T>),
+
+	/// Literal.
+	Literal(Literal<T>)
+}
+
+impl<T: Id> Term<T> {
+	/// Returns the blank node identifier of the term, if any.
+	pub fn as_blank(&self) -> Option<&BlankId> {
+		match self {
+			Term::Ref(Reference::Blank(id)) => Some(id),
+			_ => None
+		}
+	}
+}
+
+impl<T: Id> fmt::Display for Term<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Term::Ref(r) => fmt_reference(r, f),
+			Term::Literal(lit) => lit.fmt(f)
+		}
+	}
+}
+
+fn fmt_reference<T: Id>(r: &Reference<T>, f: &mut fmt::Formatter) -> fmt::Result {
+	match r {
+		Reference::Id(id) => write!(f, "<{}>", id.as_iri()),
+		Reference::Blank(id) => fmt::Display::fmt(id, f)
+	}
+}
+
+/// RDF quad.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Quad<T: Id> {
+	/// Subject.
+	pub subject: Reference<T>,
+
+	/// Predicate.
+	pub predicate: T,
+
+	/// Object.
+	pub object: Term<T>,
+
+	/// Graph name, or `None` for the default graph.
+	pub graph: Option<Reference<T>>
+}
+
+impl<T: Id> Quad<T> {
+	/// Creates a new quad.
+	pub fn new(subject: Reference<T>, predicate: T, object: Term<T>, graph: Option<Reference<T>>) -> Quad<T> {
+		Quad {
+			subject,
+			predicate,
+			object,
+			graph
+		}
+	}
+
+	/// Checks if the quad contains a blank node.
+	pub fn has_blank(&self) -> bool {
+		matches!(self.subject, Reference::Blank(_))
+		|| self.object.as_blank().is_some()
+		|| matches!(self.graph, Some(Reference::Blank(_)))
+	}
+}
+
+/// Writes the quad as an N-Quads statement, without the trailing newline.
+impl<T: Id> fmt::Display for Quad<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt_reference(&self.subject, f)?;
+		write!(f, " <{}> {}", self.predicate.as_iri(), self.object)?;
+		if let Some(graph) = &self.graph {
+			write!(f, " ")?;
+			fmt_reference(graph, f)?;
+		}
+		write!(f, " .")
+	}
+}
+
+impl<T: Id> fmt::Debug for Quad<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Display::fmt(self, f)
+	}
+}
+
+/// RDF dataset.
+pub type Dataset<T> = HashSet<Quad<T>>;
+
+/// Converts an expanded document into an RDF dataset.
+///
+/// Blank nodes are labelled `_:bN` using the [`Sequential`] generator.
+pub fn to_rdf<T: Id>(doc: &ExpandedDocument<T>) -> Dataset<T> {
+	to_rdf_with(doc, Sequential::new())
+}
+
+/// Converts an expanded document into an RDF dataset,
+/// labelling blank nodes with the given generator.
+pub fn to_rdf_with<T: Id, G: BlankIdGenerator>(doc: &ExpandedDocument<T>, generator: G) -> Dataset<T> {
+	let mut converter = Converter::new(generator, HashSet::new());
+	for object in doc {
+		converter.top_level(object);
+	}
+
+	converter.quads
+}
+
+/// Expanded document to RDF converter.
+///
+/// Generated quads are added to `quads`.
+struct Converter<T: Id, G: BlankIdGenerator, Q: Extend<Quad<T>>> {
+	/// Blank node labels.
+	labels: Relabeller<G>,
+
+	/// Generated quads.
+	quads: Q,
+
+	t: PhantomData<T>
+}
+
+impl<T: Id, G: BlankIdGenerator, Q: Extend<Quad<T>>> Converter<T, G, Q> {
+	fn new(generator: G, quads: Q) -> Converter<T, G, Q> {
+		Converter {
+			labels: Relabeller::new(generator),
+			quads,
+			t: PhantomData
+		}
+	}
+
+	fn emit(&mut self, quad: Quad<T>) {
+		self.quads.extend(Some(quad))
+	}
+
+	/// Converts a top-level object.
+	fn top_level(&mut self, object: &Indexed<Object<T>>) {
+		if let Object::Node(node) = object.inner() {
+			self.node(node, None);
+		}
+	}
+
+	fn fresh(&mut self) -> BlankId {
+		self.labels.fresh()
+	}
+
+	fn relabel(&mut self, r: &Reference<T>) -> Reference<T> {
+		self.labels.relabel_reference(r.clone())
+	}
+
+	fn subject(&mut self, node: &Node<T>) -> Option<Reference<T>> {
+		match &node.id {
+			Some(Lenient::Ok(id)) => Some(self.relabel(id)),
+			Some(Lenient::Unknown(_)) => None,
+			None => Some(Reference::Blank(self.fresh()))
+		}
+	}
+
+	/// Converts a node, returning its subject term.
+	///
+	/// Returns `None` if the node identifier is not a valid IRI or blank node identifier.
+	fn node(&mut self, node: &Node<T>, graph: Option<&Reference<T>>) -> Option<Reference<T>> {
+		let subject = self.subject(node)?;
+
+		for ty in &node.types {
+			if let Lenient::Ok(ty) = ty {
+				let ty = self.relabel(ty);
+				self.emit(Quad::new(subject.clone(), iri(RDF_TYPE), Term::Ref(ty), graph.cloned()));
+			}
+		}
+
+		for (prop, objects) in &node.properties {
+			if let Reference::Id(prop) = prop {
+				for object in objects {
+					if let Some(object) = self.object(object, graph) {
+						self.emit(Quad::new(subject.clone(), prop.clone(), object, graph.cloned()));
+					}
+				}
+			}
+		}
+
+		for (prop, nodes) in &node.reverse_properties {
+			if let Reference::Id(prop) = prop {
+				for reverse_node in nodes {
+					if let Some(reverse_subject) = self.node(reverse_node.inner(), graph) {
+						self.emit(Quad::new(reverse_subject, prop.clone(), Term::Ref(subject.clone()), graph.cloned()));
+					}
+				}
+			}
+		}
+
+		if let Some(included) = &node.included {
+			for included_node in included {
+				self.node(included_node.inner(), graph);
+			}
+		}
+
+		if let Some(objects) = &node.graph {
+			for object in objects {
+				if let Object::Node(graph_node) = object.inner() {
+					self.node(graph_node, Some(&subject));
+				}
+			}
+		}
+
+		Some(subject)
+	}
+
+	/// Converts an object, returning its term.
+	fn object(&mut self, object: &Indexed<Object<T>>, graph: Option<&Reference<T>>) -> Option<Term<T>> {
+		match object.inner() {
+			Object::Node(node) => self.node(node, graph).map(Term::Ref),
+			Object::Value(value) => value_to_literal(value).map(Term::Literal),
+			Object::List(items) => Some(self.list(items, graph))
+		}
+	}
+
+	/// Converts a list into a `rdf:first`/`rdf:rest` chain, returning its head.
+	fn list(&mut self, items: &[Indexed<Object<T>>], graph: Option<&Reference<T>>) -> Term<T> {
+		let mut head = Term::Ref(Reference::Id(iri(RDF_NIL)));
+		for item in items.iter().rev() {
+			if let Some(first) = self.object(item, graph) {
+				let node = Reference::Blank(self.fresh());
+				self.emit(Quad::new(node.clone(), iri(RDF_FIRST), first, graph.cloned()));
+				self.emit(Quad::new(node.clone(), iri(RDF_REST), head, graph.cloned()));
+				head = Term::Ref(node)
+			}
+		}
+
+		head
+	}
+}
+
+/// Converts a value object into an RDF literal.
+///
+/// Returns `None` for `null` values.
+pub fn value_to_literal<T: Id>(value: &Value<T>) -> Option<Literal<T>> {
+	match value {
+		Value::Json(json) => Some(Literal::Typed(util::canonical_json(json), iri(RDF_JSON))),
+		Value::LangString(s) => match s.language() {
+			Some(lang) => Some(Literal::LangString(s.as_str().to_string(), lang.as_str().to_string())),
+			None => Some(Literal::Typed(s.as_str().to_string(), iri(xsd::Datatype::String.as_str())))
+		},
+		Value::Literal(lit, ty) => {
+			let (lexical, default_ty) = match lit {
+				JsonLiteral::Null => return None,
+				JsonLiteral::Boolean(b) => (b.to_string(), xsd::Datatype::Boolean),
+				JsonLiteral::String(s) => (s.clone(), xsd::Datatype::String),
+				JsonLiteral::Number(n) => {
+					let is_double = ty.as_ref().map(|ty| ty.as_iri().as_str() == xsd::Datatype::Double.as_str()).unwrap_or(false);
+					match integer_lexical_form(n) {
+						Some(lexical) if !is_double => (lexical, xsd::Datatype::Integer),
+						_ => (xsd::canonical_double(f64::from(*n)), xsd::Datatype::Double)
+					}
+				}
+			};
+
+			let ty = match ty {
+				Some(ty) => ty.clone(),
+				None => iri(default_ty.as_str())
+			};
+
+			Some(Literal::Typed(lexical, ty))
+		}
+	}
+}
+
+/// Returns the canonical lexical form of the given number if it is an integer lower than 10^21.
+fn integer_lexical_form(n: &json::number::Number) -> Option<String> {
+	let (positive, mantissa, exponent) = n.as_parts();
+	let mut digits = mantissa.to_string();
+
+	if exponent >= 0 {
+		for _ in 0..exponent {
+			digits.push('0')
+		}
+	} else {
+		let exponent = (-(exponent as i32)) as usize;
+		if digits.len() < exponent || digits[(digits.len() - exponent)..].bytes().any(|c| c != b'0') {
+			return None
+		}
+
+		digits.truncate(digits.len() - exponent)
+	}
+
+	let digits = digits.trim_start_matches('0');
+	if digits.len() > 21 {
+		None
+	} else if digits.is_empty() {
+		Some("0".to_string())
+	} else if positive {
+		Some(digits.to_string())
+	} else {
+		Some(format!("-{}", digits))
+	}
+}
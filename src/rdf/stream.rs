@@ -0,0 +1,84 @@
+use std::collections::VecDeque;
+use crate::{
+	Id,
+	Indexed,
+	Object,
+	BlankIdGenerator,
+	Sequential
+};
+use super::{
+	Quad,
+	Converter
+};
+
+/// Iterator over the RDF quads of a stream of expanded top-level objects.
+///
+/// Each top-level object is converted as soon as it is read from the underlying iterator,
+/// such as an [`ExpandReader`](crate::expansion::ExpandReader),
+/// without building the whole expanded document or its dataset.
+/// Only the blank node labels are kept from one object to another,
+/// so that a blank node identifier is given the same label across the document.
+///
+/// Contrarily to [`to_rdf`](super::to_rdf), duplicate quads are not removed.
+/// Errors of the underlying iterator are forwarded.
+///
+/// # Example
+/// ```
+/// use json_ld::{JsonContext, NoLoader, expansion::{self, ExpandReader}, rdf};
+/// # use iref::IriBuf;
+/// let input = r#"{
+///     "@context": { "name": "http://schema.org/name" },
+///     "@graph": [
+///         { "@id": "http://example.org/jane", "name": "Jane Doe" },
+///         { "@id": "http://example.org/john", "name": "John Smith" }
+///     ]
+/// }"#;
+///
+/// let mut loader = NoLoader;
+/// let nodes = ExpandReader::<_, IriBuf, JsonContext<IriBuf>, _>::new(input.as_bytes(), None, JsonContext::new(None), &mut loader, expansion::Options::default());
+/// let lines: Vec<String> = rdf::quads(nodes).map(|quad| quad.unwrap().to_string()).collect();
+/// assert_eq!(lines, [
+///     r#"<http://example.org/jane> <http://schema.org/name> "Jane Doe" ."#,
+///     r#"<http://example.org/john> <http://schema.org/name> "John Smith" ."#
+/// ]);
+/// ```
+pub struct Quads<I, T: Id, G: BlankIdGenerator> {
+	/// Expanded top-level objects.
+	objects: I,
+
+	/// Converter, buffering the quads of the current object.
+	converter: Converter<T, G, VecDeque<Quad<T>>>
+}
+
+/// Converts a stream of expanded top-level objects into RDF quads.
+///
+/// Blank nodes are labelled `_:bN` using the [`Sequential`] generator.
+pub fn quads<I: IntoIterator<Item = Result<Indexed<Object<T>>, E>>, T: Id, E>(objects: I) -> Quads<I::IntoIter, T, Sequential> {
+	quads_with(objects, Sequential::new())
+}
+
+/// Converts a stream of expanded top-level objects into RDF quads,
+/// labelling blank nodes with the given generator.
+pub fn quads_with<I: IntoIterator<Item = Result<Indexed<Object<T>>, E>>, T: Id, E, G: BlankIdGenerator>(objects: I, generator: G) -> Quads<I::IntoIter, T, G> {
+	Quads {
+		objects: objects.into_iter(),
+		converter: Converter::new(generator, VecDeque::new())
+	}
+}
+
+impl<I: Iterator<Item = Result<Indexed<Object<T>>, E>>, T: Id, E, G: BlankIdGenerator> Iterator for Quads<I, T, G> {
+	type Item = Result<Quad<T>, E>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			if let Some(quad) = self.converter.quads.pop_front() {
+				return Some(Ok(quad))
+			}
+
+			match self.objects.next()? {
+				Ok(object) => self.converter.top_level(&object),
+				Err(e) => return Some(Err(e))
+			}
+		}
+	}
+}
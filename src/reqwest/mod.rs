@@ -1,6 +1,7 @@
 //! Simple document and context loader based on [`reqwest`](https://crates.io/crates/reqwest)
 
 use std::collections::HashMap;
+use std::sync::Mutex;
 use futures::future::{FutureExt, BoxFuture};
 use iref::{Iri, IriBuf};
 use json::JsonValue;
@@ -8,6 +9,8 @@ use crate::{
 	Error,
 	ErrorCode,
 	RemoteDocument,
+	SharedLoader,
+	LoaderId,
 	context::{
 		self,
 		RemoteContext
@@ -44,32 +47,72 @@ pub async fn load_remote_json_ld_document(url: Iri<'_>) -> Result<RemoteDocument
 	}
 }
 
+/// Remote document loader.
+///
+/// Loaded documents are cached. The cache is internally synchronized so that
+/// a single loader can be shared between tasks as a [`SharedLoader`].
 pub struct Loader {
-	cache: HashMap<IriBuf, RemoteDocument>
+	id: LoaderId,
+	cache: Mutex<HashMap<IriBuf, RemoteDocument>>
 }
 
 impl Loader {
 	pub fn new() -> Loader {
 		Loader {
-			cache: HashMap::new()
+			// Every instance loads the same documents.
+			id: LoaderId::of("reqwest"),
+			cache: Mutex::new(HashMap::new())
 		}
 	}
 
-	pub async fn load(&mut self, url: Iri<'_>) -> Result<RemoteDocument, Error> {
+	/// Loads the given documents concurrently, filling the cache.
+	///
+	/// Errors are ignored.
+	pub async fn prefetch(&self, urls: &[IriBuf]) {
+		futures::future::join_all(urls.iter().map(|url| self.load(url.as_iri()).map(|_| ()))).await;
+	}
+
+	pub async fn load(&self, url: Iri<'_>) -> Result<RemoteDocument, Error> {
 		let url = IriBuf::from(url);
-		match self.cache.get(&url) {
+		let cached = self.cache.lock().unwrap().get(&url).cloned();
+		match cached {
 			Some(doc) => {
-				Ok(doc.clone())
+				Ok(doc)
 			},
 			None => {
 				let doc = load_remote_json_ld_document(url.as_iri()).await?;
-				self.cache.insert(url, doc.clone());
+				self.cache.lock().unwrap().insert(url, doc.clone());
 				Ok(doc)
 			}
 		}
 	}
 }
 
+impl SharedLoader for Loader {
+	type Document = JsonValue;
+
+	fn load_shared<'a>(&'a self, url: Iri<'_>) -> BoxFuture<'a, Result<RemoteDocument, Error>> {
+		let url = IriBuf::from(url);
+		async move {
+			self.load(url.as_iri()).await
+		}.boxed()
+	}
+
+	fn prefetch_shared<'a>(&'a self, urls: Vec<IriBuf>) -> BoxFuture<'a, ()> {
+		async move {
+			self.prefetch(&urls).await
+		}.boxed()
+	}
+
+	fn supports_prefetch(&self) -> bool {
+		true
+	}
+
+	fn loader_id(&self) -> Option<LoaderId> {
+		Some(self.id)
+	}
+}
+
 impl context::Loader for Loader {
 	type Output = JsonValue;
 
@@ -95,6 +138,20 @@ impl context::Loader for Loader {
 			}
 		}.boxed()
 	}
+
+	fn prefetch_contexts<'a>(&'a mut self, urls: Vec<IriBuf>) -> BoxFuture<'a, ()> {
+		async move {
+			self.prefetch(&urls).await
+		}.boxed()
+	}
+
+	fn supports_prefetch(&self) -> bool {
+		true
+	}
+
+	fn loader_id(&self) -> Option<LoaderId> {
+		Some(self.id)
+	}
 }
 
 impl From<reqwest::Error> for Error {
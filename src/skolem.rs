@@ -0,0 +1,183 @@
+//! Skolemization of blank nodes.
+//!
+//! Skolemization replaces every blank node identifier with a fresh IRI, called a
+//! [Skolem IRI](https://www.w3.org/TR/rdf11-concepts/#section-skolemization),
+//! of the form `https://{authority}/.well-known/genid/{name}`.
+//! This is useful to store documents in systems that do not support blank nodes.
+//! Deskolemization is the inverse transform, turning Skolem IRIs back into blank node identifiers.
+
+use iref::{Iri, IriBuf};
+use crate::{
+	Id,
+	BlankId,
+	Reference,
+	ExpandedDocument,
+	BlankIdGenerator,
+	relabel
+};
+
+/// Path of Skolem IRIs.
+pub const WELL_KNOWN_GENID: &str = "/.well-known/genid/";
+
+/// Skolemizer.
+///
+/// # Example
+/// ```
+/// use json_ld::{Reference, BlankId, Node, Object, ExpandedDocument, skolem::Skolemizer};
+/// # use iref::IriBuf;
+/// let doc = || -> ExpandedDocument<IriBuf> {
+///     vec![
+///         Object::Node(Node::with_id(Reference::Blank(BlankId::new("alice")).into())).into()
+///     ].into_iter().collect()
+/// };
+///
+/// let skolemizer = Skolemizer::new("example.org").unwrap();
+/// let skolemized = skolemizer.skolemize(doc());
+/// let id = skolemized.iter().next().unwrap().id().unwrap();
+/// assert_eq!(id.as_str(), "https://example.org/.well-known/genid/alice");
+///
+/// assert!(skolemizer.deskolemize(skolemized) == doc());
+/// ```
+#[derive(Clone)]
+pub struct Skolemizer {
+	/// Prefix of the generated IRIs, ending with `/.well-known/genid/`.
+	prefix: String
+}
+
+impl Skolemizer {
+	/// Creates a new skolemizer generating `https` IRIs under the given authority.
+	pub fn new(authority: &str) -> Result<Skolemizer, iref::Error> {
+		Self::with_scheme("https", authority)
+	}
+
+	/// Creates a new skolemizer generating IRIs with the given scheme, under the given authority.
+	pub fn with_scheme(scheme: &str, authority: &str) -> Result<Skolemizer, iref::Error> {
+		let prefix = format!("{}://{}{}", scheme, authority, WELL_KNOWN_GENID);
+		IriBuf::new(&prefix)?;
+		Ok(Skolemizer {
+			prefix
+		})
+	}
+
+	/// Returns the prefix of the generated IRIs.
+	pub fn prefix(&self) -> &str {
+		&self.prefix
+	}
+
+	/// Returns the Skolem IRI of the given blank node identifier.
+	///
+	/// Characters of the blank node name that are not unreserved IRI characters are
+	/// percent-encoded.
+	pub fn skolem_iri(&self, id: &BlankId) -> IriBuf {
+		let mut iri = self.prefix.clone();
+		for b in id.name().bytes() {
+			if b.is_ascii_alphanumeric() || b"-._~".contains(&b) {
+				iri.push(b as char)
+			} else {
+				iri.push_str(&format!("%{:02X}", b))
+			}
+		}
+
+		IriBuf::new(&iri).unwrap()
+	}
+
+	/// Returns the blank node identifier of the given Skolem IRI,
+	/// or `None` if the IRI has not been generated by this skolemizer.
+	pub fn blank_id(&self, iri: Iri) -> Option<BlankId> {
+		let encoded = iri.as_str().strip_prefix(self.prefix.as_str())?;
+		let mut name = Vec::with_capacity(encoded.len());
+		let mut bytes = encoded.bytes();
+		while let Some(b) = bytes.next() {
+			if b == b'%' {
+				let hex = [bytes.next()?, bytes.next()?];
+				name.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?)
+			} else {
+				name.push(b)
+			}
+		}
+
+		String::from_utf8(name).ok().map(|name| BlankId::new(&name))
+	}
+
+	/// Skolemizes the given reference.
+	pub fn skolemize_reference<T: Id>(&self, r: Reference<T>) -> Reference<T> {
+		match r {
+			Reference::Blank(id) => Reference::Id(T::from_iri(self.skolem_iri(&id).as_iri())),
+			r => r
+		}
+	}
+
+	/// Deskolemizes the given reference.
+	pub fn deskolemize_reference<T: Id>(&self, r: Reference<T>) -> Reference<T> {
+		match r {
+			Reference::Id(id) => match self.blank_id(id.as_iri()) {
+				Some(blank) => Reference::Blank(blank),
+				None => Reference::Id(id)
+			},
+			r => r
+		}
+	}
+
+	/// Replaces every blank node identifier of the given document with its Skolem IRI.
+	///
+	/// This includes node identifiers, types, property values,
+	/// reverse properties and graph names.
+	/// Anonymous nodes are left untouched,
+	/// use [`skolemize_labelled`](Skolemizer::skolemize_labelled) to give them a Skolem IRI as well.
+	pub fn skolemize<T: Id>(&self, doc: ExpandedDocument<T>) -> ExpandedDocument<T> {
+		doc.into_iter().map(|object| {
+			object.map_inner(|object| object.map_references(&mut |r| self.skolemize_reference(r)))
+		}).collect()
+	}
+
+	/// Labels the anonymous nodes of the given document then replaces every blank node
+	/// identifier with its Skolem IRI.
+	///
+	/// The document is first [relabelled](crate::relabel) with the given generator,
+	/// with `label_anonymous` set to `true`:
+	/// identified blank nodes also receive a new label, so that it cannot collide with the
+	/// labels given to anonymous nodes.
+	/// Deskolemizing the result hence gives back the new labels, not the original ones.
+	///
+	/// # Example
+	/// ```
+	/// use json_ld::{Reference, BlankId, Node, Object, ExpandedDocument, Sequential, skolem::Skolemizer};
+	/// # use iref::IriBuf;
+	/// let doc: ExpandedDocument<IriBuf> = vec![
+	///     Object::Node(Node::with_id(Reference::Blank(BlankId::new("alice")).into())).into(),
+	///     Object::Node(Node::new()).into()
+	/// ].into_iter().collect();
+	///
+	/// let skolemizer = Skolemizer::new("example.org").unwrap();
+	/// let skolemized = skolemizer.skolemize_labelled(doc, Sequential::new());
+	/// let mut ids: Vec<_> = skolemized.iter().map(|object| object.id().unwrap().as_str().to_string()).collect();
+	/// ids.sort();
+	/// assert_eq!(ids, [
+	///     "https://example.org/.well-known/genid/b0",
+	///     "https://example.org/.well-known/genid/b1"
+	/// ]);
+	/// ```
+	pub fn skolemize_labelled<T: Id, G: BlankIdGenerator>(&self, doc: ExpandedDocument<T>, generator: G) -> ExpandedDocument<T> {
+		self.skolemize(relabel(doc, generator, true))
+	}
+
+	/// Replaces every Skolem IRI generated by this skolemizer in the given document with
+	/// the corresponding blank node identifier.
+	pub fn deskolemize<T: Id>(&self, doc: ExpandedDocument<T>) -> ExpandedDocument<T> {
+		doc.into_iter().map(|object| {
+			object.map_inner(|object| object.map_references(&mut |r| self.deskolemize_reference(r)))
+		}).collect()
+	}
+}
+
+/// Replaces every blank node identifier of the given document with a Skolem IRI
+/// under the given authority.
+pub fn skolemize<T: Id>(doc: ExpandedDocument<T>, authority: &str) -> Result<ExpandedDocument<T>, iref::Error> {
+	Ok(Skolemizer::new(authority)?.skolemize(doc))
+}
+
+/// Replaces every Skolem IRI under the given authority with the corresponding
+/// blank node identifier.
+pub fn deskolemize<T: Id>(doc: ExpandedDocument<T>, authority: &str) -> Result<ExpandedDocument<T>, iref::Error> {
+	Ok(Skolemizer::new(authority)?.deskolemize(doc))
+}
@@ -1,4 +1,6 @@
 use std::convert::TryFrom;
+use json::JsonValue;
+use crate::util::AsJson;
 use super::Keyword;
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
@@ -227,3 +229,21 @@ impl Container {
 		}
 	}
 }
+
+impl AsJson for ContainerType {
+	fn as_json(&self) -> JsonValue {
+		Keyword::from(*self).into_str().into()
+	}
+}
+
+/// Produces `null` for the empty container, a single keyword for a single container type,
+/// or an array of keywords.
+impl AsJson for Container {
+	fn as_json(&self) -> JsonValue {
+		match self.as_slice() {
+			[] => JsonValue::Null,
+			[c] => c.as_json(),
+			containers => containers.as_json()
+		}
+	}
+}
@@ -0,0 +1,148 @@
+use std::fmt::Write;
+use ::json::JsonValue;
+
+/// Serializes a JSON value following the [JSON Canonicalization Scheme](https://tools.ietf.org/html/rfc8785)
+/// (JCS, RFC 8785).
+///
+/// Object entries are sorted by key, comparing their UTF-16 code units,
+/// numbers are serialized as ECMAScript does, and no whitespace is emitted,
+/// so that equal values always have the same serialization.
+/// As with ECMAScript, non-finite numbers are serialized as `null`.
+///
+/// # Example
+/// ```
+/// use json_ld::util::canonical_json;
+/// let value = json::parse(r#"{ "b": [1.0, 1e21, 0.000001], "a": "é\n" }"#).unwrap();
+/// assert_eq!(canonical_json(&value), "{\"a\":\"\u{e9}\\n\",\"b\":[1,1e+21,0.000001]}");
+/// ```
+///
+/// Numbers switch to the exponential notation from `1e21` and below `1e-6`,
+/// the smallest subnormal is written with its shortest representation, and `-0` is written `0`:
+/// ```
+/// # use json_ld::util::canonical_json;
+/// let value = json::parse("[1e20, 1e21, 1e-6, 1e-7, 5e-324, -0, -0.0]").unwrap();
+/// assert_eq!(canonical_json(&value), "[100000000000000000000,1e+21,0.000001,1e-7,5e-324,0,0]");
+/// ```
+///
+/// Keys are sorted by UTF-16 code units, so that a key starting with a supplementary character
+/// (encoded with a surrogate pair) comes before a key starting with U+E000.
+/// Only `"`, `\` and control characters are escaped:
+/// ```
+/// # use json_ld::util::canonical_json;
+/// let value = json::parse(r#"{ "\ue000": 1, "\ud83d\ude00": 2, "a": "\u001f\u007f\u20ac\"\\/" }"#).unwrap();
+/// assert_eq!(canonical_json(&value), "{\"a\":\"\\u001f\u{7f}\u{20ac}\\\"\\\\/\",\"\u{1f600}\":2,\"\u{e000}\":1}");
+/// ```
+pub fn canonical_json(value: &JsonValue) -> String {
+	let mut result = String::new();
+	write_value(value, &mut result);
+	result
+}
+
+fn write_value(value: &JsonValue, out: &mut String) {
+	match value {
+		JsonValue::Null => out.push_str("null"),
+		JsonValue::Boolean(b) => out.push_str(if *b { "true" } else { "false" }),
+		JsonValue::Number(n) => {
+			// The conversion provided by `json` is not always correctly rounded.
+			let n = n.to_string().parse().unwrap_or_else(|_| f64::from(*n));
+			write_number(n, out)
+		},
+		JsonValue::Short(s) => write_string(s.as_str(), out),
+		JsonValue::String(s) => write_string(s, out),
+		JsonValue::Array(items) => {
+			out.push('[');
+			for (i, item) in items.iter().enumerate() {
+				if i > 0 {
+					out.push(',')
+				}
+
+				write_value(item, out)
+			}
+			out.push(']')
+		},
+		JsonValue::Object(obj) => {
+			let mut entries: Vec<_> = obj.iter().collect();
+			entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+
+			out.push('{');
+			for (i, (key, value)) in entries.into_iter().enumerate() {
+				if i > 0 {
+					out.push(',')
+				}
+
+				write_string(key, out);
+				out.push(':');
+				write_value(value, out)
+			}
+			out.push('}')
+		}
+	}
+}
+
+fn write_string(s: &str, out: &mut String) {
+	out.push('"');
+	for c in s.chars() {
+		match c {
+			'"' => out.push_str("\\\""),
+			'\\' => out.push_str("\\\\"),
+			'\u{8}' => out.push_str("\\b"),
+			'\u{c}' => out.push_str("\\f"),
+			'\n' => out.push_str("\\n"),
+			'\r' => out.push_str("\\r"),
+			'\t' => out.push_str("\\t"),
+			c if c < ' ' => write!(out, "\\u{:04x}", c as u32).unwrap(),
+			c => out.push(c)
+		}
+	}
+	out.push('"')
+}
+
+/// Writes a number as the ECMAScript `Number.prototype.toString` method.
+fn write_number(n: f64, out: &mut String) {
+	if !n.is_finite() {
+		out.push_str("null");
+		return
+	}
+
+	if n == 0.0 {
+		// Also covers `-0`.
+		out.push('0');
+		return
+	}
+
+	if n < 0.0 {
+		out.push('-')
+	}
+
+	// Shortest digits `d` and exponent `e` such that `|n| = 0.d * 10^e`.
+	let scientific = format!("{:e}", n.abs());
+	let (mantissa, exponent) = scientific.split_at(scientific.find('e').unwrap());
+	let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+	let k = digits.len() as i32;
+	let e = exponent[1..].parse::<i32>().unwrap() + 1;
+
+	if k <= e && e <= 21 {
+		out.push_str(&digits);
+		for _ in 0..(e - k) {
+			out.push('0')
+		}
+	} else if 0 < e && e <= 21 {
+		out.push_str(&digits[..(e as usize)]);
+		out.push('.');
+		out.push_str(&digits[(e as usize)..])
+	} else if -6 < e && e <= 0 {
+		out.push_str("0.");
+		for _ in 0..(-e) {
+			out.push('0')
+		}
+		out.push_str(&digits)
+	} else {
+		out.push_str(&digits[..1]);
+		if k > 1 {
+			out.push('.');
+			out.push_str(&digits[1..])
+		}
+
+		write!(out, "e{}{}", if e > 0 { '+' } else { '-' }, (e - 1).abs()).unwrap()
+	}
+}
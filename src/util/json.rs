@@ -1,4 +1,5 @@
 use std::collections::HashSet;
+use indexmap::IndexSet;
 use json::JsonValue;
 use langtag::{
 	LanguageTag,
@@ -7,6 +8,11 @@ use langtag::{
 
 pub trait AsJson {
 	fn as_json(&self) -> JsonValue;
+
+	/// Serializes this value into canonical JSON (see [`canonical_json`](super::canonical_json)).
+	fn as_canonical_json(&self) -> String {
+		super::canonical_json(&self.as_json())
+	}
 }
 
 impl AsJson for JsonValue {
@@ -73,6 +79,17 @@ impl<T: AsJson> AsJson for HashSet<T> {
 	}
 }
 
+impl<T: AsJson> AsJson for IndexSet<T> {
+	fn as_json(&self) -> JsonValue {
+		let mut ary = Vec::with_capacity(self.len());
+		for item in self {
+			ary.push(item.as_json())
+		}
+
+		JsonValue::Array(ary)
+	}
+}
+
 pub fn json_ld_eq(a: &JsonValue, b: &JsonValue) -> bool {
 	match (a, b) {
 		(JsonValue::Array(a), JsonValue::Array(b)) if a.len() == b.len() => {
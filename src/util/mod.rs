@@ -1,11 +1,15 @@
 //! Utility functions.
 
+use std::fmt;
 use std::hash::{Hash, Hasher};
-use std::collections::{HashSet, HashMap, hash_map::DefaultHasher};
+use std::collections::hash_map::DefaultHasher;
+use indexmap::{IndexSet, IndexMap};
 use ::json::{JsonValue, number::Number};
 
 mod json;
+mod jcs;
 pub use self::json::*;
+pub use self::jcs::*;
 
 pub fn as_array(json: &JsonValue) -> &[JsonValue] {
 	match json {
@@ -14,6 +18,63 @@ pub fn as_array(json: &JsonValue) -> &[JsonValue] {
 	}
 }
 
+/// 128-bit [FNV-1a](https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function) hasher.
+///
+/// Unlike [`DefaultHasher`], the hash of a given sequence of bytes is fixed and
+/// does not depend on the platform: integers are written in little endian order.
+/// Strings written with [`fmt::Write`] are hashed through their bytes.
+pub(crate) struct Fnv1a(u128);
+
+impl Fnv1a {
+	const OFFSET: u128 = 0x6c62272e07bb014262b821756295c58d;
+	const PRIME: u128 = 0x0000000001000000000000000000013B;
+
+	pub fn new() -> Fnv1a {
+		Fnv1a(Self::OFFSET)
+	}
+
+	/// Returns the full 128-bit hash.
+	pub fn finish128(&self) -> u128 {
+		self.0
+	}
+}
+
+impl Hasher for Fnv1a {
+	fn write(&mut self, bytes: &[u8]) {
+		for b in bytes {
+			self.0 ^= *b as u128;
+			self.0 = self.0.wrapping_mul(Self::PRIME)
+		}
+	}
+
+	fn write_u16(&mut self, i: u16) {
+		self.write(&i.to_le_bytes())
+	}
+
+	fn write_u32(&mut self, i: u32) {
+		self.write(&i.to_le_bytes())
+	}
+
+	fn write_u64(&mut self, i: u64) {
+		self.write(&i.to_le_bytes())
+	}
+
+	fn write_usize(&mut self, i: usize) {
+		self.write_u64(i as u64)
+	}
+
+	fn finish(&self) -> u64 {
+		self.0 as u64
+	}
+}
+
+impl fmt::Write for Fnv1a {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		self.write(s.as_bytes());
+		Ok(())
+	}
+}
+
 pub fn hash_json_number<H: Hasher>(number: &Number, hasher: &mut H) {
 	let (positive, mantissa, exponent) = number.as_parts();
 	positive.hash(hasher);
@@ -21,29 +82,94 @@ pub fn hash_json_number<H: Hasher>(number: &Number, hasher: &mut H) {
 	exponent.hash(hasher);
 }
 
+/// Hashes a JSON value such that equal values have the same hash.
+///
+/// Object entries are combined independently of their order,
+/// and numbers are normalized so that, for instance, `1` and `1.0` have the same hash.
+/// Nothing is allocated.
+///
+/// # Example
+/// ```
+/// use std::hash::Hasher;
+/// use std::collections::hash_map::DefaultHasher;
+/// use json_ld::util::hash_json;
+/// let hash = |value: &str| {
+///     let mut hasher = DefaultHasher::new();
+///     hash_json(&json::parse(value).unwrap(), &mut hasher);
+///     hasher.finish()
+/// };
+///
+/// assert_eq!(hash(r#"{ "a": [1, 0.0], "b": "c" }"#), hash(r#"{ "b": "c", "a": [1.0, -0] }"#));
+/// assert_ne!(hash(r#"{ "a": 1 }"#), hash(r#"{ "a": 10 }"#));
+/// assert_ne!(hash("[[1], 2]"), hash("[1, [2]]"));
+/// ```
 pub fn hash_json<H: Hasher>(value: &JsonValue, hasher: &mut H) {
 	match value {
-		JsonValue::Null => (),
-		JsonValue::Boolean(b) => b.hash(hasher),
-		JsonValue::Number(n) => hash_json_number(n, hasher),
-		JsonValue::Short(str) => str.hash(hasher),
-		JsonValue::String(str) => str.hash(hasher),
-		JsonValue::Array(ary) => {
-			for item in ary {
+		JsonValue::Null => hasher.write_u8(0),
+		JsonValue::Boolean(b) => {
+			hasher.write_u8(1);
+			b.hash(hasher)
+		},
+		JsonValue::Number(n) => {
+			hasher.write_u8(2);
+			hash_normalized_json_number(n, hasher)
+		},
+		JsonValue::Short(s) => {
+			hasher.write_u8(3);
+			s.as_str().hash(hasher)
+		},
+		JsonValue::String(s) => {
+			hasher.write_u8(3);
+			s.as_str().hash(hasher)
+		},
+		JsonValue::Array(items) => {
+			hasher.write_u8(4);
+			hasher.write_usize(items.len());
+			for item in items {
 				hash_json(item, hasher)
 			}
 		},
 		JsonValue::Object(obj) => {
-			// in JSON, the order of elements matters, so we don't need to be vigilant here.
+			// Entries must be combined with a associative and commutative operation •.
+			// (u64, •, 0) must form a commutative monoid.
+			// This is satisfied by • = u64::wrapping_add.
+			let mut hash = 0;
 			for (key, value) in obj.iter() {
-				key.hash(hasher);
-				hash_json(value, hasher);
+				let mut h = DefaultHasher::new();
+				key.hash(&mut h);
+				hash_json(value, &mut h);
+				hash = u64::wrapping_add(hash, h.finish());
 			}
+
+			hasher.write_u8(5);
+			hasher.write_usize(obj.len());
+			hasher.write_u64(hash)
+		}
+	}
+}
+
+/// Hashes a number independently of its representation,
+/// by removing the trailing zeros of its mantissa.
+fn hash_normalized_json_number<H: Hasher>(number: &Number, hasher: &mut H) {
+	if number.is_nan() {
+		hasher.write_u8(0)
+	} else if number.is_zero() {
+		hasher.write_u8(1)
+	} else {
+		let (positive, mut mantissa, mut exponent) = number.as_parts();
+		while mantissa % 10 == 0 {
+			mantissa /= 10;
+			exponent = exponent.wrapping_add(1)
 		}
+
+		hasher.write_u8(2);
+		positive.hash(hasher);
+		mantissa.hash(hasher);
+		exponent.hash(hasher)
 	}
 }
 
-pub fn hash_set<T: Hash, H: Hasher>(set: &HashSet<T>, hasher: &mut H) {
+pub fn hash_set<T: Hash, H: Hasher>(set: &IndexSet<T>, hasher: &mut H) {
 	// Elements must be combined with a associative and commutative operation •.
 	// (u64, •, 0) must form a commutative monoid.
 	// This is satisfied by • = u64::wrapping_add.
@@ -57,14 +183,14 @@ pub fn hash_set<T: Hash, H: Hasher>(set: &HashSet<T>, hasher: &mut H) {
 	hasher.write_u64(hash);
 }
 
-pub fn hash_set_opt<T: Hash, H: Hasher>(set_opt: &Option<HashSet<T>>, hasher: &mut H) {
+pub fn hash_set_opt<T: Hash, H: Hasher>(set_opt: &Option<IndexSet<T>>, hasher: &mut H) {
 	match set_opt.as_ref() {
 		Some(set) => hash_set(set, hasher),
 		None => ()
 	}
 }
 
-pub fn hash_map<K: Hash, V: Hash, H: Hasher>(map: &HashMap<K, V>, hasher: &mut H) {
+pub fn hash_map<K: Hash, V: Hash, H: Hasher>(map: &IndexMap<K, V>, hasher: &mut H) {
 	// Elements must be combined with a associative and commutative operation •.
 	// (u64, •, 0) must form a commutative monoid.
 	// This is satisfied by • = u64::wrapping_add.
@@ -0,0 +1,783 @@
+//! XML Schema datatypes.
+//!
+//! Typed literal values found in JSON-LD documents are most of the time typed using the
+//! [XML Schema](https://www.w3.org/TR/xmlschema11-2/) datatypes.
+//! This module provides the [`Datatype`] type enumerating the most common of them.
+
+use std::fmt;
+use std::convert::TryFrom;
+use iref::{Iri, AsIri};
+
+/// XML Schema namespace.
+pub const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+
+/// Well-known XML Schema datatype.
+///
+/// This type implements [`AsIri`] and can be converted from an [`Iri`], which means that it
+/// can be used as a [`Vocab`](crate::Vocab) to build a [`Lexicon`](crate::Lexicon).
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Datatype {
+	/// `xsd:string`.
+	String,
+
+	/// `xsd:boolean`.
+	Boolean,
+
+	/// `xsd:decimal`.
+	Decimal,
+
+	/// `xsd:integer`.
+	Integer,
+
+	/// `xsd:long`.
+	Long,
+
+	/// `xsd:int`.
+	Int,
+
+	/// `xsd:short`.
+	Short,
+
+	/// `xsd:byte`.
+	Byte,
+
+	/// `xsd:nonNegativeInteger`.
+	NonNegativeInteger,
+
+	/// `xsd:positiveInteger`.
+	PositiveInteger,
+
+	/// `xsd:nonPositiveInteger`.
+	NonPositiveInteger,
+
+	/// `xsd:negativeInteger`.
+	NegativeInteger,
+
+	/// `xsd:unsignedLong`.
+	UnsignedLong,
+
+	/// `xsd:unsignedInt`.
+	UnsignedInt,
+
+	/// `xsd:unsignedShort`.
+	UnsignedShort,
+
+	/// `xsd:unsignedByte`.
+	UnsignedByte,
+
+	/// `xsd:double`.
+	Double,
+
+	/// `xsd:float`.
+	Float,
+
+	/// `xsd:dateTime`.
+	DateTime,
+
+	/// `xsd:date`.
+	Date,
+
+	/// `xsd:anyURI`.
+	AnyUri
+}
+
+impl Datatype {
+	/// Returns the local name of the datatype in the XML Schema namespace.
+	pub fn name(&self) -> &'static str {
+		use Datatype::*;
+		match self {
+			String => "string",
+			Boolean => "boolean",
+			Decimal => "decimal",
+			Integer => "integer",
+			Long => "long",
+			Int => "int",
+			Short => "short",
+			Byte => "byte",
+			NonNegativeInteger => "nonNegativeInteger",
+			PositiveInteger => "positiveInteger",
+			NonPositiveInteger => "nonPositiveInteger",
+			NegativeInteger => "negativeInteger",
+			UnsignedLong => "unsignedLong",
+			UnsignedInt => "unsignedInt",
+			UnsignedShort => "unsignedShort",
+			UnsignedByte => "unsignedByte",
+			Double => "double",
+			Float => "float",
+			DateTime => "dateTime",
+			Date => "date",
+			AnyUri => "anyURI"
+		}
+	}
+
+	/// Returns the full IRI of the datatype as a string.
+	pub fn as_str(&self) -> &'static str {
+		use Datatype::*;
+		match self {
+			String => "http://www.w3.org/2001/XMLSchema#string",
+			Boolean => "http://www.w3.org/2001/XMLSchema#boolean",
+			Decimal => "http://www.w3.org/2001/XMLSchema#decimal",
+			Integer => "http://www.w3.org/2001/XMLSchema#integer",
+			Long => "http://www.w3.org/2001/XMLSchema#long",
+			Int => "http://www.w3.org/2001/XMLSchema#int",
+			Short => "http://www.w3.org/2001/XMLSchema#short",
+			Byte => "http://www.w3.org/2001/XMLSchema#byte",
+			NonNegativeInteger => "http://www.w3.org/2001/XMLSchema#nonNegativeInteger",
+			PositiveInteger => "http://www.w3.org/2001/XMLSchema#positiveInteger",
+			NonPositiveInteger => "http://www.w3.org/2001/XMLSchema#nonPositiveInteger",
+			NegativeInteger => "http://www.w3.org/2001/XMLSchema#negativeInteger",
+			UnsignedLong => "http://www.w3.org/2001/XMLSchema#unsignedLong",
+			UnsignedInt => "http://www.w3.org/2001/XMLSchema#unsignedInt",
+			UnsignedShort => "http://www.w3.org/2001/XMLSchema#unsignedShort",
+			UnsignedByte => "http://www.w3.org/2001/XMLSchema#unsignedByte",
+			Double => "http://www.w3.org/2001/XMLSchema#double",
+			Float => "http://www.w3.org/2001/XMLSchema#float",
+			DateTime => "http://www.w3.org/2001/XMLSchema#dateTime",
+			Date => "http://www.w3.org/2001/XMLSchema#date",
+			AnyUri => "http://www.w3.org/2001/XMLSchema#anyURI"
+		}
+	}
+
+	/// Find the datatype identified by the given IRI, if any.
+	pub fn from_iri(iri: Iri) -> Option<Datatype> {
+		Self::from_iri_str(iri.as_str())
+	}
+
+	/// Find the datatype identified by the given IRI string, if any.
+	pub fn from_iri_str(iri: &str) -> Option<Datatype> {
+		use Datatype::*;
+		let name = iri.strip_prefix(XSD)?;
+		match name {
+			"string" => Some(String),
+			"boolean" => Some(Boolean),
+			"decimal" => Some(Decimal),
+			"integer" => Some(Integer),
+			"long" => Some(Long),
+			"int" => Some(Int),
+			"short" => Some(Short),
+			"byte" => Some(Byte),
+			"nonNegativeInteger" => Some(NonNegativeInteger),
+			"positiveInteger" => Some(PositiveInteger),
+			"nonPositiveInteger" => Some(NonPositiveInteger),
+			"negativeInteger" => Some(NegativeInteger),
+			"unsignedLong" => Some(UnsignedLong),
+			"unsignedInt" => Some(UnsignedInt),
+			"unsignedShort" => Some(UnsignedShort),
+			"unsignedByte" => Some(UnsignedByte),
+			"double" => Some(Double),
+			"float" => Some(Float),
+			"dateTime" => Some(DateTime),
+			"date" => Some(Date),
+			"anyURI" => Some(AnyUri),
+			_ => None
+		}
+	}
+
+	/// Checks if this datatype is `xsd:integer` or derived from it.
+	pub fn is_integer(&self) -> bool {
+		use Datatype::*;
+		matches!(self, Integer | Long | Int | Short | Byte | NonNegativeInteger | PositiveInteger | NonPositiveInteger | NegativeInteger | UnsignedLong | UnsignedInt | UnsignedShort | UnsignedByte)
+	}
+
+	/// Checks if this datatype is `xsd:decimal` or derived from it.
+	///
+	/// This includes every integer datatype.
+	pub fn is_decimal(&self) -> bool {
+		*self == Datatype::Decimal || self.is_integer()
+	}
+
+	/// Checks if this datatype is a numeric datatype.
+	pub fn is_numeric(&self) -> bool {
+		self.is_decimal() || *self == Datatype::Double || *self == Datatype::Float
+	}
+
+	/// Checks that the given string is a valid lexical form for this datatype.
+	///
+	/// For integer datatypes, this also checks that the value is in the datatype range.
+	pub fn is_valid(&self, lexical: &str) -> bool {
+		use Datatype::*;
+		match self {
+			String | AnyUri => true,
+			Boolean => is_boolean(lexical),
+			Decimal => is_decimal(lexical),
+			Double | Float => is_double(lexical),
+			DateTime => is_date_time(lexical),
+			Date => is_date(lexical),
+			_ => is_integer(lexical) && self.integer_in_range(lexical)
+		}
+	}
+
+	/// Checks that the given valid `xsd:integer` lexical form is in the range of this datatype.
+	fn integer_in_range(&self, lexical: &str) -> bool {
+		use Datatype::*;
+		let negative = lexical.starts_with('-');
+		let is_zero = lexical.bytes().all(|c| !(b'1'..=b'9').contains(&c));
+		match self {
+			NonNegativeInteger => !negative || is_zero,
+			PositiveInteger => !negative && !is_zero,
+			NonPositiveInteger => negative || is_zero,
+			NegativeInteger => negative && !is_zero,
+			_ => {
+				let (min, max): (i128, i128) = match self {
+					Long => (i64::MIN as i128, i64::MAX as i128),
+					Int => (i32::MIN as i128, i32::MAX as i128),
+					Short => (i16::MIN as i128, i16::MAX as i128),
+					Byte => (i8::MIN as i128, i8::MAX as i128),
+					UnsignedLong => (0, u64::MAX as i128),
+					UnsignedInt => (0, u32::MAX as i128),
+					UnsignedShort => (0, u16::MAX as i128),
+					UnsignedByte => (0, u8::MAX as i128),
+					_ => return true
+				};
+
+				match lexical.parse::<i128>() {
+					Ok(i) => min <= i && i <= max,
+					Err(_) => false
+				}
+			}
+		}
+	}
+
+	/// Returns the canonical form of the given lexical form.
+	///
+	/// Returns `None` if the input is not a valid lexical form for this datatype.
+	///
+	/// # Example
+	/// ```
+	/// use json_ld::xsd::Datatype;
+	///
+	/// assert_eq!(Datatype::Integer.canonicalize("+007"), Some("7".to_string()));
+	/// assert_eq!(Datatype::Decimal.canonicalize("01.500"), Some("1.5".to_string()));
+	/// assert_eq!(Datatype::Double.canonicalize("100"), Some("1.0E2".to_string()));
+	/// assert_eq!(Datatype::Float.canonicalize("0.1"), Some("1.0E-1".to_string()));
+	/// assert_eq!(Datatype::Float.canonicalize("3.4028235E38"), Some("3.4028235E38".to_string()));
+	/// assert_eq!(Datatype::Boolean.canonicalize("1"), Some("true".to_string()));
+	/// assert_eq!(Datatype::DateTime.canonicalize("2020-02-29T10:00:00.500+00:00"), Some("2020-02-29T10:00:00.5Z".to_string()));
+	/// assert_eq!(Datatype::Date.canonicalize("2021-02-29"), None);
+	/// assert_eq!(Datatype::Byte.canonicalize("128"), None);
+	/// ```
+	pub fn canonicalize(&self, lexical: &str) -> Option<std::string::String> {
+		use Datatype::*;
+		if !self.is_valid(lexical) {
+			return None
+		}
+
+		match self {
+			String | AnyUri => Some(lexical.to_string()),
+			Boolean => Some(match lexical {
+				"1" | "true" => "true".to_string(),
+				_ => "false".to_string()
+			}),
+			Decimal => self::Decimal::parse(lexical).map(|d| d.to_string()),
+			Double => parse_double(lexical).map(canonical_double),
+			Float => parse_float(lexical).map(canonical_float),
+			DateTime | Date => Some(canonical_date_time(lexical)),
+			_ => {
+				let negative = lexical.starts_with('-');
+				let digits = lexical.trim_start_matches(['+', '-']).trim_start_matches('0');
+				if digits.is_empty() {
+					Some("0".to_string())
+				} else if negative {
+					Some(format!("-{}", digits))
+				} else {
+					Some(digits.to_string())
+				}
+			}
+		}
+	}
+}
+
+impl AsIri for Datatype {
+	fn as_iri(&self) -> Iri<'_> {
+		Iri::new(self.as_str()).unwrap()
+	}
+}
+
+impl AsRef<str> for Datatype {
+	fn as_ref(&self) -> &str {
+		self.as_str()
+	}
+}
+
+impl<'a> TryFrom<Iri<'a>> for Datatype {
+	type Error = ();
+
+	fn try_from(iri: Iri<'a>) -> Result<Datatype, ()> {
+		Datatype::from_iri(iri).ok_or(())
+	}
+}
+
+impl fmt::Display for Datatype {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "xsd:{}", self.name())
+	}
+}
+
+/// Checks that the given string is a valid `xsd:boolean` lexical form.
+pub fn is_boolean(lexical: &str) -> bool {
+	matches!(lexical, "true" | "false" | "1" | "0")
+}
+
+/// Checks that the given string is a valid `xsd:integer` lexical form.
+pub fn is_integer(lexical: &str) -> bool {
+	let digits = lexical.strip_prefix(['+', '-']).unwrap_or(lexical);
+	!digits.is_empty() && digits.bytes().all(|c| c.is_ascii_digit())
+}
+
+/// Checks that the given string is a valid `xsd:decimal` lexical form.
+pub fn is_decimal(lexical: &str) -> bool {
+	let unsigned = lexical.strip_prefix(['+', '-']).unwrap_or(lexical);
+	let (integer, fraction) = match unsigned.find('.') {
+		Some(i) => (&unsigned[..i], &unsigned[(i+1)..]),
+		None => (unsigned, "")
+	};
+
+	(!integer.is_empty() || !fraction.is_empty())
+	&& integer.bytes().all(|c| c.is_ascii_digit())
+	&& fraction.bytes().all(|c| c.is_ascii_digit())
+}
+
+/// Checks that the given string is a valid `xsd:double` or `xsd:float` lexical form.
+pub fn is_double(lexical: &str) -> bool {
+	match lexical {
+		"INF" | "+INF" | "-INF" | "NaN" => true,
+		_ => {
+			let (mantissa, exponent) = match lexical.find(['e', 'E']) {
+				Some(i) => (&lexical[..i], Some(&lexical[(i+1)..])),
+				None => (lexical, None)
+			};
+
+			is_decimal(mantissa) && exponent.map(is_integer).unwrap_or(true)
+		}
+	}
+}
+
+/// Checks that the given string is a valid `xsd:dateTime` lexical form.
+pub fn is_date_time(lexical: &str) -> bool {
+	match lexical.find('T') {
+		Some(i) => {
+			let (time, timezone) = split_timezone(&lexical[(i+1)..]);
+			is_date_without_timezone(&lexical[..i]) && is_time(time) && is_timezone(timezone)
+		},
+		None => false
+	}
+}
+
+/// Checks that the given string is a valid `xsd:date` lexical form.
+pub fn is_date(lexical: &str) -> bool {
+	// The timezone, if any, starts after the day.
+	match date_prefix_len(lexical) {
+		Some(len) => is_date_without_timezone(&lexical[..len]) && is_timezone(&lexical[len..]),
+		None => false
+	}
+}
+
+/// Length of the `yyyy-mm-dd` prefix of a date, including the optional sign.
+fn date_prefix_len(lexical: &str) -> Option<usize> {
+	let unsigned = lexical.strip_prefix('-').unwrap_or(lexical);
+	let offset = lexical.len() - unsigned.len();
+	let year_len = unsigned.find('-')?;
+	let len = offset + year_len + 6;
+	if lexical.len() >= len && lexical.is_char_boundary(len) {
+		Some(len)
+	} else {
+		None
+	}
+}
+
+fn is_digits(s: &str, len: usize) -> bool {
+	s.len() == len && s.bytes().all(|c| c.is_ascii_digit())
+}
+
+fn two_digits(s: &str) -> Option<u32> {
+	if is_digits(s, 2) {
+		s.parse().ok()
+	} else {
+		None
+	}
+}
+
+fn is_date_without_timezone(lexical: &str) -> bool {
+	let unsigned = lexical.strip_prefix('-').unwrap_or(lexical);
+	let mut parts = unsigned.split('-');
+	match (parts.next(), parts.next(), parts.next(), parts.next()) {
+		(Some(year), Some(month), Some(day), None) => {
+			let year_valid = year.len() >= 4 && year.bytes().all(|c| c.is_ascii_digit()) && (year.len() == 4 || !year.starts_with('0'));
+			match (year_valid, two_digits(month), two_digits(day)) {
+				(true, Some(month), Some(day)) => {
+					let leap = year.parse::<u64>().map(|y| y % 4 == 0 && (y % 100 != 0 || y % 400 == 0)).unwrap_or(false);
+					let days = match month {
+						1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+						4 | 6 | 9 | 11 => 30,
+						2 if leap => 29,
+						2 => 28,
+						_ => return false
+					};
+
+					day >= 1 && day <= days
+				},
+				_ => false
+			}
+		},
+		_ => false
+	}
+}
+
+fn is_time(lexical: &str) -> bool {
+	let (time, fraction) = match lexical.find('.') {
+		Some(i) => (&lexical[..i], Some(&lexical[(i+1)..])),
+		None => (lexical, None)
+	};
+
+	let mut parts = time.split(':');
+	match (parts.next(), parts.next(), parts.next(), parts.next()) {
+		(Some(h), Some(m), Some(s), None) => {
+			match (two_digits(h), two_digits(m), two_digits(s)) {
+				(Some(h), Some(m), Some(s)) => {
+					let fraction_valid = match fraction {
+						Some(f) => !f.is_empty() && f.bytes().all(|c| c.is_ascii_digit()),
+						None => true
+					};
+
+					let end_of_day = h == 24 && m == 0 && s == 0 && fraction.map(|f| f.bytes().all(|c| c == b'0')).unwrap_or(true);
+					fraction_valid && ((h < 24 && m < 60 && s < 60) || end_of_day)
+				},
+				_ => false
+			}
+		},
+		_ => false
+	}
+}
+
+/// Splits a time (or date) and its optional timezone.
+fn split_timezone(lexical: &str) -> (&str, &str) {
+	match lexical.find(['Z', '+', '-']) {
+		Some(i) => (&lexical[..i], &lexical[i..]),
+		None => (lexical, "")
+	}
+}
+
+fn is_timezone(lexical: &str) -> bool {
+	match lexical {
+		"" | "Z" => true,
+		_ => {
+			let offset = match lexical.strip_prefix(['+', '-']) {
+				Some(offset) => offset,
+				None => return false
+			};
+
+			match offset.split_once(':') {
+				Some((h, m)) => match (two_digits(h), two_digits(m)) {
+					(Some(h), Some(m)) => (h < 14 && m < 60) || (h == 14 && m == 0),
+					_ => false
+				},
+				None => false
+			}
+		}
+	}
+}
+
+/// Canonical form of a valid `xsd:dateTime` or `xsd:date` lexical form.
+///
+/// Trailing zeros of fractional seconds are removed, and the `+00:00` and `-00:00`
+/// timezones are written `Z`.
+/// End of day times (`24:00:00`) are left untouched.
+fn canonical_date_time(lexical: &str) -> String {
+	let date_len = date_prefix_len(lexical).unwrap();
+	let (date, rest) = lexical.split_at(date_len);
+	let (time, timezone) = match rest.strip_prefix('T') {
+		Some(rest) => split_timezone(rest),
+		None => ("", rest)
+	};
+
+	let mut result = date.to_string();
+	if !time.is_empty() {
+		result.push('T');
+		match time.find('.') {
+			Some(i) => {
+				let fraction = time[(i+1)..].trim_end_matches('0');
+				result.push_str(&time[..i]);
+				if !fraction.is_empty() {
+					result.push('.');
+					result.push_str(fraction);
+				}
+			},
+			None => result.push_str(time)
+		}
+	}
+
+	match timezone {
+		"+00:00" | "-00:00" => result.push('Z'),
+		tz => result.push_str(tz)
+	}
+
+	result
+}
+
+/// Canonical form of a `xsd:double` value.
+///
+/// The result uses the scientific notation with at least one fractional digit, such as `1.0E2`.
+pub fn canonical_double(value: f64) -> String {
+	if value.is_nan() {
+		"NaN".to_string()
+	} else if value.is_infinite() {
+		if value > 0.0 { "INF".to_string() } else { "-INF".to_string() }
+	} else {
+		canonical_scientific(format!("{:E}", value))
+	}
+}
+
+/// Canonical form of a `xsd:float` value.
+///
+/// As with [`canonical_double`], but using the shortest representation of the `f32` value,
+/// such as `1.0E-1` for `0.1`.
+pub fn canonical_float(value: f32) -> String {
+	if value.is_nan() {
+		"NaN".to_string()
+	} else if value.is_infinite() {
+		if value > 0.0 { "INF".to_string() } else { "-INF".to_string() }
+	} else {
+		canonical_scientific(format!("{:E}", value))
+	}
+}
+
+/// Adds the fractional digit missing from a number formatted with `{:E}`.
+fn canonical_scientific(s: String) -> String {
+	let (mantissa, exponent) = s.split_at(s.find('E').unwrap());
+	if mantissa.contains('.') {
+		s
+	} else {
+		format!("{}.0{}", mantissa, exponent)
+	}
+}
+
+/// Parse an `xsd:double` lexical form.
+///
+/// Returns `None` if the input is not a valid lexical form.
+pub fn parse_double(lexical: &str) -> Option<f64> {
+	match lexical {
+		"INF" | "+INF" => Some(f64::INFINITY),
+		"-INF" => Some(f64::NEG_INFINITY),
+		"NaN" => Some(f64::NAN),
+		_ if is_double(lexical) => lexical.parse().ok(),
+		_ => None
+	}
+}
+
+/// Parse an `xsd:float` lexical form.
+///
+/// Returns `None` if the input is not a valid lexical form.
+/// The value is rounded directly to the nearest `f32`.
+pub fn parse_float(lexical: &str) -> Option<f32> {
+	match lexical {
+		"INF" | "+INF" => Some(f32::INFINITY),
+		"-INF" => Some(f32::NEG_INFINITY),
+		"NaN" => Some(f32::NAN),
+		_ if is_double(lexical) => lexical.parse().ok(),
+		_ => None
+	}
+}
+
+/// Lossless `xsd:decimal` value.
+///
+/// Decimal numbers are stored in their canonical form,
+/// so two `Decimal` are equal if and only if they denote the same number.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Decimal {
+	/// Is the number negative.
+	negative: bool,
+
+	/// Integer part digits, without leading zeros.
+	integer: String,
+
+	/// Fractional part digits, without trailing zeros.
+	fraction: String
+}
+
+impl Decimal {
+	/// Parse a decimal number from its lexical form.
+	///
+	/// Returns `None` if the input is not a valid `xsd:decimal` lexical form.
+	pub fn parse(lexical: &str) -> Option<Decimal> {
+		if !is_decimal(lexical) {
+			return None
+		}
+
+		let negative = lexical.starts_with('-');
+		let unsigned = lexical.strip_prefix(['+', '-']).unwrap_or(lexical);
+		let (integer, fraction) = match unsigned.find('.') {
+			Some(i) => (&unsigned[..i], &unsigned[(i+1)..]),
+			None => (unsigned, "")
+		};
+
+		let integer = integer.trim_start_matches('0').to_string();
+		let fraction = fraction.trim_end_matches('0').to_string();
+		let is_zero = integer.is_empty() && fraction.is_empty();
+
+		Some(Decimal {
+			negative: negative && !is_zero,
+			integer,
+			fraction
+		})
+	}
+
+	/// Checks if the number is negative.
+	pub fn is_negative(&self) -> bool {
+		self.negative
+	}
+
+	/// Checks if the number is an integer.
+	pub fn is_integer(&self) -> bool {
+		self.fraction.is_empty()
+	}
+
+	/// Returns the closest `f64` to this number.
+	pub fn to_f64(&self) -> f64 {
+		self.to_string().parse().unwrap()
+	}
+}
+
+impl fmt::Display for Decimal {
+	/// Writes the canonical form of the decimal number.
+	///
+	/// Following the XML Schema 1.1 specification,
+	/// integral numbers are written without decimal point.
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.negative {
+			write!(f, "-")?;
+		}
+
+		if self.integer.is_empty() {
+			write!(f, "0")?;
+		} else {
+			write!(f, "{}", self.integer)?;
+		}
+
+		if !self.fraction.is_empty() {
+			write!(f, ".{}", self.fraction)?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Components of a `xsd:dateTime` or `xsd:date` value.
+///
+/// # Example
+/// ```
+/// use json_ld::xsd::DateTime;
+/// let value = DateTime::parse("2020-01-01T24:00:00.5Z");
+/// assert!(value.is_none());
+///
+/// let value = DateTime::parse("12020-01-01T10:30:00.25+02:00").unwrap();
+/// assert_eq!((value.year, value.hour, value.nanosecond, value.timezone), (12020, 10, 250_000_000, Some(120)));
+///
+/// let value = DateTime::parse_date("-0044-03-15").unwrap();
+/// assert_eq!((value.year, value.month, value.day, value.timezone), (-44, 3, 15, None));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DateTime {
+	/// Year, where `0` is the year before `1`.
+	pub year: i64,
+
+	/// Month, from 1 to 12.
+	pub month: u32,
+
+	/// Day of the month, from 1.
+	pub day: u32,
+
+	/// Hour, from 0 to 24 (`24:00:00` is the end of the day).
+	///
+	/// Always 0 for dates.
+	pub hour: u32,
+
+	/// Minute.
+	pub minute: u32,
+
+	/// Second.
+	pub second: u32,
+
+	/// Fractional seconds in nanoseconds.
+	///
+	/// Smaller fractions of seconds are truncated.
+	pub nanosecond: u32,
+
+	/// Timezone offset in minutes, if any.
+	pub timezone: Option<i32>
+}
+
+impl DateTime {
+	/// Parse a `xsd:dateTime` lexical form.
+	///
+	/// Returns `None` if the input is not a valid `xsd:dateTime` lexical form,
+	/// or if its year does not fit in an `i64`.
+	pub fn parse(lexical: &str) -> Option<DateTime> {
+		if !is_date_time(lexical) {
+			return None
+		}
+
+		let i = lexical.find('T').unwrap();
+		let (time, timezone) = split_timezone(&lexical[(i+1)..]);
+		let (time, fraction) = match time.find('.') {
+			Some(j) => (&time[..j], &time[(j+1)..]),
+			None => (time, "")
+		};
+
+		let mut nanosecond = 0;
+		for k in 0..9 {
+			let digit = fraction.as_bytes().get(k).map(|c| (c - b'0') as u32).unwrap_or(0);
+			nanosecond = nanosecond * 10 + digit
+		}
+
+		let mut result = Self::parse_date(&lexical[..i])?;
+		result.hour = time[0..2].parse().unwrap();
+		result.minute = time[3..5].parse().unwrap();
+		result.second = time[6..8].parse().unwrap();
+		result.nanosecond = nanosecond;
+		result.timezone = parse_timezone(timezone);
+		Some(result)
+	}
+
+	/// Parse a `xsd:date` lexical form.
+	///
+	/// Returns `None` if the input is not a valid `xsd:date` lexical form,
+	/// or if its year does not fit in an `i64`.
+	pub fn parse_date(lexical: &str) -> Option<DateTime> {
+		if !is_date(lexical) {
+			return None
+		}
+
+		let len = date_prefix_len(lexical).unwrap();
+		let (date, timezone) = lexical.split_at(len);
+		let (year, month_day) = date.split_at(len - 6);
+
+		Some(DateTime {
+			year: year.parse().ok()?,
+			month: month_day[1..3].parse().unwrap(),
+			day: month_day[4..6].parse().unwrap(),
+			hour: 0,
+			minute: 0,
+			second: 0,
+			nanosecond: 0,
+			timezone: parse_timezone(timezone)
+		})
+	}
+}
+
+/// Timezone offset in minutes of a valid timezone.
+fn parse_timezone(lexical: &str) -> Option<i32> {
+	match lexical {
+		"" => None,
+		"Z" => Some(0),
+		_ => {
+			let hours: i32 = lexical[1..3].parse().unwrap();
+			let minutes: i32 = lexical[4..6].parse().unwrap();
+			let offset = hours * 60 + minutes;
+			if lexical.starts_with('-') {
+				Some(-offset)
+			} else {
+				Some(offset)
+			}
+		}
+	}
+}